@@ -0,0 +1,201 @@
+use crate::helpers::response_structs::{
+    CallResponse, DeclareResponse, DeployResponse, InvokeResponse,
+};
+use crate::helpers::retry_transport::RetryTransport;
+use crate::helpers::scarb_utils::CastConfig;
+use crate::helpers::signer::CastSigner;
+use crate::starknet_commands::declare::ContractSource;
+use crate::starknet_commands::{call, declare, deploy, invoke};
+use crate::{get_account_with_signer, get_block_id, WaitForTx};
+use anyhow::Result;
+use camino::Utf8PathBuf;
+use starknet::accounts::{Call, SingleOwnerAccount};
+use starknet::core::types::{BlockId, FieldElement};
+use starknet::providers::JsonRpcClient;
+
+/// Max fee and nonce of a sent transaction; when not set, the fee is estimated
+/// (and multiplied by the fee multiplier from the config) and the nonce is fetched from the network
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TransactionOptions {
+    pub max_fee: Option<FieldElement>,
+    pub nonce: Option<FieldElement>,
+}
+
+/// Runs sncast commands from Rust code, using the account, fee multiplier and wait parameters from the config.
+/// The provider can be created with `get_provider_with_fallbacks` from the RPC urls in the config
+pub struct SncastClient<'a> {
+    provider: &'a JsonRpcClient<RetryTransport>,
+    config: CastConfig,
+    wait: bool,
+}
+
+impl<'a> SncastClient<'a> {
+    #[must_use]
+    pub fn new(provider: &'a JsonRpcClient<RetryTransport>, config: CastConfig) -> Self {
+        SncastClient {
+            provider,
+            config,
+            wait: false,
+        }
+    }
+
+    /// If set, commands sending transactions wait until the transaction is accepted
+    #[must_use]
+    pub fn wait(mut self, wait: bool) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    #[must_use]
+    pub fn provider(&self) -> &'a JsonRpcClient<RetryTransport> {
+        self.provider
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &CastConfig {
+        &self.config
+    }
+
+    pub async fn account(
+        &self,
+    ) -> Result<SingleOwnerAccount<&'a JsonRpcClient<RetryTransport>, CastSigner>> {
+        let (account, _) = self.account_with_signer().await?;
+        Ok(account)
+    }
+
+    pub async fn account_with_signer(
+        &self,
+    ) -> Result<(
+        SingleOwnerAccount<&'a JsonRpcClient<RetryTransport>, CastSigner>,
+        CastSigner,
+    )> {
+        get_account_with_signer(
+            &self.config.account,
+            &self.config.accounts_files,
+            self.provider,
+            &self.config.keystore,
+            &self.config.signer_command,
+        )
+        .await
+    }
+
+    /// `path_to_scarb_toml` is used to build the contract when it is declared by name.
+    /// Unless `force` is set, no transaction is sent if the class is already declared
+    pub async fn declare(
+        &self,
+        contract: &ContractSource,
+        options: TransactionOptions,
+        path_to_scarb_toml: &Option<Utf8PathBuf>,
+        force: bool,
+    ) -> Result<DeclareResponse> {
+        let account = self.account().await?;
+        declare::declare(
+            contract,
+            options.max_fee,
+            self.config.fee_multiplier,
+            options.nonce,
+            &account,
+            path_to_scarb_toml,
+            self.wait_config(),
+            force,
+        )
+        .await
+    }
+
+    /// Deploys the contract with the Universal Deployer Contract; a random salt is used if none is passed.
+    /// Unless `skip_validation` is set, it is checked that the class is declared first
+    pub async fn deploy(
+        &self,
+        class_hash: FieldElement,
+        constructor_calldata: Vec<FieldElement>,
+        salt: Option<FieldElement>,
+        unique: bool,
+        skip_validation: bool,
+        options: TransactionOptions,
+    ) -> Result<DeployResponse> {
+        let account = self.account().await?;
+        deploy::deploy(
+            class_hash,
+            constructor_calldata,
+            salt,
+            unique,
+            options.max_fee,
+            self.config.fee_multiplier,
+            options.nonce,
+            skip_validation,
+            &account,
+            self.wait_config(),
+        )
+        .await
+    }
+
+    /// The fee is estimated on `block_id`, while the transaction is always sent against the pending block
+    pub async fn invoke(
+        &self,
+        contract_address: FieldElement,
+        selector: FieldElement,
+        calldata: Vec<FieldElement>,
+        block_id: BlockId,
+        options: TransactionOptions,
+    ) -> Result<InvokeResponse> {
+        let account = self.account().await?;
+        invoke::invoke(
+            contract_address,
+            selector,
+            calldata,
+            options.max_fee,
+            self.config.fee_multiplier,
+            options.nonce,
+            block_id,
+            &account,
+            self.wait_config(),
+        )
+        .await
+    }
+
+    /// Executes all the calls atomically in a single transaction; like in `invoke`, the fee is estimated on `block_id`
+    pub async fn execute(
+        &self,
+        calls: Vec<Call>,
+        block_id: BlockId,
+        options: TransactionOptions,
+    ) -> Result<InvokeResponse> {
+        let account = self.account().await?;
+        invoke::execute_calls(
+            &account,
+            calls,
+            options.max_fee,
+            self.config.fee_multiplier,
+            options.nonce,
+            block_id,
+            self.wait_config(),
+        )
+        .await
+    }
+
+    /// Calls the function on the `default_block_id` block from the config
+    pub async fn call(
+        &self,
+        contract_address: FieldElement,
+        selector: FieldElement,
+        calldata: Vec<FieldElement>,
+    ) -> Result<CallResponse> {
+        let block_id = get_block_id(&self.config.default_block_id)?;
+        call::call(
+            contract_address,
+            selector,
+            calldata,
+            self.provider,
+            &block_id,
+            None,
+        )
+        .await
+    }
+
+    fn wait_config(&self) -> WaitForTx {
+        WaitForTx {
+            wait: self.wait,
+            wait_params: self.config.wait_params,
+        }
+    }
+}
@@ -0,0 +1,804 @@
+use crate::helpers::retry_transport::RetryTransport;
+use crate::{handle_rpc_error, parse_number};
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8PathBuf;
+use primitive_types::U256;
+use serde_json::{Map, Value};
+use starknet::core::types::contract::{AbiEntry, AbiFunction, AbiNamedMember};
+use starknet::core::types::{BlockId, ContractClass, FieldElement};
+use starknet::core::utils::cairo_short_string_to_felt;
+use starknet::providers::{JsonRpcClient, Provider};
+use std::slice::Iter;
+
+const U256_TYPE: &str = "core::integer::u256";
+const UNSIGNED_INTEGER_TYPE_PREFIX: &str = "core::integer::u";
+const BOOL_TYPE: &str = "core::bool";
+const ARRAY_TYPE_PREFIXES: [&str; 2] = ["core::array::Array::<", "core::array::Span::<"];
+
+/// Parses an ABI from either a plain ABI JSON array or a Sierra contract class artifact
+pub fn parse_abi(contents: &str) -> Result<Vec<AbiEntry>> {
+    let value: Value = serde_json::from_str(contents).context("Failed to parse ABI as JSON")?;
+
+    let abi = match value {
+        Value::Object(mut class) => match class.remove("abi") {
+            Some(Value::String(abi)) => serde_json::from_str(&abi)?,
+            Some(abi @ Value::Array(_)) => abi,
+            _ => bail!("No `abi` field found in contract class"),
+        },
+        abi @ Value::Array(_) => abi,
+        _ => bail!("ABI must be a JSON array or a contract class object"),
+    };
+
+    serde_json::from_value(abi).context("Failed to parse ABI entries")
+}
+
+pub fn read_abi_file(path: &Utf8PathBuf) -> Result<Vec<AbiEntry>> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read ABI file {path}"))?;
+    parse_abi(&contents).with_context(|| format!("Failed to load ABI from {path}"))
+}
+
+pub async fn get_contract_abi(
+    provider: &JsonRpcClient<RetryTransport>,
+    contract_address: FieldElement,
+    block_id: &BlockId,
+) -> Result<Vec<AbiEntry>> {
+    let class = match provider.get_class_at(block_id, contract_address).await {
+        Ok(class) => class,
+        Err(error) => return handle_rpc_error(error),
+    };
+
+    match class {
+        ContractClass::Sierra(class) => parse_abi(&class.abi),
+        ContractClass::Legacy(_) => {
+            bail!("Encoding arguments with ABI is not supported for Cairo 0 contracts")
+        }
+    }
+}
+
+#[must_use]
+pub fn find_function<'a>(abi: &'a [AbiEntry], name: &str) -> Option<&'a AbiFunction> {
+    abi.iter().find_map(|entry| match entry {
+        AbiEntry::Function(function) if function.name == name => Some(function),
+        AbiEntry::Interface(interface) => find_function(&interface.items, name),
+        _ => None,
+    })
+}
+
+/// Like `find_function`, but fails listing the functions available in the ABI if the function is not found
+pub fn get_function<'a>(abi: &'a [AbiEntry], name: &str) -> Result<&'a AbiFunction> {
+    find_function(abi, name).ok_or_else(|| {
+        let mut names = vec![];
+        function_names(abi, &mut names);
+        match names.as_slice() {
+            [] => anyhow!("Function {name} not found in the contract ABI, which has no functions"),
+            names => anyhow!(
+                "Function {name} not found in the contract ABI. Available functions: {}",
+                names.join(", ")
+            ),
+        }
+    })
+}
+
+fn function_names<'a>(abi: &'a [AbiEntry], names: &mut Vec<&'a str>) {
+    for entry in abi {
+        match entry {
+            AbiEntry::Function(function) => names.push(&function.name),
+            AbiEntry::Interface(interface) => function_names(&interface.items, names),
+            _ => {}
+        }
+    }
+}
+
+/// Splits `name:value` pairs into a JSON object. Values that look like JSON objects or arrays
+/// are parsed as JSON, everything else is kept as a string
+pub fn parse_named_arguments(arguments: &[String]) -> Result<Map<String, Value>> {
+    let mut parsed = Map::new();
+
+    for argument in arguments {
+        let (name, value) = argument
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Invalid argument {argument}, expected `name:value`"))?;
+        let value = parse_argument_value(value)
+            .with_context(|| format!("Failed to parse value of argument {name} as JSON"))?;
+
+        if parsed.insert(name.trim().to_string(), value).is_some() {
+            bail!("Argument {name} passed more than once");
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_argument_value(value: &str) -> Result<Value> {
+    let value = value.trim();
+    if value.starts_with('{') || value.starts_with('[') {
+        Ok(serde_json::from_str(value)?)
+    } else {
+        Ok(Value::String(value.to_string()))
+    }
+}
+
+/// Splits positional arguments passed with `--calldata`: values that look like JSON objects or arrays
+/// are single arguments, other values may hold multiple whitespace separated arguments
+pub fn parse_positional_arguments(values: &[String]) -> Result<Vec<Value>> {
+    let mut arguments = vec![];
+    for value in values {
+        let trimmed = value.trim();
+        if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            arguments.push(
+                parse_argument_value(trimmed)
+                    .with_context(|| format!("Failed to parse argument {trimmed} as JSON"))?,
+            );
+        } else {
+            arguments.extend(
+                trimmed
+                    .split_whitespace()
+                    .map(|argument| Value::String(argument.to_string())),
+            );
+        }
+    }
+    Ok(arguments)
+}
+
+/// Parses a single argument value (e.g. entered interactively) and checks that it can be serialized as `type_name`
+pub fn parse_typed_argument(abi: &[AbiEntry], type_name: &str, value: &str) -> Result<Value> {
+    let value = parse_argument_value(value).context("Failed to parse value as JSON")?;
+    encode_value(abi, type_name, &value, &mut vec![])?;
+    Ok(value)
+}
+
+pub fn parse_arguments_json(arguments: &str) -> Result<Map<String, Value>> {
+    match serde_json::from_str(arguments).context("Failed to parse arguments as JSON")? {
+        Value::Object(arguments) => Ok(arguments),
+        _ => bail!("Arguments must be a JSON object mapping argument names to values"),
+    }
+}
+
+/// Serializes named arguments according to Cairo serialization rules, in the order of function inputs
+pub fn encode_function_arguments(
+    abi: &[AbiEntry],
+    function_name: &str,
+    arguments: &Map<String, Value>,
+) -> Result<Vec<FieldElement>> {
+    let function = get_function(abi, function_name)?;
+
+    encode_members(abi, &function.inputs, arguments, function_name)
+}
+
+/// Serializes arguments passed in the order of function inputs, validating their number and types against the ABI
+pub fn encode_positional_arguments(
+    abi: &[AbiEntry],
+    function_name: &str,
+    arguments: &[Value],
+) -> Result<Vec<FieldElement>> {
+    let function = get_function(abi, function_name)?;
+
+    if arguments.len() != function.inputs.len() {
+        bail!(
+            "Function {function_name} expects {} arguments, got {}; expected signature: {}",
+            function.inputs.len(),
+            arguments.len(),
+            function_signature(function)
+        );
+    }
+
+    let mut calldata = vec![];
+    for (input, argument) in function.inputs.iter().zip(arguments) {
+        encode_value(abi, &input.r#type, argument, &mut calldata).with_context(|| {
+            format!(
+                "Failed to encode argument {}; expected signature: {}",
+                input.name,
+                function_signature(function)
+            )
+        })?;
+    }
+
+    Ok(calldata)
+}
+
+#[must_use]
+pub fn function_signature(function: &AbiFunction) -> String {
+    let inputs: Vec<String> = function
+        .inputs
+        .iter()
+        .map(|input| format!("{}: {}", input.name, input.r#type))
+        .collect();
+    format!("{}({})", function.name, inputs.join(", "))
+}
+
+fn encode_members(
+    abi: &[AbiEntry],
+    members: &[AbiNamedMember],
+    values: &Map<String, Value>,
+    context: &str,
+) -> Result<Vec<FieldElement>> {
+    if let Some(unknown) = values
+        .keys()
+        .find(|name| !members.iter().any(|member| &member.name == *name))
+    {
+        bail!("Unknown argument {unknown} for {context}");
+    }
+
+    let mut calldata = vec![];
+    for member in members {
+        let value = values
+            .get(&member.name)
+            .ok_or_else(|| anyhow!("Missing argument {} for {context}", member.name))?;
+        encode_value(abi, &member.r#type, value, &mut calldata)
+            .with_context(|| format!("Failed to encode argument {}", member.name))?;
+    }
+
+    Ok(calldata)
+}
+
+fn encode_value(
+    abi: &[AbiEntry],
+    type_name: &str,
+    value: &Value,
+    calldata: &mut Vec<FieldElement>,
+) -> Result<()> {
+    if type_name == "()" {
+        return Ok(());
+    }
+    if type_name == U256_TYPE {
+        let (low, high) = parse_u256(value)?;
+        calldata.extend([low, high]);
+        return Ok(());
+    }
+    if type_name == BOOL_TYPE {
+        let value = match value {
+            Value::Bool(value) => *value,
+            Value::String(value) if value == "true" => true,
+            Value::String(value) if value == "false" => false,
+            _ => bail!("Expected a boolean, got {value}"),
+        };
+        calldata.push(FieldElement::from(u8::from(value)));
+        return Ok(());
+    }
+    if let Some(inner_type) = array_inner_type(type_name) {
+        let Value::Array(items) = value else {
+            bail!("Expected an array for type {type_name}, got {value}");
+        };
+        calldata.push(FieldElement::from(items.len()));
+        for item in items {
+            encode_value(abi, inner_type, item, calldata)?;
+        }
+        return Ok(());
+    }
+    if let Some(types) = tuple_types(type_name) {
+        let Value::Array(items) = value else {
+            bail!("Expected an array for tuple type {type_name}, got {value}");
+        };
+        if items.len() != types.len() {
+            bail!(
+                "Expected {} elements for tuple type {type_name}, got {}",
+                types.len(),
+                items.len()
+            );
+        }
+        for (item_type, item) in types.iter().zip(items) {
+            encode_value(abi, item_type, item, calldata)?;
+        }
+        return Ok(());
+    }
+
+    for entry in abi {
+        match entry {
+            AbiEntry::Struct(abi_struct) if abi_struct.name == type_name => {
+                let Value::Object(members) = value else {
+                    bail!("Expected an object for struct {type_name}, got {value}");
+                };
+                calldata.extend(encode_members(
+                    abi,
+                    &abi_struct.members,
+                    members,
+                    type_name,
+                )?);
+                return Ok(());
+            }
+            AbiEntry::Enum(abi_enum) if abi_enum.name == type_name => {
+                let (variant_name, payload) = match value {
+                    Value::String(variant) => (variant.as_str(), &Value::Null),
+                    Value::Object(variant) if variant.len() == 1 => {
+                        let (name, payload) = variant.iter().next().expect("checked length");
+                        (name.as_str(), payload)
+                    }
+                    _ => bail!("Expected a variant name or a single-key object for enum {type_name}, got {value}"),
+                };
+                let (index, variant) = abi_enum
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .find(|(_, variant)| variant.name == variant_name)
+                    .ok_or_else(|| anyhow!("Unknown variant {variant_name} of enum {type_name}"))?;
+
+                calldata.push(FieldElement::from(index));
+                encode_value(abi, &variant.r#type, payload, calldata)?;
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+
+    calldata.push(parse_felt(value)?);
+    Ok(())
+}
+
+/// Decodes the values returned by a function according to its ABI output types.
+/// Structs are decoded to objects, arrays and tuples to lists, unsigned integers to decimal strings
+/// and other felts to hex strings
+pub fn decode_function_outputs(
+    abi: &[AbiEntry],
+    function_name: &str,
+    response: &[FieldElement],
+) -> Result<Value> {
+    let function = get_function(abi, function_name)?;
+
+    let mut values = response.iter();
+    let mut decoded = function
+        .outputs
+        .iter()
+        .map(|output| decode_value(abi, &output.r#type, &mut values))
+        .collect::<Result<Vec<Value>>>()?;
+
+    if values.len() != 0 {
+        bail!(
+            "Response contains {} values not described by the outputs of {function_name}",
+            values.len()
+        );
+    }
+
+    Ok(if decoded.len() == 1 {
+        decoded.remove(0)
+    } else {
+        Value::Array(decoded)
+    })
+}
+
+/// Checks that the serialized constructor calldata can be decoded to the constructor inputs
+/// from the ABI, without leftover values. ABIs without a constructor accept no calldata
+pub fn validate_constructor_calldata(abi: &[AbiEntry], calldata: &[FieldElement]) -> Result<()> {
+    let inputs = abi
+        .iter()
+        .find_map(|entry| match entry {
+            AbiEntry::Constructor(constructor) => Some(constructor.inputs.as_slice()),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    let mut values = calldata.iter();
+    for input in inputs {
+        decode_value(abi, &input.r#type, &mut values).with_context(|| {
+            format!(
+                "Invalid constructor calldata for input {}: {}",
+                input.name, input.r#type
+            )
+        })?;
+    }
+
+    if values.len() != 0 {
+        bail!(
+            "Constructor calldata contains {} values not described by the constructor inputs",
+            values.len()
+        );
+    }
+    Ok(())
+}
+
+fn decode_value(
+    abi: &[AbiEntry],
+    type_name: &str,
+    values: &mut Iter<FieldElement>,
+) -> Result<Value> {
+    if type_name == "()" {
+        return Ok(Value::Null);
+    }
+    if type_name == U256_TYPE {
+        let low = next_value(values, type_name)?;
+        let high = next_value(values, type_name)?;
+        let number = (U256::from_big_endian(&high.to_bytes_be()) << 128)
+            + U256::from_big_endian(&low.to_bytes_be());
+        return Ok(Value::String(number.to_string()));
+    }
+    if type_name == BOOL_TYPE {
+        let value = next_value(values, type_name)?;
+        if value == FieldElement::ZERO {
+            return Ok(Value::Bool(false));
+        }
+        if value == FieldElement::ONE {
+            return Ok(Value::Bool(true));
+        }
+        bail!("Invalid value {value:#x} for type {type_name}");
+    }
+    if let Some(inner_type) = array_inner_type(type_name) {
+        let length = next_value(values, type_name)?;
+        let length = felt_to_usize(length)
+            .ok_or_else(|| anyhow!("Invalid length {length:#x} of type {type_name}"))?;
+        return (0..length)
+            .map(|_| decode_value(abi, inner_type, values))
+            .collect::<Result<Vec<Value>>>()
+            .map(Value::Array);
+    }
+    if let Some(types) = tuple_types(type_name) {
+        return types
+            .iter()
+            .map(|item_type| decode_value(abi, item_type, values))
+            .collect::<Result<Vec<Value>>>()
+            .map(Value::Array);
+    }
+
+    for entry in abi {
+        match entry {
+            AbiEntry::Struct(abi_struct) if abi_struct.name == type_name => {
+                let mut members = Map::new();
+                for member in &abi_struct.members {
+                    members.insert(
+                        member.name.clone(),
+                        decode_value(abi, &member.r#type, values)?,
+                    );
+                }
+                return Ok(Value::Object(members));
+            }
+            AbiEntry::Enum(abi_enum) if abi_enum.name == type_name => {
+                let index = next_value(values, type_name)?;
+                let variant = felt_to_usize(index)
+                    .and_then(|index| abi_enum.variants.get(index))
+                    .ok_or_else(|| anyhow!("Invalid variant {index:#x} of enum {type_name}"))?;
+
+                // variants without data are decoded to their names, the same way they are passed as arguments
+                return match decode_value(abi, &variant.r#type, values)? {
+                    Value::Null => Ok(Value::String(variant.name.clone())),
+                    payload => Ok(Value::Object(Map::from_iter([(
+                        variant.name.clone(),
+                        payload,
+                    )]))),
+                };
+            }
+            _ => {}
+        }
+    }
+
+    let value = next_value(values, type_name)?;
+    if type_name.starts_with(UNSIGNED_INTEGER_TYPE_PREFIX) {
+        return Ok(Value::String(format!("{value:#}")));
+    }
+    Ok(Value::String(format!("{value:#x}")))
+}
+
+fn felt_to_usize(value: FieldElement) -> Option<usize> {
+    let bytes = value.to_bytes_be();
+    let (high, low) = bytes.split_at(bytes.len() - 8);
+    if high.iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    usize::try_from(u64::from_be_bytes(low.try_into().ok()?)).ok()
+}
+
+fn next_value(values: &mut Iter<FieldElement>, type_name: &str) -> Result<FieldElement> {
+    values
+        .next()
+        .copied()
+        .ok_or_else(|| anyhow!("Not enough values in the response to decode type {type_name}"))
+}
+
+fn parse_felt(value: &Value) -> Result<FieldElement> {
+    match value {
+        Value::String(value) => match value
+            .strip_prefix('\'')
+            .and_then(|value| value.strip_suffix('\''))
+        {
+            Some(text) => cairo_short_string_to_felt(text)
+                .with_context(|| format!("Failed to encode {value} as a Cairo short string")),
+            None => parse_number(value)
+                .with_context(|| format!("Failed to parse {value} to FieldElement")),
+        },
+        Value::Number(number) => number
+            .as_u64()
+            .map(FieldElement::from)
+            .ok_or_else(|| anyhow!("Number {number} is out of range, pass it as a string instead")),
+        _ => bail!("Expected a number, got {value}"),
+    }
+}
+
+fn parse_u256(value: &Value) -> Result<(FieldElement, FieldElement)> {
+    let number = match value {
+        Value::String(value) => match value.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16),
+            None => U256::from_dec_str(value),
+        }
+        .map_err(|_| anyhow!("Failed to parse {value} to u256"))?,
+        Value::Number(number) => U256::from(number.as_u64().ok_or_else(|| {
+            anyhow!("Number {number} is out of range, pass it as a string instead")
+        })?),
+        _ => bail!("Expected a number, got {value}"),
+    };
+
+    Ok((
+        FieldElement::from(number.low_u128()),
+        FieldElement::from((number >> 128).low_u128()),
+    ))
+}
+
+fn array_inner_type(type_name: &str) -> Option<&str> {
+    ARRAY_TYPE_PREFIXES.iter().find_map(|prefix| {
+        type_name
+            .strip_prefix(prefix)
+            .and_then(|inner| inner.strip_suffix('>'))
+    })
+}
+
+fn tuple_types(type_name: &str) -> Option<Vec<&str>> {
+    let inner = type_name.strip_prefix('(')?.strip_suffix(')')?;
+
+    let mut types = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, character) in inner.char_indices() {
+        match character {
+            '(' | '<' => depth += 1,
+            ')' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                types.push(inner[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    types.push(inner[start..].trim());
+
+    Some(types.into_iter().filter(|t| !t.is_empty()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::abi::{
+        decode_function_outputs, encode_function_arguments, encode_positional_arguments, parse_abi,
+        parse_named_arguments, parse_positional_arguments, parse_typed_argument,
+        validate_constructor_calldata,
+    };
+    use serde_json::json;
+    use starknet::core::types::FieldElement;
+
+    const ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "contracts::Point",
+            "members": [
+                { "name": "x", "type": "core::felt252" },
+                { "name": "y", "type": "core::integer::u256" }
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "contracts::Direction",
+            "variants": [
+                { "name": "Up", "type": "()" },
+                { "name": "Down", "type": "core::felt252" }
+            ]
+        },
+        {
+            "type": "interface",
+            "name": "contracts::IContract",
+            "items": [
+                {
+                    "type": "function",
+                    "name": "put",
+                    "inputs": [
+                        { "name": "amount", "type": "core::integer::u256" },
+                        { "name": "points", "type": "core::array::Array::<contracts::Point>" },
+                        { "name": "direction", "type": "contracts::Direction" },
+                        { "name": "flag", "type": "core::bool" },
+                        { "name": "pair", "type": "(core::felt252, core::integer::u8)" }
+                    ],
+                    "outputs": [],
+                    "state_mutability": "external"
+                },
+                {
+                    "type": "function",
+                    "name": "get",
+                    "inputs": [],
+                    "outputs": [
+                        { "type": "(core::array::Array::<contracts::Point>, contracts::Direction, core::integer::u8)" }
+                    ],
+                    "state_mutability": "view"
+                }
+            ]
+        }
+    ]"#;
+
+    #[test]
+    fn test_encode_function_arguments() {
+        let abi = parse_abi(ABI).unwrap();
+        let arguments = parse_named_arguments(&[
+            "amount:0x100000000000000000000000000000002".to_string(),
+            r#"points:[{"x": "0x1", "y": "3"}]"#.to_string(),
+            r#"direction:{"Down": "7"}"#.to_string(),
+            "flag:true".to_string(),
+            r#"pair:["0x5", 6]"#.to_string(),
+        ])
+        .unwrap();
+
+        let calldata = encode_function_arguments(&abi, "put", &arguments).unwrap();
+
+        let expected: Vec<FieldElement> = [2, 1, 1, 1, 3, 0, 1, 7, 1, 5, 6]
+            .into_iter()
+            .map(FieldElement::from)
+            .collect();
+        assert_eq!(calldata, expected);
+    }
+
+    #[test]
+    fn test_encode_u256_larger_than_felt() {
+        let abi = parse_abi(ABI).unwrap();
+        let mut arguments = parse_named_arguments(&[
+            format!("amount:{}", "0x".to_string() + &"f".repeat(64)),
+            "points:[]".to_string(),
+            "direction:Up".to_string(),
+            "flag:false".to_string(),
+            "pair:[1, 2]".to_string(),
+        ])
+        .unwrap();
+
+        let calldata = encode_function_arguments(&abi, "put", &arguments).unwrap();
+        let u128_max = FieldElement::from(u128::MAX);
+        assert_eq!(calldata[..2], [u128_max, u128_max]);
+        assert_eq!(calldata[2], FieldElement::ZERO);
+
+        arguments.remove("flag");
+        let err = encode_function_arguments(&abi, "put", &arguments).unwrap_err();
+        assert!(err.to_string().contains("Missing argument flag for put"));
+    }
+
+    #[test]
+    fn test_encode_positional_arguments() {
+        let abi = parse_abi(ABI).unwrap();
+        let arguments = parse_positional_arguments(&[
+            "0x100000000000000000000000000000002".to_string(),
+            r#"[{"x": "'ab'", "y": "3"}]"#.to_string(),
+            r#"{"Down": "7"}"#.to_string(),
+            "true".to_string(),
+            r#"["0x5", 6]"#.to_string(),
+        ])
+        .unwrap();
+
+        let calldata = encode_positional_arguments(&abi, "put", &arguments).unwrap();
+
+        let expected: Vec<FieldElement> = [2, 1, 1, 0x6162, 3, 0, 1, 7, 1, 5, 6]
+            .into_iter()
+            .map(FieldElement::from)
+            .collect();
+        assert_eq!(calldata, expected);
+    }
+
+    #[test]
+    fn test_encode_positional_arguments_invalid() {
+        let abi = parse_abi(ABI).unwrap();
+        let signature = "put(amount: core::integer::u256, points: core::array::Array::<contracts::Point>, direction: contracts::Direction, flag: core::bool, pair: (core::felt252, core::integer::u8))";
+
+        let arguments = parse_positional_arguments(&["0x1 0x2".to_string()]).unwrap();
+        let err = encode_positional_arguments(&abi, "put", &arguments).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Function put expects 5 arguments, got 2; expected signature: {signature}")
+        );
+
+        let arguments =
+            parse_positional_arguments(&["1", "[]", "Up", "maybe", "[1, 2]"].map(String::from))
+                .unwrap();
+        let err = encode_positional_arguments(&abi, "put", &arguments).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Failed to encode argument flag; expected signature: {signature}")
+        );
+    }
+
+    #[test]
+    fn test_parse_typed_argument() {
+        let abi = parse_abi(ABI).unwrap();
+
+        assert_eq!(
+            parse_typed_argument(&abi, "core::integer::u256", "0x5").unwrap(),
+            json!("0x5")
+        );
+        assert_eq!(
+            parse_typed_argument(&abi, "contracts::Point", r#"{"x": "0x1", "y": "3"}"#).unwrap(),
+            json!({"x": "0x1", "y": "3"})
+        );
+        assert!(parse_typed_argument(&abi, "contracts::Point", "0x1").is_err());
+        assert!(parse_typed_argument(&abi, "contracts::Direction", "Left").is_err());
+    }
+
+    #[test]
+    fn test_parse_abi_from_contract_class() {
+        let class = json!({ "sierra_program": [], "abi": ABI }).to_string();
+
+        assert_eq!(parse_abi(&class).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_unknown_function() {
+        let abi = parse_abi(ABI).unwrap();
+        let err =
+            encode_function_arguments(&abi, "nonexistent", &serde_json::Map::new()).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Function nonexistent not found in the contract ABI. Available functions: put, get"
+        );
+    }
+
+    #[test]
+    fn test_decode_function_outputs() {
+        let abi = parse_abi(ABI).unwrap();
+        let response: Vec<FieldElement> = [1, 0x1f, 3, 1, 1, 7, 255]
+            .into_iter()
+            .map(FieldElement::from)
+            .collect();
+
+        let decoded = decode_function_outputs(&abi, "get", &response).unwrap();
+
+        assert_eq!(
+            decoded,
+            json!([[{ "x": "0x1f", "y": "340282366920938463463374607431768211459" }], { "Down": "0x7" }, "255"])
+        );
+    }
+
+    #[test]
+    fn test_decode_function_outputs_variant_without_data() {
+        let abi = parse_abi(ABI).unwrap();
+        let response: Vec<FieldElement> = [0, 0, 1].into_iter().map(FieldElement::from).collect();
+
+        let decoded = decode_function_outputs(&abi, "get", &response).unwrap();
+
+        assert_eq!(decoded, json!([[], "Up", "1"]));
+    }
+
+    #[test]
+    fn test_decode_function_outputs_invalid_length() {
+        let abi = parse_abi(ABI).unwrap();
+
+        let err = decode_function_outputs(&abi, "get", &[FieldElement::ONE]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Not enough values in the response to decode type core::felt252"));
+
+        let response: Vec<FieldElement> =
+            [0, 0, 1, 2].into_iter().map(FieldElement::from).collect();
+        let err = decode_function_outputs(&abi, "get", &response).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Response contains 1 values not described by the outputs of get"));
+    }
+
+    #[test]
+    fn test_validate_constructor_calldata() {
+        let abi = parse_abi(
+            r#"[
+                {
+                    "type": "constructor",
+                    "name": "constructor",
+                    "inputs": [
+                        { "name": "owner", "type": "core::felt252" },
+                        { "name": "guardian", "type": "core::felt252" }
+                    ]
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        validate_constructor_calldata(&abi, &[FieldElement::ONE, FieldElement::ZERO]).unwrap();
+
+        let err = validate_constructor_calldata(&abi, &[FieldElement::ONE]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid constructor calldata for input guardian: core::felt252"
+        );
+
+        let err = validate_constructor_calldata(&abi, &[FieldElement::ONE; 3]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Constructor calldata contains 1 values not described by the constructor inputs"
+        );
+    }
+}
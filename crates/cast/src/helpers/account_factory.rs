@@ -0,0 +1,310 @@
+use crate::helpers::constants::{
+    ARGENT_CLASS_HASH, BRAAVOS_BASE_ACCOUNT_CLASS_HASH, BRAAVOS_CLASS_HASH, OZ_CLASS_HASH,
+};
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use starknet::accounts::{
+    AccountFactory, ExecutionEncoding, PreparedAccountDeployment, RawAccountDeployment,
+};
+use starknet::core::types::FieldElement;
+use starknet::providers::Provider;
+use starknet::signers::Signer;
+use starknet_crypto::poseidon_hash_many;
+
+/// Account contract implementation, which determines the class hash, the constructor calldata
+/// and the encoding of executed calls
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountType {
+    /// OpenZeppelin account, constructed with the public key
+    #[default]
+    Oz,
+    /// Argent account, constructed with the owner public key and no guardian
+    Argent,
+    /// Braavos account, deployed with the base account class and constructed with the public key
+    Braavos,
+}
+
+impl AccountType {
+    #[must_use]
+    pub fn default_class_hash(self) -> FieldElement {
+        let class_hash = match self {
+            AccountType::Oz => OZ_CLASS_HASH,
+            AccountType::Argent => ARGENT_CLASS_HASH,
+            AccountType::Braavos => BRAAVOS_CLASS_HASH,
+        };
+        FieldElement::from_hex_be(class_hash).expect("Invalid default account class hash")
+    }
+
+    /// Class hash with which the account contract is deployed, which determines its address.
+    /// Braavos accounts are deployed with the base account class and upgrade themselves to `class_hash`
+    #[must_use]
+    pub fn deployment_class_hash(self, class_hash: FieldElement) -> FieldElement {
+        match self {
+            AccountType::Oz | AccountType::Argent => class_hash,
+            AccountType::Braavos => FieldElement::from_hex_be(BRAAVOS_BASE_ACCOUNT_CLASS_HASH)
+                .expect("Invalid Braavos base account class hash"),
+        }
+    }
+
+    #[must_use]
+    pub fn constructor_calldata(self, public_key: FieldElement) -> Vec<FieldElement> {
+        match self {
+            AccountType::Oz | AccountType::Braavos => vec![public_key],
+            AccountType::Argent => vec![public_key, FieldElement::ZERO],
+        }
+    }
+
+    #[must_use]
+    pub fn execution_encoding(self) -> ExecutionEncoding {
+        match self {
+            AccountType::Oz => ExecutionEncoding::Legacy,
+            AccountType::Argent | AccountType::Braavos => ExecutionEncoding::New,
+        }
+    }
+
+    /// Name of the account variant in starkli account files
+    #[must_use]
+    pub fn starkli_variant(self) -> &'static str {
+        match self {
+            AccountType::Oz => "open_zeppelin",
+            AccountType::Argent => "argent",
+            AccountType::Braavos => "braavos",
+        }
+    }
+
+    pub fn from_starkli_variant(variant: &str) -> Result<Self> {
+        match variant {
+            "open_zeppelin" => Ok(AccountType::Oz),
+            "argent" => Ok(AccountType::Argent),
+            "braavos" => Ok(AccountType::Braavos),
+            _ => {
+                bail!("Unsupported account type {variant}, supported types: open_zeppelin, argent, braavos")
+            }
+        }
+    }
+}
+
+/// Deploys accounts of any supported type, signing the deployment with the owner key
+pub struct CastAccountFactory<S, P> {
+    account_type: AccountType,
+    class_hash: FieldElement,
+    public_key: FieldElement,
+    constructor_calldata: Option<Vec<FieldElement>>,
+    chain_id: FieldElement,
+    signer: S,
+    provider: P,
+}
+
+impl<S, P> CastAccountFactory<S, P>
+where
+    S: Signer,
+{
+    pub async fn new(
+        account_type: AccountType,
+        class_hash: FieldElement,
+        chain_id: FieldElement,
+        signer: S,
+        provider: P,
+    ) -> Result<Self, S::GetPublicKeyError> {
+        let public_key = signer.get_public_key().await?.scalar();
+        Ok(Self {
+            account_type,
+            class_hash,
+            public_key,
+            constructor_calldata: None,
+            chain_id,
+            signer,
+            provider,
+        })
+    }
+
+    /// Deploys the account with the calldata instead of the default one of the account type
+    #[must_use]
+    pub fn with_constructor_calldata(mut self, constructor_calldata: Vec<FieldElement>) -> Self {
+        self.constructor_calldata = Some(constructor_calldata);
+        self
+    }
+
+    /// Signs the hash of the deploy account transaction, together with the auxiliary data for braavos accounts
+    async fn sign_transaction_hash(
+        &self,
+        transaction_hash: FieldElement,
+    ) -> Result<Vec<FieldElement>, S::SignError> {
+        let signature = self.signer.sign_hash(&transaction_hash).await?;
+        let mut signature = vec![signature.r, signature.s];
+
+        // the base account class checks the signed auxiliary data before upgrading to the account class
+        if self.account_type == AccountType::Braavos {
+            let mut aux_data = braavos_aux_data(self.class_hash, self.chain_id);
+            let aux_signature = self
+                .signer
+                .sign_hash(&poseidon_hash_many(&aux_data))
+                .await?;
+            signature.append(&mut aux_data);
+            signature.extend([aux_signature.r, aux_signature.s]);
+        }
+
+        Ok(signature)
+    }
+}
+
+#[async_trait]
+impl<S, P> AccountFactory for CastAccountFactory<S, P>
+where
+    S: Signer + Sync + Send,
+    P: Provider + Sync + Send,
+{
+    type Provider = P;
+    type SignError = S::SignError;
+
+    fn class_hash(&self) -> FieldElement {
+        self.account_type.deployment_class_hash(self.class_hash)
+    }
+
+    fn calldata(&self) -> Vec<FieldElement> {
+        self.constructor_calldata
+            .clone()
+            .unwrap_or_else(|| self.account_type.constructor_calldata(self.public_key))
+    }
+
+    fn chain_id(&self) -> FieldElement {
+        self.chain_id
+    }
+
+    fn provider(&self) -> &Self::Provider {
+        &self.provider
+    }
+
+    async fn sign_deployment(
+        &self,
+        deployment: &RawAccountDeployment,
+        query_only: bool,
+    ) -> Result<Vec<FieldElement>, Self::SignError> {
+        let transaction_hash = PreparedAccountDeployment::from_raw(deployment.clone(), self)
+            .transaction_hash(query_only);
+        self.sign_transaction_hash(transaction_hash).await
+    }
+}
+
+/// Account class, stark signer type, empty secp256r1 signer, disabled multisig,
+/// no withdrawal limit and fee rates, and the chain id
+fn braavos_aux_data(class_hash: FieldElement, chain_id: FieldElement) -> Vec<FieldElement> {
+    let mut aux_data = vec![class_hash];
+    aux_data.extend([FieldElement::ZERO; 9]);
+    aux_data.push(chain_id);
+    aux_data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountType, CastAccountFactory};
+    use starknet::core::types::FieldElement;
+    use starknet::signers::{LocalWallet, Signer, SigningKey};
+    use starknet_crypto::{poseidon_hash_many, verify};
+
+    #[test]
+    fn test_constructor_calldata() {
+        let public_key = FieldElement::from(123_u8);
+
+        assert_eq!(
+            AccountType::Oz.constructor_calldata(public_key),
+            vec![public_key]
+        );
+        assert_eq!(
+            AccountType::Argent.constructor_calldata(public_key),
+            vec![public_key, FieldElement::ZERO]
+        );
+        assert_eq!(
+            AccountType::Braavos.constructor_calldata(public_key),
+            vec![public_key]
+        );
+    }
+
+    #[test]
+    fn test_deployment_class_hash() {
+        let class_hash = FieldElement::from(123_u8);
+
+        assert_eq!(
+            AccountType::Oz.deployment_class_hash(class_hash),
+            class_hash
+        );
+        assert_ne!(
+            AccountType::Braavos.deployment_class_hash(class_hash),
+            class_hash
+        );
+    }
+
+    #[test]
+    fn test_starkli_variant_roundtrip() {
+        for account_type in [AccountType::Oz, AccountType::Argent, AccountType::Braavos] {
+            assert_eq!(
+                AccountType::from_starkli_variant(account_type.starkli_variant()).unwrap(),
+                account_type
+            );
+        }
+        assert!(AccountType::from_starkli_variant("argent_multisig").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deployment_signature() {
+        let signer = LocalWallet::from_signing_key(SigningKey::from_secret_scalar(
+            FieldElement::from(0x1234_u16),
+        ));
+        let public_key = signer.get_public_key().await.unwrap().scalar();
+        let class_hash = FieldElement::from(123_u8);
+        let chain_id = FieldElement::from_byte_slice_be(b"SN_GOERLI").unwrap();
+        let transaction_hash = FieldElement::from(456_u16);
+
+        let factory = CastAccountFactory::new(AccountType::Oz, class_hash, chain_id, signer, ())
+            .await
+            .unwrap();
+        let signature = factory
+            .sign_transaction_hash(transaction_hash)
+            .await
+            .unwrap();
+
+        assert_eq!(signature.len(), 2);
+        assert!(verify(&public_key, &transaction_hash, &signature[0], &signature[1]).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_braavos_deployment_signature() {
+        let signer = LocalWallet::from_signing_key(SigningKey::from_secret_scalar(
+            FieldElement::from(0x1234_u16),
+        ));
+        let public_key = signer.get_public_key().await.unwrap().scalar();
+        let class_hash = FieldElement::from(123_u8);
+        let chain_id = FieldElement::from_byte_slice_be(b"SN_GOERLI").unwrap();
+        let transaction_hash = FieldElement::from(456_u16);
+
+        let factory =
+            CastAccountFactory::new(AccountType::Braavos, class_hash, chain_id, signer, ())
+                .await
+                .unwrap();
+        let signature = factory
+            .sign_transaction_hash(transaction_hash)
+            .await
+            .unwrap();
+
+        // transaction signature, auxiliary data and its signature
+        assert_eq!(signature.len(), 2 + 11 + 2);
+        assert!(verify(&public_key, &transaction_hash, &signature[0], &signature[1]).unwrap());
+
+        let aux_data = &signature[2..13];
+        assert_eq!(aux_data[0], class_hash);
+        assert!(aux_data[1..10]
+            .iter()
+            .all(|felt| *felt == FieldElement::ZERO));
+        assert_eq!(aux_data[10], chain_id);
+        assert!(verify(
+            &public_key,
+            &poseidon_hash_many(aux_data),
+            &signature[13],
+            &signature[14]
+        )
+        .unwrap());
+    }
+}
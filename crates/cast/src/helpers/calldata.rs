@@ -0,0 +1,434 @@
+use crate::helpers::abi::{
+    encode_function_arguments, encode_positional_arguments, get_contract_abi, get_function,
+    parse_arguments_json, parse_named_arguments, parse_positional_arguments, parse_typed_argument,
+    read_abi_file,
+};
+use crate::helpers::retry_transport::RetryTransport;
+use crate::parse_number;
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use promptly::prompt;
+use serde_json::{Map, Value};
+use starknet::core::types::contract::AbiEntry;
+use starknet::core::types::{BlockId, FieldElement};
+use starknet::core::utils::cairo_short_string_to_felt;
+use starknet::providers::JsonRpcClient;
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct CalldataArgs {
+    /// Arguments of the function (list of hex or decimal felts); `'text'` is encoded as a Cairo short string
+    /// and `str:"text"` as a ByteArray. If `--abi-path` is passed, these are arguments in the order of
+    /// the function inputs, serialized according to their types (arrays, tuples and structs as JSON)
+    #[clap(short, long, num_args = 1..)]
+    pub calldata: Vec<String>,
+
+    /// Path to the file with arguments of the function (`-` reads them from stdin), separated by whitespace
+    /// or newlines, or as a JSON array; everything after `#` in a line is treated as a comment
+    #[clap(long, conflicts_with_all = ["calldata", "arguments", "arguments_json"])]
+    pub calldata_file: Option<Utf8PathBuf>,
+
+    /// Named argument of the function in `name:value` format, serialized using the contract ABI;
+    /// can be passed multiple times
+    #[clap(long = "arg", conflicts_with_all = ["calldata", "arguments_json"])]
+    pub arguments: Vec<String>,
+
+    /// Named arguments of the function as a JSON object, serialized using the contract ABI
+    #[clap(long, conflicts_with = "calldata")]
+    pub arguments_json: Option<String>,
+
+    /// Path to the ABI or Sierra contract class used to serialize arguments (and decode results of `call`);
+    /// if not passed, ABI of the class deployed at the contract address is used for named arguments
+    #[clap(long, alias = "abi-file")]
+    pub abi_path: Option<Utf8PathBuf>,
+
+    /// If passed, the value of every function input not passed with `--arg` is prompted for, showing its type
+    /// from the ABI; invalid values can be entered again
+    #[clap(long, requires = "abi_path", conflicts_with_all = ["calldata", "calldata_file", "arguments_json"])]
+    pub interactive: bool,
+}
+
+impl CalldataArgs {
+    /// Returns raw calldata (passed directly or read from a file), or serializes named arguments
+    /// (or `--calldata` when `--abi-path` is passed) against the function's ABI; these require the function name
+    pub async fn resolve(
+        &self,
+        provider: &JsonRpcClient<RetryTransport>,
+        contract_address: FieldElement,
+        function_name: Option<&str>,
+        block_id: &BlockId,
+    ) -> Result<Vec<FieldElement>> {
+        if let Some(calldata_file) = &self.calldata_file {
+            // raw calldata is not serialized, but the function is still checked against the ABI if passed
+            if let (Some(abi_path), Some(function_name)) = (&self.abi_path, function_name) {
+                get_function(&read_abi_file(abi_path)?, function_name)?;
+            }
+            return read_calldata_file(calldata_file);
+        }
+        if self.interactive {
+            let function_name = function_name.ok_or_else(|| {
+                anyhow!("--interactive requires --function, as the function is looked up in the ABI by name")
+            })?;
+            let abi = self.abi(provider, contract_address, block_id).await?;
+            let mut arguments = parse_named_arguments(&self.arguments)?;
+            prompt_missing_arguments(&abi, function_name, &mut arguments)?;
+
+            return encode_function_arguments(&abi, function_name, &arguments);
+        }
+
+        let arguments = match &self.arguments_json {
+            Some(arguments_json) => parse_arguments_json(arguments_json)?,
+            None if !self.arguments.is_empty() => parse_named_arguments(&self.arguments)?,
+            None => {
+                let Some(abi_path) = &self.abi_path else {
+                    return parse_calldata_values(&self.calldata);
+                };
+                let function_name = function_name.ok_or_else(|| {
+                    anyhow!("Typed arguments (--calldata with --abi-path) require --function, as the function is looked up in the ABI by name")
+                })?;
+                return encode_positional_arguments(
+                    &read_abi_file(abi_path)?,
+                    function_name,
+                    &parse_positional_arguments(&self.calldata)?,
+                );
+            }
+        };
+
+        let function_name = function_name.ok_or_else(|| {
+            anyhow!("Named arguments require --function, as the function is looked up in the ABI by name")
+        })?;
+        let abi = self.abi(provider, contract_address, block_id).await?;
+
+        encode_function_arguments(&abi, function_name, &arguments)
+    }
+
+    /// Returns the ABI from `--abi-path`, or the ABI of the class deployed at the contract address
+    pub async fn abi(
+        &self,
+        provider: &JsonRpcClient<RetryTransport>,
+        contract_address: FieldElement,
+        block_id: &BlockId,
+    ) -> Result<Vec<AbiEntry>> {
+        match &self.abi_path {
+            Some(abi_path) => read_abi_file(abi_path),
+            None => get_contract_abi(provider, contract_address, block_id).await,
+        }
+    }
+}
+
+fn prompt_missing_arguments(
+    abi: &[AbiEntry],
+    function_name: &str,
+    arguments: &mut Map<String, Value>,
+) -> Result<()> {
+    let function = get_function(abi, function_name)?;
+
+    for input in &function.inputs {
+        if arguments.contains_key(&input.name) {
+            continue;
+        }
+        let value = loop {
+            let entered: String = prompt(format!("{} ({})", input.name, input.r#type))?;
+            match parse_typed_argument(abi, &input.r#type, &entered) {
+                Ok(value) => break value,
+                Err(error) => eprintln!("Invalid value for {}: {error:#}", input.name),
+            }
+        };
+        arguments.insert(input.name.clone(), value);
+    }
+    Ok(())
+}
+
+/// Reads calldata from the file, or from stdin if the path is `-`
+pub fn read_calldata_file(path: &Utf8PathBuf) -> Result<Vec<FieldElement>> {
+    if path.as_str() == "-" {
+        let contents = std::io::read_to_string(std::io::stdin())
+            .context("Failed to read calldata from stdin")?;
+        return parse_calldata(&contents).context("Failed to parse calldata from stdin");
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read calldata file {path}"))?;
+    parse_calldata(&contents).with_context(|| format!("Failed to parse calldata file {path}"))
+}
+
+/// Parses calldata separated by whitespace or newlines, or a JSON array of felts (as numbers or strings)
+pub fn parse_calldata(contents: &str) -> Result<Vec<FieldElement>> {
+    if contents.trim_start().starts_with('[') {
+        return parse_calldata_json(contents);
+    }
+
+    let mut calldata = vec![];
+
+    for (index, line) in contents.lines().enumerate() {
+        for token in split_calldata_tokens(line)? {
+            calldata.extend(
+                parse_calldata_token(&token)
+                    .with_context(|| format!("Invalid felt {token} in line {}", index + 1))?,
+            );
+        }
+    }
+
+    Ok(calldata)
+}
+
+/// String elements are parsed like calldata tokens, so they may hold short strings and ByteArrays as well
+fn parse_calldata_json(contents: &str) -> Result<Vec<FieldElement>> {
+    let values: Vec<Value> =
+        serde_json::from_str(contents).context("Failed to parse calldata as a JSON array")?;
+
+    let mut calldata = vec![];
+    for (index, value) in values.iter().enumerate() {
+        let felts = match value {
+            Value::String(token) => parse_calldata_token(token),
+            Value::Number(number) => parse_number(&number.to_string()).map(|felt| vec![felt]),
+            _ => Err(anyhow!("expected a number or a string")),
+        };
+        calldata.extend(felts.with_context(|| format!("Invalid felt {value} at index {index}"))?);
+    }
+
+    Ok(calldata)
+}
+
+/// Parses calldata passed with `--calldata` (or `--constructor-calldata`), where every value may hold
+/// multiple whitespace separated tokens
+pub fn parse_calldata_values(values: &[String]) -> Result<Vec<FieldElement>> {
+    let mut calldata = vec![];
+    for value in values {
+        for token in split_calldata_tokens(value)? {
+            calldata.extend(
+                parse_calldata_token(&token).with_context(|| format!("Invalid felt {token}"))?,
+            );
+        }
+    }
+    Ok(calldata)
+}
+
+/// Splits on whitespace outside of quotes; everything after `#` outside of quotes is a comment
+fn split_calldata_tokens(line: &str) -> Result<Vec<String>> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut quote: Option<char> = None;
+
+    for c in line.chars() {
+        match quote {
+            Some(opening) => {
+                token.push(c);
+                if c == opening {
+                    quote = None;
+                }
+            }
+            None if c == '\'' || c == '"' => {
+                token.push(c);
+                quote = Some(c);
+            }
+            None if c == '#' => break,
+            None if c.is_whitespace() => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            None => token.push(c),
+        }
+    }
+    if quote.is_some() {
+        bail!("Unterminated string in calldata: {line}");
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Returns the felts a single calldata token is serialized to: `'text'` is a Cairo short string (one felt),
+/// `str:"text"` is a ByteArray (multiple felts), anything else is a hex or decimal felt
+pub fn parse_calldata_token(token: &str) -> Result<Vec<FieldElement>> {
+    if let Some(text) = token
+        .strip_prefix('\'')
+        .and_then(|token| token.strip_suffix('\''))
+    {
+        let felt = cairo_short_string_to_felt(text).map_err(|_| {
+            anyhow!("Invalid short string '{text}', expected at most 31 ASCII characters")
+        })?;
+        return Ok(vec![felt]);
+    }
+    if let Some(text) = token
+        .strip_prefix("str:\"")
+        .and_then(|token| token.strip_suffix('"'))
+    {
+        return Ok(encode_byte_array(text));
+    }
+
+    Ok(vec![parse_number(token)?])
+}
+
+/// Serializes the string as a Cairo ByteArray: the number of full 31-byte words, the full words,
+/// the pending word holding the remaining bytes and its length
+fn encode_byte_array(text: &str) -> Vec<FieldElement> {
+    let bytes = text.as_bytes();
+    let full_words: Vec<&[u8]> = bytes.chunks_exact(BYTES_IN_WORD).collect();
+    let pending_word = &bytes[full_words.len() * BYTES_IN_WORD..];
+
+    let mut calldata = vec![FieldElement::from(full_words.len())];
+    calldata.extend(full_words.into_iter().map(felt_from_bytes));
+    calldata.push(felt_from_bytes(pending_word));
+    calldata.push(FieldElement::from(pending_word.len()));
+    calldata
+}
+
+const BYTES_IN_WORD: usize = 31;
+
+fn felt_from_bytes(bytes: &[u8]) -> FieldElement {
+    let mut buffer = [0_u8; 32];
+    buffer[32 - bytes.len()..].copy_from_slice(bytes);
+    FieldElement::from_bytes_be(&buffer).expect("31 bytes always fit in a felt")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::calldata::{parse_calldata, parse_calldata_token, parse_calldata_values};
+    use indoc::indoc;
+    use starknet::core::types::FieldElement;
+    use starknet::core::utils::cairo_short_string_to_felt;
+
+    #[test]
+    fn test_parse_calldata() {
+        let contents = indoc! {"
+            # recipient
+            0x123
+            # amount (u256)
+            1000 0   # low and high
+
+            0xa
+        "};
+
+        let calldata = parse_calldata(contents).unwrap();
+
+        assert_eq!(
+            calldata,
+            vec![
+                FieldElement::from(0x123_u64),
+                FieldElement::from(1000_u64),
+                FieldElement::ZERO,
+                FieldElement::from(10_u64),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_calldata_json() {
+        let contents = r#"["0x123", 1000, "0", "'text'"]"#;
+
+        let calldata = parse_calldata(contents).unwrap();
+
+        assert_eq!(
+            calldata,
+            vec![
+                FieldElement::from(0x123_u64),
+                FieldElement::from(1000_u64),
+                FieldElement::ZERO,
+                cairo_short_string_to_felt("text").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_calldata_json_invalid_felt() {
+        let err = parse_calldata(r#"["0x1", "kapusta"]"#).unwrap_err();
+        assert_eq!(err.to_string(), r#"Invalid felt "kapusta" at index 1"#);
+
+        let err = parse_calldata(r#"[1, [2]]"#).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid felt [2] at index 1");
+
+        let err = parse_calldata("[0x1").unwrap_err();
+        assert_eq!(err.to_string(), "Failed to parse calldata as a JSON array");
+    }
+
+    #[test]
+    fn test_parse_calldata_invalid_felt() {
+        let contents = "0x1\n0x2 kapusta\n";
+
+        let err = parse_calldata(contents).unwrap_err();
+
+        assert_eq!(err.to_string(), "Invalid felt kapusta in line 2");
+    }
+
+    #[test]
+    fn test_parse_calldata_values() {
+        let values = vec![
+            "0x1 'hello world'".to_string(),
+            "2".to_string(),
+            "str:\"a b\"".to_string(),
+        ];
+
+        let calldata = parse_calldata_values(&values).unwrap();
+
+        assert_eq!(
+            calldata,
+            vec![
+                FieldElement::ONE,
+                cairo_short_string_to_felt("hello world").unwrap(),
+                FieldElement::TWO,
+                FieldElement::ZERO,
+                cairo_short_string_to_felt("a b").unwrap(),
+                FieldElement::THREE,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_short_string_boundary() {
+        let text = "a".repeat(31);
+        let calldata = parse_calldata_token(&format!("'{text}'")).unwrap();
+        assert_eq!(calldata, vec![cairo_short_string_to_felt(&text).unwrap()]);
+
+        let err = parse_calldata_token(&format!("'{}'", "a".repeat(32))).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected at most 31 ASCII characters"));
+    }
+
+    #[test]
+    fn test_byte_array_layout() {
+        let text = "a".repeat(31) + "bc";
+
+        let calldata = parse_calldata_token(&format!("str:\"{text}\"")).unwrap();
+
+        assert_eq!(
+            calldata,
+            vec![
+                FieldElement::ONE,
+                cairo_short_string_to_felt(&"a".repeat(31)).unwrap(),
+                cairo_short_string_to_felt("bc").unwrap(),
+                FieldElement::TWO,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_byte_array_full_words_only() {
+        let text = "a".repeat(62);
+
+        let calldata = parse_calldata_token(&format!("str:\"{text}\"")).unwrap();
+
+        let word = cairo_short_string_to_felt(&"a".repeat(31)).unwrap();
+        assert_eq!(
+            calldata,
+            vec![
+                FieldElement::TWO,
+                word,
+                word,
+                FieldElement::ZERO,
+                FieldElement::ZERO
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_calldata_unterminated_string() {
+        let err = parse_calldata("0x1 'hello\n").unwrap_err();
+
+        assert!(format!("{err:#}").contains("Unterminated string in calldata"));
+    }
+}
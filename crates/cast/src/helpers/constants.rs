@@ -13,15 +13,98 @@ inputs = []
 "#;
 
 pub const UDC_ADDRESS: &str = "0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf";
+// same address on all Starknet networks
+pub const ETH_ADDRESS: &str = "0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7";
 pub const OZ_CLASS_HASH: &str =
     "0x058d97f7d76e78f44905cc30cb65b91ea49a4b908a76703c54197bca90f81773";
+// Argent X account (Cairo 1, v0.3.0), declared on all Starknet networks
+pub const ARGENT_CLASS_HASH: &str =
+    "0x01a736d6ed154502257f02b1ccdf4d9d1089f80811cd6acad48e6b6a9d1f2003";
+// Braavos account (Cairo 1, v1.0.0), declared on all Starknet networks
+pub const BRAAVOS_CLASS_HASH: &str =
+    "0x00816dd0297efc55dc1e7559020a3a825e81ef734b558f03c83325d4da7e6253";
+// Braavos accounts are deployed with the base account class, which upgrades itself to the account class
+pub const BRAAVOS_BASE_ACCOUNT_CLASS_HASH: &str =
+    "0x013bfe114fb1cf405bfc3a7f8dbe2d91db146c17521d40dcf57e16d6b59fa8e6";
 
-// used in wait_for_tx. Txs are fetched every 5 seconds
-#[allow(dead_code)]
-pub const DEFAULT_RETRIES: u8 = 12;
+// used in wait_for_tx. Tx status is fetched every 5 seconds for at most 300 seconds by default
+pub const DEFAULT_WAIT_TIMEOUT: u16 = 300;
+pub const DEFAULT_WAIT_RETRY_INTERVAL: u8 = 5;
+
+// used in get_provider. Idempotent RPC requests are retried this many times on transient failures
+pub const DEFAULT_RPC_RETRIES: u8 = 3;
+// initial delay in milliseconds between retries of RPC requests, doubled after every attempt
+pub const DEFAULT_RPC_RETRY_BASE_DELAY: u16 = 500;
+
+// used when max fee is not passed. Max fee is set to the estimated fee multiplied by this value
+pub const DEFAULT_FEE_MULTIPLIER: f64 = 1.5;
+pub const FEE_ESTIMATION_FAILED_MESSAGE: &str =
+    "Failed to estimate transaction fee, pass --max-fee explicitly";
+
+// used by call when --block-id is not passed nor default-block-id is set in Scarb.toml
+pub const DEFAULT_BLOCK_ID: &str = "pending";
+
+// networks known by name: (network name, chain id as a short string)
+pub const KNOWN_NETWORKS: [(&str, &str); 3] = [
+    ("alpha-goerli", "SN_GOERLI"),
+    ("alpha-goerli2", "SN_GOERLI2"),
+    ("alpha-mainnet", "SN_MAIN"),
+];
+
+// used by `account create --fund-from-faucet`, keyed by the network name returned by chain_id_to_network_name;
+// faucets from `[tool.sncast.faucets]` take precedence. A faucet is sent `POST <url>` with a
+// `{"address": "0x..."}` JSON body and any successful status is taken as the funds being sent
+pub const FAUCET_URLS: [(&str, &str); 1] = [(
+    "alpha-goerli",
+    "https://faucet.goerli.starknet.io/api/v1/faucet",
+)];
+
+// used by `verify`: (verifier, network name returned by chain_id_to_network_name, API url)
+pub const VERIFIER_API_URLS: [(&str, &str, &str); 4] = [
+    (
+        "voyager",
+        "alpha-goerli",
+        "https://goerli.voyager.online/api",
+    ),
+    ("voyager", "alpha-mainnet", "https://voyager.online/api"),
+    (
+        "starkscan",
+        "alpha-goerli",
+        "https://api-testnet.starkscan.co/api/v0",
+    ),
+    (
+        "starkscan",
+        "alpha-mainnet",
+        "https://api.starkscan.co/api/v0",
+    ),
+];
+
+// used with --manage-nonce, stored in the directory of the accounts file
+pub const NONCE_CACHE_FILE_NAME: &str = ".sncast_nonce_cache.json";
+
+// JSON-RPC error code returned when there is no contract at the requested address
+pub const CONTRACT_NOT_FOUND_ERROR_CODE: i64 = 20;
+
+pub const DRY_RUN_MESSAGE: &str = "Dry run - transaction has not been sent";
 
 #[allow(dead_code)]
 pub const DEFAULT_ACCOUNTS_FILE: &str = "~/.starknet_accounts/starknet_open_zeppelin_accounts.json";
 
 pub const KEYSTORE_PASSWORD_ENV_VAR: &str = "KEYSTORE_PASSWORD";
 pub const CREATE_KEYSTORE_PASSWORD_ENV_VAR: &str = "CREATE_KEYSTORE_PASSWORD";
+// used as a fallback when the password is not set in the command specific variable above
+pub const SNCAST_KEYSTORE_PASSWORD_ENV_VAR: &str = "SNCAST_KEYSTORE_PASSWORD";
+
+// environment variables used when the corresponding flag is not passed; they take precedence over Scarb.toml
+pub const RPC_URL_ENV_VAR: &str = "SNCAST_RPC_URL";
+// shared with other Starknet tools, so it is used only when the url is not set in Scarb.toml either
+pub const STARKNET_RPC_ENV_VAR: &str = "STARKNET_RPC";
+pub const ACCOUNT_ENV_VAR: &str = "SNCAST_ACCOUNT";
+pub const ACCOUNTS_FILE_ENV_VAR: &str = "SNCAST_ACCOUNTS_FILE";
+pub const KEYSTORE_ENV_VAR: &str = "SNCAST_KEYSTORE";
+pub const PROFILE_ENV_VAR: &str = "SNCAST_PROFILE";
+/// See https://no-color.org
+pub const NO_COLOR_ENV_VAR: &str = "NO_COLOR";
+
+// config file independent of Scarb, holding the same fields as `[tool.sncast]` under `[sncast]`
+pub const SNFOUNDRY_CONFIG_FILE: &str = "snfoundry.toml";
@@ -0,0 +1,98 @@
+use serde_json::Value;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable with log directives (e.g. `cast=debug`), overriding the level set with `-v`
+pub const LOG_ENV_VAR: &str = "SNCAST_LOG";
+
+const REDACTED: &str = "<redacted>";
+
+/// Initializes logging to stderr: `-v` logs the resolved configuration and timing, `-vv` RPC requests
+/// and `-vvv` full request and response bodies. Nothing is logged without `-v`, unless `SNCAST_LOG` is set
+pub fn init_logging(verbosity: u8) {
+    let filter = match EnvFilter::try_from_env(LOG_ENV_VAR) {
+        Ok(filter) => filter,
+        Err(_) if verbosity == 0 => return,
+        Err(_) => {
+            let level = log_level(verbosity);
+            EnvFilter::new(format!("cast={level},sncast={level}"))
+        }
+    };
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_target(false)
+        .init();
+}
+
+fn log_level(verbosity: u8) -> &'static str {
+    match verbosity {
+        0 => "off",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    }
+}
+
+/// Returns the JSON with values of all fields holding private keys or passwords replaced, so it can be logged;
+/// text which is not valid JSON is returned as is
+#[must_use]
+pub fn redact_secrets(json: &str) -> String {
+    match serde_json::from_str::<Value>(json) {
+        Ok(value) => redact_value(value).to_string(),
+        Err(_) => json.to_string(),
+    }
+}
+
+fn redact_value(value: Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| {
+                    if is_secret(&key) {
+                        (key, Value::String(REDACTED.to_string()))
+                    } else {
+                        (key, redact_value(value))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(array) => Value::Array(array.into_iter().map(redact_value).collect()),
+        value => value,
+    }
+}
+
+fn is_secret(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key.contains("private_key") || key.contains("password")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{log_level, redact_secrets};
+
+    #[test]
+    fn test_redact_secrets() {
+        let redacted = redact_secrets(
+            r#"{"params": {"private_key": "0x123", "nested": [{"keystore_password": "abc"}], "address": "0x1"}}"#,
+        );
+
+        assert!(redacted.contains(r#""private_key":"<redacted>""#));
+        assert!(redacted.contains(r#""keystore_password":"<redacted>""#));
+        assert!(redacted.contains(r#""address":"0x1""#));
+        assert!(!redacted.contains("0x123"));
+    }
+
+    #[test]
+    fn test_redact_secrets_invalid_json() {
+        assert_eq!(redact_secrets("not json"), "not json");
+    }
+
+    #[test]
+    fn test_log_level() {
+        assert_eq!(log_level(1), "info");
+        assert_eq!(log_level(2), "debug");
+        assert_eq!(log_level(5), "trace");
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Request received by the mock server: method, path and body
+#[derive(Clone, Debug)]
+pub struct ReceivedRequest {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+/// Minimal HTTP server for testing clients of external APIs, answering each request with
+/// the next JSON body queued for its path; the last body of a path is repeated once the queue runs out
+pub struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<ReceivedRequest>>>,
+}
+
+impl MockServer {
+    pub fn start(responses: Vec<(&str, Vec<&str>)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let mut responses: HashMap<String, Vec<String>> = responses
+            .into_iter()
+            .map(|(path, bodies)| {
+                (
+                    path.to_string(),
+                    bodies.into_iter().map(ToString::to_string).collect(),
+                )
+            })
+            .collect();
+        let received = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let request = read_request(&stream);
+                let body = match responses.get_mut(&request.path) {
+                    Some(bodies) if bodies.len() > 1 => Some(bodies.remove(0)),
+                    Some(bodies) => bodies.first().cloned(),
+                    None => None,
+                };
+                received.lock().unwrap().push(request);
+                write_response(stream, body);
+            }
+        });
+
+        MockServer { url, requests }
+    }
+
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    #[must_use]
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request(stream: &TcpStream) -> ReceivedRequest {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+
+    ReceivedRequest {
+        method,
+        path,
+        body: String::from_utf8(body).unwrap(),
+    }
+}
+
+fn write_response(mut stream: TcpStream, body: Option<String>) {
+    let (status, body) = match body {
+        Some(body) => ("200 OK", body),
+        None => ("404 Not Found", String::new()),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).unwrap();
+}
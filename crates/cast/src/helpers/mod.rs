@@ -1,3 +1,12 @@
+pub mod abi;
+pub mod account_factory;
+pub mod calldata;
 pub mod constants;
+pub mod logging;
+#[cfg(test)]
+pub(crate) mod mock_server;
+pub mod nonce_cache;
 pub mod response_structs;
+pub mod retry_transport;
 pub mod scarb_utils;
+pub mod signer;
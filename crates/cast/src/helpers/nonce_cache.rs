@@ -0,0 +1,192 @@
+use crate::handle_rpc_error;
+use crate::helpers::constants::NONCE_CACHE_FILE_NAME;
+use crate::helpers::retry_transport::RetryTransport;
+use crate::helpers::signer::CastSigner;
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use starknet::accounts::{Account, ConnectedAccount, SingleOwnerAccount};
+use starknet::core::types::FieldElement;
+use starknet::providers::JsonRpcClient;
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Nonces of accounts cached locally when using `--manage-nonce`, so that transactions can be sent
+/// one after another without waiting for the previous ones to be accepted.
+/// Like in the accounts file, nonces are grouped by network, as the same address may be used on many networks
+pub struct NonceCache {
+    path: Utf8PathBuf,
+    network_name: String,
+}
+
+type Nonces = BTreeMap<String, BTreeMap<String, FieldElement>>;
+
+impl NonceCache {
+    /// The cache is stored next to the accounts file and holds nonces of accounts on `network_name`
+    #[must_use]
+    pub fn for_accounts_file(accounts_file: &Utf8PathBuf, network_name: &str) -> Self {
+        let directory = accounts_file
+            .parent()
+            .map(Utf8PathBuf::from)
+            .unwrap_or_default();
+        NonceCache {
+            path: directory.join(NONCE_CACHE_FILE_NAME),
+            network_name: network_name.to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Utf8PathBuf {
+        &self.path
+    }
+
+    pub fn get(&self, address: FieldElement) -> Result<Option<FieldElement>> {
+        Ok(self
+            .read()?
+            .get(&self.network_name)
+            .and_then(|nonces| nonces.get(&cache_key(address)))
+            .copied())
+    }
+
+    pub fn set(&self, address: FieldElement, nonce: FieldElement) -> Result<()> {
+        let mut nonces = self.read()?;
+        nonces
+            .entry(self.network_name.clone())
+            .or_default()
+            .insert(cache_key(address), nonce);
+        self.write(&nonces)
+    }
+
+    pub fn invalidate(&self, address: FieldElement) -> Result<()> {
+        let mut nonces = self.read()?;
+        let Some(network_nonces) = nonces.get_mut(&self.network_name) else {
+            return Ok(());
+        };
+        if network_nonces.remove(&cache_key(address)).is_some() {
+            if network_nonces.is_empty() {
+                nonces.remove(&self.network_name);
+            }
+            self.write(&nonces)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the cached nonce of the account, fetching it from the network if it is not cached yet
+    pub async fn next_nonce(
+        &self,
+        account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    ) -> Result<FieldElement> {
+        if let Some(nonce) = self.get(account.address())? {
+            return Ok(nonce);
+        }
+        match account.get_nonce().await {
+            Ok(nonce) => Ok(nonce),
+            Err(error) => handle_rpc_error(error),
+        }
+    }
+
+    /// Increments the cached nonce after the transaction has been sent, or invalidates it if sending failed,
+    /// so that it is fetched from the network next time
+    pub fn update<T>(
+        &self,
+        address: FieldElement,
+        nonce: FieldElement,
+        result: &Result<T>,
+    ) -> Result<()> {
+        match result {
+            Ok(_) => self.set(address, nonce + FieldElement::ONE),
+            Err(_) => self.invalidate(address),
+        }
+    }
+
+    fn read(&self) -> Result<Nonces> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("Cannot read a file {}", self.path))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse nonce cache file at {}", self.path))
+    }
+
+    fn write(&self, nonces: &Nonces) -> Result<()> {
+        if let Some(directory) = self.path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(nonces)?)
+            .with_context(|| format!("Failed to write nonce cache file at {}", self.path))
+    }
+}
+
+fn cache_key(address: FieldElement) -> String {
+    format!("{address:#x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NonceCache;
+    use anyhow::anyhow;
+    use camino::Utf8PathBuf;
+    use starknet::core::types::FieldElement;
+    use tempfile::TempDir;
+
+    fn nonce_cache(tempdir: &TempDir, network_name: &str) -> NonceCache {
+        let accounts_file = Utf8PathBuf::from_path_buf(tempdir.path().join("accounts.json"))
+            .expect("Invalid temp dir path");
+        NonceCache::for_accounts_file(&accounts_file, network_name)
+    }
+
+    #[test]
+    fn test_update_increments_nonce() {
+        let tempdir = TempDir::new().unwrap();
+        let cache = nonce_cache(&tempdir, "alpha-goerli");
+
+        assert_eq!(cache.get(FieldElement::ONE).unwrap(), None);
+
+        cache
+            .update(FieldElement::ONE, FieldElement::TWO, &Ok(()))
+            .unwrap();
+        assert_eq!(
+            cache.get(FieldElement::ONE).unwrap(),
+            Some(FieldElement::THREE)
+        );
+        assert_eq!(cache.get(FieldElement::TWO).unwrap(), None);
+    }
+
+    #[test]
+    fn test_update_invalidates_nonce_on_error() {
+        let tempdir = TempDir::new().unwrap();
+        let cache = nonce_cache(&tempdir, "alpha-goerli");
+
+        cache.set(FieldElement::ONE, FieldElement::TWO).unwrap();
+        cache
+            .update::<()>(FieldElement::ONE, FieldElement::TWO, &Err(anyhow!("error")))
+            .unwrap();
+
+        assert_eq!(cache.get(FieldElement::ONE).unwrap(), None);
+    }
+
+    #[test]
+    fn test_nonces_are_separate_per_network() {
+        let tempdir = TempDir::new().unwrap();
+        let goerli_cache = nonce_cache(&tempdir, "alpha-goerli");
+        let mainnet_cache = nonce_cache(&tempdir, "alpha-mainnet");
+
+        goerli_cache
+            .set(FieldElement::ONE, FieldElement::TWO)
+            .unwrap();
+        mainnet_cache
+            .set(FieldElement::ONE, FieldElement::THREE)
+            .unwrap();
+        assert_eq!(
+            goerli_cache.get(FieldElement::ONE).unwrap(),
+            Some(FieldElement::TWO)
+        );
+
+        mainnet_cache.invalidate(FieldElement::ONE).unwrap();
+        assert_eq!(mainnet_cache.get(FieldElement::ONE).unwrap(), None);
+        assert_eq!(
+            goerli_cache.get(FieldElement::ONE).unwrap(),
+            Some(FieldElement::TWO)
+        );
+    }
+}
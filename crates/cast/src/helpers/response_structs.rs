@@ -1,6 +1,8 @@
 use camino::Utf8PathBuf;
 use serde::Serialize;
-use starknet::core::types::FieldElement;
+use serde_json::Value;
+use starknet::core::types::{FeeEstimate, FieldElement, StateDiff};
+use std::collections::BTreeMap;
 
 #[derive(Serialize, Clone)]
 pub struct InvokeResponse {
@@ -11,17 +13,104 @@ pub struct InvokeResponse {
 pub struct DeployResponse {
     pub contract_address: FieldElement,
     pub transaction_hash: FieldElement,
+    pub salt: FieldElement,
 }
 
 #[derive(Serialize)]
 pub struct DeclareResponse {
     pub class_hash: FieldElement,
+    /// Not set if the class was already declared and no transaction was sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<FieldElement>,
+    /// Set if the class was already declared, so the existing class hash is returned instead of declaring it again
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub already_declared: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct FeeEstimateResponse {
+    pub gas_consumed: u64,
+    pub gas_price: u64,
+    pub overall_fee: u64,
+    pub max_fee: Option<u64>,
+}
+
+impl From<FeeEstimate> for FeeEstimateResponse {
+    fn from(fee_estimate: FeeEstimate) -> Self {
+        FeeEstimateResponse {
+            gas_consumed: fee_estimate.gas_consumed,
+            gas_price: fee_estimate.gas_price,
+            overall_fee: fee_estimate.overall_fee,
+            max_fee: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DryRunResponse {
+    pub message: String,
+    pub version: FieldElement,
     pub transaction_hash: FieldElement,
+    pub sender_address: FieldElement,
+    pub nonce: FieldElement,
+    pub max_fee: FieldElement,
+    pub signature: Vec<FieldElement>,
+    pub calldata: Option<Vec<FieldElement>>,
+    pub contract_address: Option<FieldElement>,
+    pub class_hash: Option<FieldElement>,
+    pub compiled_class_hash: Option<FieldElement>,
+    pub salt: Option<FieldElement>,
 }
 
 #[derive(Serialize, Clone, Debug)]
 pub struct CallResponse {
     pub response: Vec<FieldElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<Value>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeclareStatus {
+    Declared,
+    /// Class with the same hash was declared before, so no transaction was sent
+    AlreadyDeclared,
+    Failed,
+}
+
+#[derive(Serialize)]
+pub struct DeclareManyResponse {
+    pub results: Vec<ContractDeclareResult>,
+}
+
+#[derive(Serialize)]
+pub struct ContractDeclareResult {
+    pub contract: String,
+    pub status: DeclareStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_hash: Option<FieldElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<FieldElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct BatchCallResponse {
+    pub results: Vec<BatchCallResult>,
+}
+
+#[derive(Serialize)]
+pub struct BatchCallResult {
+    pub contract_address: FieldElement,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<Vec<FieldElement>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -30,6 +119,7 @@ pub struct AccountCreateResponse {
     pub max_fee: u64,
     pub add_profile: String,
     pub message: String,
+    pub salt: FieldElement,
 }
 
 #[derive(Serialize)]
@@ -37,27 +127,233 @@ pub struct AccountAddResponse {
     pub add_profile: String,
 }
 
+#[derive(Serialize)]
+pub struct AccountImportResponse {
+    pub address: FieldElement,
+    pub network: String,
+}
+
+#[derive(Serialize)]
+pub struct AccountExportResponse {
+    pub address: String,
+    pub public_key: String,
+    pub class_hash: Option<String>,
+    pub salt: Option<String>,
+    pub deployed: Option<bool>,
+    /// Included only when explicitly requested, so that it doesn't leak into logs by accident
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_file: Option<Utf8PathBuf>,
+}
+
 #[derive(Serialize)]
 pub struct AccountDeleteResponse {
     pub result: String,
     pub scarb_result: String,
 }
 
+#[derive(Serialize)]
+pub struct AccountListItem {
+    pub address: String,
+    pub class_hash: Option<String>,
+    pub deployed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    /// Nonce of the account on the network, or `not deployed`; only set with `--with-nonce`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct AccountListResponse {
+    pub accounts: BTreeMap<String, BTreeMap<String, AccountListItem>>,
+}
+
+#[derive(Serialize)]
+pub struct NetworksResponse {
+    pub networks: Vec<NetworkItem>,
+}
+
+#[derive(Serialize)]
+pub struct NetworkItem {
+    pub name: String,
+    /// Chain id as a short string, e.g. `SN_GOERLI`
+    pub chain_id: String,
+    pub chain_id_hex: String,
+    pub chain_id_int: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub faucet_url: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct MulticallRunResponse {
+    /// Hash of the last sent transaction; not set if no transaction was sent with `--continue-on-error`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<FieldElement>,
+    /// Hashes of all sent transactions, when calls were split into multiple transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hashes: Option<Vec<FieldElement>>,
+    /// Status of every call, reported with `--continue-on-error`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<MulticallStepResponse>>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StepStatus {
+    Succeeded,
+    Failed,
+    /// Not sent, because the call depends on a call which failed or was skipped
+    Skipped,
+}
+
+#[derive(Serialize)]
+pub struct MulticallStepResponse {
+    /// Position of the call in the file, starting from 0
+    pub step: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub status: StepStatus,
+    /// Hash of the transaction the call was sent in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<FieldElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct MulticallSimulateResponse {
+    pub gas_consumed: u64,
+    pub gas_price: u64,
+    pub overall_fee: u64,
+    pub calls: Vec<SimulatedCallResponse>,
+    /// Index of the first call which fails, if the transaction is reverted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverted_call: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<StateDiff>,
+}
+
+#[derive(Serialize)]
+pub struct SimulatedCallResponse {
+    pub contract_address: FieldElement,
+    pub selector: FieldElement,
+    pub events: Vec<EventResponse>,
+}
+
+#[derive(Serialize)]
+pub struct SimulateResponse {
+    pub gas_consumed: u64,
+    pub gas_price: u64,
+    pub overall_fee: u64,
+    /// Address of the contract which would be deployed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<FieldElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_hash: Option<FieldElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// Invocation of the account's `__execute__`, not set for declare transactions and reverted ones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<InvocationResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<StateDiff>,
+}
+
+#[derive(Serialize)]
+pub struct InvocationResponse {
+    pub contract_address: FieldElement,
+    pub entry_point_selector: FieldElement,
+    pub calldata: Vec<FieldElement>,
+    pub result: Vec<FieldElement>,
+    pub events: Vec<EventResponse>,
+    /// Internal calls made by the function
+    pub calls: Vec<InvocationResponse>,
+}
+
+#[derive(Serialize)]
+pub struct EventResponse {
+    pub from_address: FieldElement,
+    pub keys: Vec<FieldElement>,
+    pub data: Vec<FieldElement>,
+}
+
 #[derive(Serialize)]
 pub struct MulticallNewResponse {
-    pub path: Utf8PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<Utf8PathBuf>,
     pub content: String,
 }
 
 #[derive(Serialize)]
 pub struct ShowConfigResponse {
     pub profile: Option<String>,
+    /// Name of the network the RPC provider is connected to
     pub chain_id: String,
+    /// Chain id returned by the RPC provider, decoded from a short string
+    pub resolved_chain_id: String,
     pub rpc_url: Option<String>,
     pub account: Option<String>,
     pub scarb_path: Option<Utf8PathBuf>,
-    pub accounts_file_path: Option<Utf8PathBuf>,
+    pub accounts_file_path: Option<String>,
     pub keystore: Option<Utf8PathBuf>,
+    pub signer_command: Option<String>,
+    /// Problems with the configuration, e.g. conflicting ways of signing transactions
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct BalanceResponse {
+    pub balance: String,
+    pub balance_low: FieldElement,
+    pub balance_high: FieldElement,
+}
+
+#[derive(Serialize)]
+pub struct NonceResponse {
+    pub nonce: FieldElement,
+}
+
+#[derive(Serialize)]
+pub struct ClassHashAtResponse {
+    pub class_hash: FieldElement,
+}
+
+#[derive(Serialize)]
+pub struct VerifySignatureResponse {
+    pub valid: bool,
+    /// Error returned by the account contract, if verifying the signature failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TransactionStatusResponse {
+    pub finality_status: String,
+    pub execution_status: Option<String>,
+    pub revert_reason: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct EventResponse {
+    pub from_address: FieldElement,
+    pub keys: Vec<FieldElement>,
+    pub data: Vec<FieldElement>,
+}
+
+#[derive(Serialize)]
+pub struct TransactionReceiptResponse {
+    pub transaction_type: String,
+    pub finality_status: String,
+    pub execution_status: String,
+    pub revert_reason: Option<String>,
+    pub actual_fee: u64,
+    pub block_number: u64,
+    pub events: Vec<EventResponse>,
 }
 
 #[derive(Serialize)]
@@ -65,3 +361,12 @@ pub struct ScriptResponse {
     pub status: String,
     pub msg: Option<String>,
 }
+
+#[derive(Serialize)]
+pub struct VerifyResponse {
+    pub verifier: String,
+    pub job_id: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
@@ -0,0 +1,175 @@
+use crate::helpers::logging::redact_secrets;
+use async_trait::async_trait;
+use rand::Rng;
+use reqwest::{Client, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::json;
+use starknet::providers::jsonrpc::{
+    HttpTransportError, JsonRpcMethod, JsonRpcResponse, JsonRpcTransport,
+};
+use std::time::{Duration, Instant};
+use url::Url;
+
+const MAX_JITTER_MS: u64 = 250;
+
+/// JSON-RPC transport over HTTP which retries idempotent requests failing with
+/// transient errors (429, 5xx, connection errors and timeouts) using exponential backoff.
+#[derive(Debug)]
+pub struct RetryTransport {
+    client: Client,
+    url: Url,
+    max_retries: u8,
+    base_delay_ms: u16,
+}
+
+impl RetryTransport {
+    #[must_use]
+    pub fn new(url: Url, max_retries: u8, base_delay_ms: u16) -> Self {
+        RetryTransport {
+            client: Client::new(),
+            url,
+            max_retries,
+            base_delay_ms,
+        }
+    }
+}
+
+// Submitting the same transaction twice is not safe to repeat blindly, every other method only reads state
+fn is_idempotent(method: &JsonRpcMethod) -> bool {
+    !matches!(
+        method,
+        JsonRpcMethod::AddInvokeTransaction
+            | JsonRpcMethod::AddDeclareTransaction
+            | JsonRpcMethod::AddDeployAccountTransaction
+    )
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn is_transient_error(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect()
+}
+
+#[must_use]
+pub fn backoff_duration(base_delay_ms: u16, attempt: u8) -> Duration {
+    let jitter = rand::thread_rng().gen_range(0..=MAX_JITTER_MS);
+    Duration::from_millis(u64::from(base_delay_ms) * 2u64.pow(attempt.into()) + jitter)
+}
+
+#[async_trait]
+impl JsonRpcTransport for RetryTransport {
+    type Error = HttpTransportError;
+
+    async fn send_request<P, R>(
+        &self,
+        method: JsonRpcMethod,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, Self::Error>
+    where
+        P: Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        let retries = if is_idempotent(&method) {
+            self.max_retries
+        } else {
+            0
+        };
+        let method_name = serde_json::to_value(&method).map_err(HttpTransportError::Json)?;
+        let request_body = serde_json::to_string(&json!({
+            "id": 1,
+            "jsonrpc": "2.0",
+            "method": method_name,
+            "params": params,
+        }))
+        .map_err(HttpTransportError::Json)?;
+
+        tracing::trace!("RPC request body: {}", redact_secrets(&request_body));
+
+        let mut attempt = 0;
+        loop {
+            tracing::debug!("Sending RPC request {method_name} to {}", self.url);
+            let start = Instant::now();
+            let response = self
+                .client
+                .post(self.url.clone())
+                .body(request_body.clone())
+                .header("Content-Type", "application/json")
+                .send()
+                .await;
+
+            let failure = match &response {
+                Ok(response) if is_transient_status(response.status()) => {
+                    Some(response.status().to_string())
+                }
+                Err(error) if is_transient_error(error) => Some(error.to_string()),
+                _ => None,
+            };
+
+            if let Some(failure) = failure {
+                if attempt < retries {
+                    let backoff = backoff_duration(self.base_delay_ms, attempt);
+                    attempt += 1;
+                    eprintln!(
+                        "RPC request {method_name} failed ({failure}), retrying in {}ms (attempt {attempt}/{retries})",
+                        backoff.as_millis()
+                    );
+                    tokio::time::sleep(backoff).await;
+                    continue;
+                }
+            }
+
+            let response = response.map_err(HttpTransportError::Reqwest)?;
+            // Body of a failed response is usually not a JSON-RPC response, so the HTTP error is reported instead
+            let status_error = response.error_for_status_ref().err();
+            let status = response.status();
+            let response_body = response.text().await.map_err(HttpTransportError::Reqwest)?;
+            tracing::debug!(
+                "RPC request {method_name} finished with status {status} in {}ms",
+                start.elapsed().as_millis()
+            );
+            tracing::trace!("RPC response body: {}", redact_secrets(&response_body));
+
+            return serde_json::from_str(&response_body).map_err(|error| match status_error {
+                Some(status_error) => HttpTransportError::Reqwest(status_error),
+                None => HttpTransportError::Json(error),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::retry_transport::{backoff_duration, is_idempotent, is_transient_status};
+    use reqwest::StatusCode;
+    use starknet::providers::jsonrpc::JsonRpcMethod;
+    use std::time::Duration;
+
+    #[test]
+    fn test_submissions_are_not_idempotent() {
+        assert!(!is_idempotent(&JsonRpcMethod::AddInvokeTransaction));
+        assert!(!is_idempotent(&JsonRpcMethod::AddDeclareTransaction));
+        assert!(!is_idempotent(&JsonRpcMethod::AddDeployAccountTransaction));
+        assert!(is_idempotent(&JsonRpcMethod::ChainId));
+        assert!(is_idempotent(&JsonRpcMethod::Call));
+    }
+
+    #[test]
+    fn test_transient_status() {
+        assert!(is_transient_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_transient_status(StatusCode::OK));
+        assert!(!is_transient_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_backoff_grows_exponentially() {
+        assert!(backoff_duration(500, 0) >= Duration::from_millis(500));
+        assert!(backoff_duration(500, 0) <= Duration::from_millis(750));
+        assert!(backoff_duration(500, 2) >= Duration::from_millis(2000));
+        assert!(backoff_duration(500, 2) <= Duration::from_millis(2250));
+        assert!(backoff_duration(100, 3) >= Duration::from_millis(800));
+        assert!(backoff_duration(100, 3) <= Duration::from_millis(1050));
+    }
+}
@@ -1,20 +1,39 @@
+use crate::helpers::constants::{
+    DEFAULT_BLOCK_ID, DEFAULT_FEE_MULTIPLIER, DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY,
+    DEFAULT_WAIT_RETRY_INTERVAL, DEFAULT_WAIT_TIMEOUT, PROFILE_ENV_VAR, SNFOUNDRY_CONFIG_FILE,
+};
+use crate::{
+    get_block_id, get_env_var, parse_chain_id, validate_fee_multiplier, ValidatedWaitParams,
+};
 use anyhow::{anyhow, bail, Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
 use scarb_metadata;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use starknet::core::types::FieldElement;
 use std::default::Default;
 use std::env;
-use std::fs::canonicalize;
+use std::fs::{self, canonicalize};
 use std::process::{Command, Stdio};
 use std::str::FromStr;
 
-#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct CastConfig {
-    pub rpc_url: String,
+    pub rpc_urls: Vec<String>,
     pub account: String,
-    pub accounts_file: Utf8PathBuf,
+    pub accounts_files: Vec<Utf8PathBuf>,
     pub keystore: Utf8PathBuf,
+    pub signer_command: String,
+    pub wait_params: ValidatedWaitParams,
+    pub rpc_retries: u8,
+    pub rpc_retry_base_delay: u16,
+    pub fee_multiplier: f64,
+    pub default_block_id: String,
+    pub decode_strings: bool,
+    /// Names of custom networks with their chain ids, taking precedence over the built-in network names
+    pub networks: Vec<(String, FieldElement)>,
+    /// Faucet urls keyed by network name, taking precedence over the built-in faucets
+    pub faucets: Vec<(String, String)>,
 }
 
 impl CastConfig {
@@ -22,23 +41,125 @@ impl CastConfig {
         package_tool_sncast: &Value,
         profile: &Option<String>,
     ) -> Result<CastConfig> {
-        let tool = get_profile(package_tool_sncast, profile)?;
+        let tool = &get_profile(package_tool_sncast, profile)?;
+        let default_block_id = match tool.get("default-block-id") {
+            Some(_) => get_property(tool, "default-block-id"),
+            None => String::from(DEFAULT_BLOCK_ID),
+        };
+        get_block_id(&default_block_id)
+            .context("Invalid value of field default-block-id in [tool.sncast]")?;
 
         Ok(CastConfig {
-            rpc_url: get_property(tool, "url"),
+            rpc_urls: get_url_list_property(tool, "url")?,
             account: get_property(tool, "account"),
-            accounts_file: get_property(tool, "accounts-file"),
+            accounts_files: get_path_list_property(tool, "accounts-file")?,
             keystore: get_property(tool, "keystore"),
+            signer_command: get_property(tool, "signer-command"),
+            wait_params: ValidatedWaitParams::new(
+                get_number_property(tool, "wait-retry-interval", DEFAULT_WAIT_RETRY_INTERVAL)?,
+                get_number_property(tool, "wait-timeout", DEFAULT_WAIT_TIMEOUT)?,
+            )?,
+            rpc_retries: get_number_property(tool, "rpc-retries", DEFAULT_RPC_RETRIES)?,
+            rpc_retry_base_delay: get_number_property(
+                tool,
+                "rpc-retry-base-delay",
+                DEFAULT_RPC_RETRY_BASE_DELAY,
+            )?,
+            fee_multiplier: validate_fee_multiplier(get_float_property(
+                tool,
+                "fee-multiplier",
+                DEFAULT_FEE_MULTIPLIER,
+            )?)?,
+            default_block_id,
+            decode_strings: get_bool_property(tool, "decode-strings")?,
+            networks: get_networks_property(tool, "networks")?,
+            faucets: get_faucets_property(tool, "faucets")?,
         })
     }
+
+    /// Accounts file to which new accounts are written - the first one of the accounts files
+    #[must_use]
+    pub fn accounts_file(&self) -> Utf8PathBuf {
+        self.accounts_files.first().cloned().unwrap_or_default()
+    }
 }
 
-pub fn get_profile<'a>(tool_sncast: &'a Value, profile: &Option<String>) -> Result<&'a Value> {
-    match profile {
-        Some(profile_) => tool_sncast
-            .get(profile_)
-            .ok_or_else(|| anyhow!("No field [tool.sncast.{}] found in package", profile_)),
-        None => Ok(tool_sncast),
+impl Default for CastConfig {
+    fn default() -> Self {
+        CastConfig {
+            rpc_urls: Vec::default(),
+            account: String::default(),
+            accounts_files: Vec::default(),
+            keystore: Utf8PathBuf::default(),
+            signer_command: String::default(),
+            wait_params: ValidatedWaitParams::default(),
+            rpc_retries: DEFAULT_RPC_RETRIES,
+            rpc_retry_base_delay: DEFAULT_RPC_RETRY_BASE_DELAY,
+            fee_multiplier: DEFAULT_FEE_MULTIPLIER,
+            default_block_id: String::from(DEFAULT_BLOCK_ID),
+            decode_strings: false,
+            networks: Vec::default(),
+            faucets: Vec::default(),
+        }
+    }
+}
+
+/// Profile from `[tool.sncast.<profile>]` with the fields of the profiles it (transitively) `inherits` from
+/// merged in, the inheriting profile's fields taking precedence
+pub fn get_profile(tool_sncast: &Value, profile: &Option<String>) -> Result<Value> {
+    let Some(profile) = profile else {
+        return Ok(tool_sncast.clone());
+    };
+
+    let mut chain = vec![profile.clone()];
+    let mut merged = get_named_profile(tool_sncast, profile)?.clone();
+    while let Some(base) = merged
+        .as_object_mut()
+        .and_then(|profile| profile.remove("inherits"))
+    {
+        let base = base
+            .as_str()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Field inherits in [tool.sncast.{}] must be a string",
+                    chain[chain.len() - 1]
+                )
+            })?
+            .to_string();
+        if chain.contains(&base) {
+            bail!(
+                "Profile inheritance cycle detected: {} -> {base}",
+                chain.join(" -> ")
+            );
+        }
+        let mut base_profile = get_named_profile(tool_sncast, &base)?.clone();
+        merge_profile(&mut base_profile, merged);
+        merged = base_profile;
+        chain.push(base);
+    }
+    Ok(merged)
+}
+
+fn get_named_profile<'a>(tool_sncast: &'a Value, profile: &str) -> Result<&'a Value> {
+    tool_sncast
+        .get(profile)
+        .ok_or_else(|| anyhow!("No field [tool.sncast.{}] found in package", profile))
+}
+
+/// Overrides fields of `base` with the ones from `profile`, merging nested tables (e.g. `networks`) field by field
+fn merge_profile(base: &mut Value, profile: Value) {
+    match (base, profile) {
+        (Value::Object(base), Value::Object(profile)) => {
+            for (field, value) in profile {
+                match base.get_mut(&field) {
+                    Some(base_value) => merge_profile(base_value, value),
+                    None => {
+                        base.insert(field, value);
+                    }
+                }
+            }
+        }
+        (base, profile) => *base = profile,
     }
 }
 
@@ -52,6 +173,161 @@ where
         .unwrap_or_default()
 }
 
+/// Accepts either a single string, which may hold multiple comma separated urls, or an array of strings.
+/// `${NAME}` in the urls is replaced with the value of the environment variable `NAME`, so that secrets
+/// (e.g. API keys) don't have to be committed
+pub fn get_url_list_property(tool: &Value, field: &str) -> Result<Vec<String>> {
+    get_list_property(tool, field, ',')?
+        .iter()
+        .map(|url| {
+            expand_env_variables(url)
+                .with_context(|| format!("Invalid value of field {field} in [tool.sncast]"))
+        })
+        .collect()
+}
+
+fn expand_env_variables(value: &str) -> Result<String> {
+    let mut result = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("Unclosed variable in {value}, expected `${{NAME}}`"))?;
+        let name = &rest[start + 2..start + end];
+        let variable =
+            env::var(name).map_err(|_| anyhow!("Environment variable {name} is not set"))?;
+        result.push_str(&variable);
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+/// Accepts either a single string, which may hold multiple colon separated paths, or an array of strings
+pub fn get_path_list_property(tool: &Value, field: &str) -> Result<Vec<Utf8PathBuf>> {
+    Ok(get_list_property(tool, field, ':')?
+        .into_iter()
+        .map(Utf8PathBuf::from)
+        .collect())
+}
+
+fn get_list_property(tool: &Value, field: &str, separator: char) -> Result<Vec<String>> {
+    match tool.get(field) {
+        Some(Value::String(values)) => Ok(split_list(values, separator)),
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|value| {
+                value
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| anyhow!("Invalid value of field {field} in [tool.sncast]"))
+            })
+            .collect(),
+        Some(_) => bail!("Invalid value of field {field} in [tool.sncast]"),
+        None => Ok(Vec::default()),
+    }
+}
+
+#[must_use]
+pub fn split_urls(urls: &str) -> Vec<String> {
+    split_list(urls, ',')
+}
+
+#[must_use]
+pub fn split_paths(paths: &str) -> Vec<Utf8PathBuf> {
+    split_list(paths, ':')
+        .into_iter()
+        .map(Utf8PathBuf::from)
+        .collect()
+}
+
+fn split_list(values: &str, separator: char) -> Vec<String> {
+    values
+        .split(separator)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn get_number_property<T>(tool: &Value, field: &str, default: T) -> Result<T>
+where
+    T: TryFrom<u64>,
+{
+    match tool.get(field) {
+        Some(value) => value
+            .as_u64()
+            .and_then(|value| T::try_from(value).ok())
+            .ok_or_else(|| anyhow!("Invalid value of field {field} in [tool.sncast]")),
+        None => Ok(default),
+    }
+}
+
+pub fn get_float_property(tool: &Value, field: &str, default: f64) -> Result<f64> {
+    match tool.get(field) {
+        Some(value) => value
+            .as_f64()
+            .ok_or_else(|| anyhow!("Invalid value of field {field} in [tool.sncast]")),
+        None => Ok(default),
+    }
+}
+
+pub fn get_bool_property(tool: &Value, field: &str) -> Result<bool> {
+    match tool.get(field) {
+        Some(value) => value
+            .as_bool()
+            .ok_or_else(|| anyhow!("Invalid value of field {field} in [tool.sncast]")),
+        None => Ok(false),
+    }
+}
+
+/// Accepts a table mapping chain ids (hex values or short strings) to network names
+pub fn get_networks_property(tool: &Value, field: &str) -> Result<Vec<(String, FieldElement)>> {
+    match tool.get(field) {
+        Some(Value::Object(networks)) => networks
+            .iter()
+            .map(|(chain_id, name)| {
+                let name = name.as_str().ok_or_else(|| {
+                    anyhow!("Invalid name of network {chain_id} in [tool.sncast]")
+                })?;
+                let chain_id = parse_chain_id(chain_id).with_context(|| {
+                    format!("Invalid chain id of network {name} in [tool.sncast]")
+                })?;
+                Ok((name.to_string(), chain_id))
+            })
+            .collect(),
+        Some(_) => bail!("Invalid value of field {field} in [tool.sncast]"),
+        None => Ok(Vec::default()),
+    }
+}
+
+/// Accepts a table mapping network names to faucet urls, which may reference environment variables like `url`
+pub fn get_faucets_property(tool: &Value, field: &str) -> Result<Vec<(String, String)>> {
+    match tool.get(field) {
+        Some(Value::Object(faucets)) => faucets
+            .iter()
+            .map(|(network, url)| {
+                let url = url
+                    .as_str()
+                    .ok_or_else(|| {
+                        anyhow!("Invalid faucet url of network {network} in [tool.sncast]")
+                    })
+                    .and_then(|url| {
+                        expand_env_variables(url).with_context(|| {
+                            format!("Invalid faucet url of network {network} in [tool.sncast]")
+                        })
+                    })?;
+                Ok((network.clone(), url))
+            })
+            .collect(),
+        Some(_) => bail!("Invalid value of field {field} in [tool.sncast]"),
+        None => Ok(Vec::default()),
+    }
+}
+
 pub fn get_scarb_manifest() -> Result<Utf8PathBuf> {
     get_scarb_manifest_for(<&Utf8Path>::from("."))
 }
@@ -153,10 +429,62 @@ pub fn get_package_metadata<'a>(
     Ok(package)
 }
 
+/// Profile passed with `--profile` takes precedence over the one set in the environment variable
+#[must_use]
+pub fn get_profile_name(profile: &Option<String>) -> Option<String> {
+    profile.clone().or_else(|| get_env_var(PROFILE_ENV_VAR))
+}
+
+/// Config is read from the file passed with `--config-file`, then from `snfoundry.toml` found in the current
+/// or parent directories (unless `--path-to-scarb-toml` is passed), and finally from `[tool.sncast]` in Scarb.toml
+pub fn parse_config(
+    profile: &Option<String>,
+    path_to_scarb_toml: &Option<Utf8PathBuf>,
+    config_file: &Option<Utf8PathBuf>,
+) -> Result<CastConfig> {
+    if let Some(config_file) = config_file {
+        if !config_file.exists() {
+            bail!("{config_file} file does not exist!");
+        }
+        return parse_snfoundry_config(profile, config_file);
+    }
+    if path_to_scarb_toml.is_none() {
+        if let Some(config_file) = find_snfoundry_config()? {
+            return parse_snfoundry_config(profile, &config_file);
+        }
+    }
+    parse_scarb_config(profile, path_to_scarb_toml)
+}
+
+fn find_snfoundry_config() -> Result<Option<Utf8PathBuf>> {
+    let current_dir = Utf8PathBuf::try_from(env::current_dir()?)
+        .context("Current directory path is not valid UTF-8")?;
+    Ok(current_dir
+        .ancestors()
+        .map(|dir| dir.join(SNFOUNDRY_CONFIG_FILE))
+        .find(|path| path.exists()))
+}
+
+pub fn parse_snfoundry_config(profile: &Option<String>, path: &Utf8PathBuf) -> Result<CastConfig> {
+    let profile = get_profile_name(profile);
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read config file {path}"))?;
+    let config: toml::Value =
+        toml::from_str(&contents).with_context(|| format!("Failed to parse config file {path}"))?;
+
+    match config.get("sncast") {
+        Some(sncast) => {
+            CastConfig::from_package_tool_sncast(&serde_json::to_value(sncast)?, &profile)
+        }
+        None => Ok(CastConfig::default()),
+    }
+}
+
 pub fn parse_scarb_config(
     profile: &Option<String>,
     path: &Option<Utf8PathBuf>,
 ) -> Result<CastConfig> {
+    let profile = get_profile_name(profile);
     let manifest_path = match path.clone() {
         Some(path) => {
             if !(path.exists()) {
@@ -175,7 +503,7 @@ pub fn parse_scarb_config(
 
     match get_package_tool_sncast(&metadata) {
         Ok(package_tool_sncast) => {
-            CastConfig::from_package_tool_sncast(package_tool_sncast, profile)
+            CastConfig::from_package_tool_sncast(package_tool_sncast, &profile)
         }
         Err(_) => Ok(CastConfig::default()),
     }
@@ -202,11 +530,16 @@ pub fn get_package_tool_sncast(metadata: &scarb_metadata::Metadata) -> Result<&V
 
 #[cfg(test)]
 mod tests {
+    use crate::helpers::constants::{
+        DEFAULT_BLOCK_ID, DEFAULT_FEE_MULTIPLIER, DEFAULT_RPC_RETRIES,
+        DEFAULT_RPC_RETRY_BASE_DELAY, DEFAULT_WAIT_RETRY_INTERVAL, DEFAULT_WAIT_TIMEOUT,
+    };
     use crate::helpers::scarb_utils::get_scarb_metadata;
-    use crate::helpers::scarb_utils::parse_scarb_config;
+    use crate::helpers::scarb_utils::{parse_config, parse_scarb_config};
     use camino::Utf8PathBuf;
     use sealed_test::prelude::rusty_fork_test;
     use sealed_test::prelude::sealed_test;
+    use starknet::core::types::FieldElement;
 
     #[test]
     fn test_parse_scarb_config_happy_case_with_profile() {
@@ -219,7 +552,10 @@ mod tests {
         .unwrap();
 
         assert_eq!(config.account, String::from("user1"));
-        assert_eq!(config.rpc_url, String::from("http://127.0.0.1:5055/rpc"));
+        assert_eq!(
+            config.rpc_urls,
+            vec![String::from("http://127.0.0.1:5055/rpc")]
+        );
     }
 
     #[test]
@@ -230,7 +566,211 @@ mod tests {
         )
         .unwrap();
         assert_eq!(config.account, String::from("user2"));
-        assert_eq!(config.rpc_url, String::from("http://127.0.0.1:5055/rpc"));
+        assert_eq!(
+            config.rpc_urls,
+            vec![String::from("http://127.0.0.1:5055/rpc")]
+        );
+    }
+
+    #[test]
+    fn test_parse_scarb_config_wait_params() {
+        let config = parse_scarb_config(
+            &Some(String::from("profile1")),
+            &Some(Utf8PathBuf::from("tests/data/files/correct_Scarb.toml")),
+        )
+        .unwrap();
+
+        assert_eq!(config.wait_params.get_timeout(), 300);
+        assert_eq!(config.wait_params.get_retry_interval(), 10);
+        assert_eq!(config.rpc_retries, 5);
+        assert_eq!(config.rpc_retry_base_delay, 1000);
+        assert!((config.fee_multiplier - 2.5).abs() < f64::EPSILON);
+        assert_eq!(config.default_block_id, "latest");
+        assert!(config.decode_strings);
+    }
+
+    #[test]
+    fn test_parse_scarb_config_default_wait_params() {
+        let config = parse_scarb_config(
+            &None,
+            &Some(Utf8PathBuf::from("tests/data/contracts/map/Scarb.toml")),
+        )
+        .unwrap();
+
+        assert_eq!(config.wait_params.get_timeout(), DEFAULT_WAIT_TIMEOUT);
+        assert_eq!(
+            config.wait_params.get_retry_interval(),
+            DEFAULT_WAIT_RETRY_INTERVAL
+        );
+        assert_eq!(config.rpc_retries, DEFAULT_RPC_RETRIES);
+        assert_eq!(config.rpc_retry_base_delay, DEFAULT_RPC_RETRY_BASE_DELAY);
+        assert!((config.fee_multiplier - DEFAULT_FEE_MULTIPLIER).abs() < f64::EPSILON);
+        assert!(!config.decode_strings);
+        assert_eq!(config.default_block_id, DEFAULT_BLOCK_ID);
+    }
+
+    #[test]
+    fn test_parse_scarb_config_multiple_urls() {
+        let config = parse_scarb_config(
+            &Some(String::from("profile3")),
+            &Some(Utf8PathBuf::from("tests/data/files/correct_Scarb.toml")),
+        )
+        .unwrap();
+        let config_with_array = parse_scarb_config(
+            &Some(String::from("profile4")),
+            &Some(Utf8PathBuf::from("tests/data/files/correct_Scarb.toml")),
+        )
+        .unwrap();
+
+        let expected = vec![
+            String::from("http://127.0.0.1:5055/rpc"),
+            String::from("http://127.0.0.1:5050/rpc"),
+        ];
+        assert_eq!(config.rpc_urls, expected);
+        assert_eq!(config_with_array.rpc_urls, expected);
+    }
+
+    #[test]
+    fn test_parse_scarb_config_multiple_accounts_files() {
+        let config = parse_scarb_config(
+            &Some(String::from("profile3")),
+            &Some(Utf8PathBuf::from("tests/data/files/correct_Scarb.toml")),
+        )
+        .unwrap();
+        let config_with_array = parse_scarb_config(
+            &Some(String::from("profile4")),
+            &Some(Utf8PathBuf::from("tests/data/files/correct_Scarb.toml")),
+        )
+        .unwrap();
+
+        let expected = vec![
+            Utf8PathBuf::from("../account-file"),
+            Utf8PathBuf::from("../shared-account-file"),
+        ];
+        assert_eq!(config.accounts_files, expected);
+        assert_eq!(config_with_array.accounts_files, expected);
+        assert_eq!(config.accounts_file(), Utf8PathBuf::from("../account-file"));
+    }
+
+    #[sealed_test(
+        files = ["tests/data/files/correct_Scarb.toml"],
+        env = [("SNCAST_TEST_RPC_KEY", "secret")]
+    )]
+    fn test_parse_scarb_config_url_with_env_variable() {
+        let config = parse_scarb_config(
+            &Some(String::from("profile6")),
+            &Some(Utf8PathBuf::from("correct_Scarb.toml")),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.rpc_urls,
+            vec![String::from("http://127.0.0.1:5055/rpc/secret")]
+        );
+
+        std::env::remove_var("SNCAST_TEST_RPC_KEY");
+        let error = parse_scarb_config(
+            &Some(String::from("profile6")),
+            &Some(Utf8PathBuf::from("correct_Scarb.toml")),
+        )
+        .unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Invalid value of field url in [tool.sncast]: Environment variable SNCAST_TEST_RPC_KEY is not set"
+        );
+    }
+
+    #[test]
+    fn test_parse_scarb_config_networks() {
+        let config = parse_scarb_config(
+            &Some(String::from("profile5")),
+            &Some(Utf8PathBuf::from("tests/data/files/correct_Scarb.toml")),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.networks,
+            vec![
+                (
+                    String::from("devnet"),
+                    FieldElement::from_hex_be("0x534e5f474f45524c49").unwrap()
+                ),
+                (
+                    String::from("my-appchain"),
+                    FieldElement::from_hex_be("0x4d595f415050434841494e").unwrap()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_scarb_config_faucets() {
+        let config = parse_scarb_config(
+            &Some(String::from("profile5")),
+            &Some(Utf8PathBuf::from("tests/data/files/correct_Scarb.toml")),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.faucets,
+            vec![(
+                String::from("my-appchain"),
+                String::from("http://127.0.0.1:5055/faucet")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_scarb_config_inherited_profile() {
+        let config = parse_scarb_config(
+            &Some(String::from("profile8")),
+            &Some(Utf8PathBuf::from("tests/data/files/correct_Scarb.toml")),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.rpc_urls,
+            vec![String::from("http://127.0.0.1:5050/rpc")]
+        );
+        assert_eq!(config.account, String::from("user2"));
+        assert!((config.fee_multiplier - 3.0).abs() < f64::EPSILON);
+        assert_eq!(config.networks.len(), 3);
+        assert!(config
+            .networks
+            .iter()
+            .any(|(name, _)| name == "my-other-appchain"));
+    }
+
+    #[test]
+    fn test_parse_scarb_config_inheritance_cycle() {
+        let error = parse_scarb_config(
+            &Some(String::from("cycle1")),
+            &Some(Utf8PathBuf::from("tests/data/files/correct_Scarb.toml")),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Profile inheritance cycle detected: cycle1 -> cycle2 -> cycle1"
+        );
+    }
+
+    #[sealed_test(
+        files = ["tests/data/files/correct_Scarb.toml"],
+        env = [("SNCAST_PROFILE", "profile1")]
+    )]
+    fn test_parse_scarb_config_profile_from_env() {
+        let path = Some(Utf8PathBuf::from("correct_Scarb.toml"));
+
+        let config = parse_scarb_config(&None, &path).unwrap();
+        assert_eq!(config.account, String::from("user3"));
+
+        let config = parse_scarb_config(&Some(String::from("profile2")), &path).unwrap();
+        assert_eq!(config.account, String::from("user100"));
+
+        std::env::remove_var("SNCAST_PROFILE");
+        let config = parse_scarb_config(&None, &path).unwrap();
+        assert_eq!(config.account, String::from("user1"));
     }
 
     #[test]
@@ -244,7 +784,7 @@ mod tests {
     fn test_parse_scarb_config_no_path_not_found() {
         let config = parse_scarb_config(&None, &None).unwrap();
 
-        assert!(config.rpc_url.is_empty());
+        assert!(config.rpc_urls.is_empty());
         assert!(config.account.is_empty());
     }
 
@@ -256,7 +796,7 @@ mod tests {
         )
         .unwrap();
 
-        assert!(config.rpc_url.is_empty());
+        assert!(config.rpc_urls.is_empty());
         assert!(config.account.is_empty());
     }
 
@@ -288,7 +828,7 @@ mod tests {
     fn test_parse_scarb_config_no_profile_no_path() {
         let config = parse_scarb_config(&None, &None).unwrap();
 
-        assert!(config.rpc_url.is_empty());
+        assert!(config.rpc_urls.is_empty());
         assert!(config.account.is_empty());
     }
 
@@ -296,7 +836,59 @@ mod tests {
     fn test_parse_scarb_config_no_path() {
         let config = parse_scarb_config(&Some(String::from("myprofile")), &None).unwrap();
 
-        assert_eq!(config.rpc_url, String::from("http://127.0.0.1:5055/rpc"));
+        assert_eq!(
+            config.rpc_urls,
+            vec![String::from("http://127.0.0.1:5055/rpc")]
+        );
+        assert_eq!(config.account, String::from("user1"));
+    }
+
+    #[test]
+    fn test_parse_config_from_config_file() {
+        let config = parse_config(
+            &Some(String::from("profile1")),
+            &Some(Utf8PathBuf::from("tests/data/contracts/map/Scarb.toml")),
+            &Some(Utf8PathBuf::from("tests/data/files/snfoundry.toml")),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.rpc_urls,
+            vec![String::from("http://127.0.0.1:5055/rpc")]
+        );
+        assert_eq!(config.account, String::from("user3"));
+        assert_eq!(config.wait_params.get_timeout(), 500);
+    }
+
+    #[test]
+    fn test_parse_config_file_does_not_exist() {
+        let error = parse_config(
+            &None,
+            &None,
+            &Some(Utf8PathBuf::from("nonexistent/snfoundry.toml")),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "nonexistent/snfoundry.toml file does not exist!"
+        );
+    }
+
+    #[sealed_test(files = [
+        "tests/data/files/snfoundry.toml",
+        "tests/data/contracts/constructor_with_params/Scarb.toml"
+    ])]
+    fn test_parse_config_prefers_snfoundry_toml() {
+        let config = parse_config(&None, &None, &None).unwrap();
+
+        assert_eq!(config.account, String::from("user2"));
+    }
+
+    #[sealed_test(files = ["tests/data/contracts/constructor_with_params/Scarb.toml"])]
+    fn test_parse_config_without_snfoundry_toml() {
+        let config = parse_config(&Some(String::from("myprofile")), &None, &None).unwrap();
+
         assert_eq!(config.account, String::from("user1"));
     }
 
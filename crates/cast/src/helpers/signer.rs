@@ -0,0 +1,212 @@
+use crate::helpers::constants::KEYSTORE_PASSWORD_ENV_VAR;
+use crate::{get_keystore_password, parse_number};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use camino::Utf8PathBuf;
+use starknet::core::crypto::Signature;
+use starknet::core::types::FieldElement;
+use starknet::signers::{LocalWallet, Signer, SigningKey, VerifyingKey};
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, OnceLock};
+
+/// Signer of the account used by sncast - either a local private key (from the accounts file or keystore)
+/// or an external command passed with `--signer-command`
+#[derive(Clone, Debug)]
+pub enum CastSigner {
+    Local(LocalWallet),
+    Keystore(KeystoreSigner),
+    Command(CommandSigner),
+}
+
+/// Private key stored in an encrypted keystore file. The keystore is decrypted (prompting for the password
+/// if it is not set in the environment) the first time the key is needed, and the key is shared between clones
+#[derive(Clone, Debug)]
+pub struct KeystoreSigner {
+    path: Utf8PathBuf,
+    wallet: Arc<OnceLock<LocalWallet>>,
+}
+
+impl KeystoreSigner {
+    #[must_use]
+    pub fn new(path: &Utf8PathBuf) -> Self {
+        KeystoreSigner {
+            path: path.clone(),
+            wallet: Arc::new(OnceLock::new()),
+        }
+    }
+
+    pub fn wallet(&self) -> Result<&LocalWallet> {
+        if let Some(wallet) = self.wallet.get() {
+            return Ok(wallet);
+        }
+
+        let password = get_keystore_password(KEYSTORE_PASSWORD_ENV_VAR)?;
+        let key = SigningKey::from_keystore(&self.path, &password)
+            .with_context(|| format!("Failed to decrypt keystore {}", self.path))?;
+
+        Ok(self.wallet.get_or_init(|| LocalWallet::from(key)))
+    }
+}
+
+/// Delegates signing to an external process, so that sncast never has access to the private key.
+///
+/// The command is run with `sh -c`. The hash to sign is written to its stdin as a 0x prefixed hex string
+/// followed by a newline. The command is expected to exit with status 0 and print the `r` and `s` parts
+/// of the signature to stdout, separated by whitespace or a comma (either hex or decimal).
+#[derive(Clone, Debug)]
+pub struct CommandSigner {
+    command: String,
+}
+
+impl CommandSigner {
+    #[must_use]
+    pub fn new(command: &str) -> Self {
+        CommandSigner {
+            command: command.to_string(),
+        }
+    }
+
+    pub fn sign(&self, hash: &FieldElement) -> Result<Signature> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to start signer command `{}`", self.command))?;
+
+        child
+            .stdin
+            .take()
+            .context("Failed to open stdin of signer command")?
+            .write_all(format!("{hash:#x}\n").as_bytes())
+            .context("Failed to write hash to signer command")?;
+
+        let output = child
+            .wait_with_output()
+            .context("Failed to wait for signer command")?;
+        if !output.status.success() {
+            bail!(
+                "Signer command exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let stdout =
+            String::from_utf8(output.stdout).context("Signer command output is not valid UTF-8")?;
+        let parts: Vec<&str> = stdout
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|part| !part.is_empty())
+            .collect();
+        let [r, s] = parts[..] else {
+            bail!(
+                "Expected signer command to output r and s parts of the signature, got: {}",
+                stdout.trim()
+            );
+        };
+
+        Ok(Signature {
+            r: parse_number(r).context("Failed to parse r part of the signature")?,
+            s: parse_number(s).context("Failed to parse s part of the signature")?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SignerError(String);
+
+impl Display for SignerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SignerError {}
+
+#[async_trait]
+impl Signer for CastSigner {
+    type GetPublicKeyError = SignerError;
+    type SignError = SignerError;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        match self {
+            CastSigner::Local(wallet) => wallet
+                .get_public_key()
+                .await
+                .map_err(|error| SignerError(error.to_string())),
+            CastSigner::Keystore(keystore_signer) => {
+                let wallet = keystore_signer
+                    .wallet()
+                    .map_err(|error| SignerError(format!("{error:#}")))?;
+                wallet
+                    .get_public_key()
+                    .await
+                    .map_err(|error| SignerError(error.to_string()))
+            }
+            CastSigner::Command(_) => Err(SignerError(
+                "Public key is not available when using --signer-command".to_string(),
+            )),
+        }
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        match self {
+            CastSigner::Local(wallet) => wallet
+                .sign_hash(hash)
+                .await
+                .map_err(|error| SignerError(error.to_string())),
+            CastSigner::Keystore(keystore_signer) => {
+                let wallet = keystore_signer
+                    .wallet()
+                    .map_err(|error| SignerError(format!("{error:#}")))?;
+                wallet
+                    .sign_hash(hash)
+                    .await
+                    .map_err(|error| SignerError(error.to_string()))
+            }
+            CastSigner::Command(command_signer) => command_signer
+                .sign(hash)
+                .map_err(|error| SignerError(format!("{error:#}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandSigner;
+    use starknet::core::types::FieldElement;
+
+    #[test]
+    fn test_command_signer() {
+        let signer = CommandSigner::new("read hash; echo \"$hash, 0x2\"");
+
+        let signature = signer.sign(&FieldElement::from(0x123_u32)).unwrap();
+
+        assert_eq!(signature.r, FieldElement::from(0x123_u32));
+        assert_eq!(signature.s, FieldElement::TWO);
+    }
+
+    #[test]
+    fn test_command_signer_fails() {
+        let signer = CommandSigner::new("echo 'device locked' >&2; exit 1");
+
+        let error = signer.sign(&FieldElement::ONE).unwrap_err();
+
+        assert!(error.to_string().contains("device locked"));
+    }
+
+    #[test]
+    fn test_command_signer_invalid_output() {
+        let signer = CommandSigner::new("echo 0x1");
+
+        let error = signer.sign(&FieldElement::ONE).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("Expected signer command to output r and s parts of the signature"));
+    }
+}
@@ -1,39 +1,55 @@
 use anyhow::{anyhow, bail, Context, Error, Result};
-use camino::Utf8PathBuf;
-use helpers::constants::{DEFAULT_RETRIES, KEYSTORE_PASSWORD_ENV_VAR, UDC_ADDRESS};
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::ColorChoice;
+use helpers::account_factory::AccountType;
+use helpers::constants::{
+    CONTRACT_NOT_FOUND_ERROR_CODE, DEFAULT_WAIT_RETRY_INTERVAL, DEFAULT_WAIT_TIMEOUT, ETH_ADDRESS,
+    FEE_ESTIMATION_FAILED_MESSAGE, KNOWN_NETWORKS, NO_COLOR_ENV_VAR, RPC_URL_ENV_VAR,
+    SNCAST_KEYSTORE_PASSWORD_ENV_VAR, STARKNET_RPC_ENV_VAR, UDC_ADDRESS,
+};
+use helpers::retry_transport::RetryTransport;
+use helpers::signer::{CastSigner, CommandSigner, KeystoreSigner};
 use rand::rngs::OsRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use starknet::core::utils::UdcUniqueness::{NotUnique, Unique};
-use starknet::core::utils::{UdcUniqueSettings, UdcUniqueness};
+use starknet::core::utils::{
+    cairo_short_string_to_felt, get_selector_from_name, starknet_keccak, UdcUniqueSettings,
+    UdcUniqueness,
+};
 use starknet::providers::jsonrpc::RpcError::{Code, Unknown};
 use starknet::providers::ProviderError::Other;
 use starknet::{
-    accounts::{ExecutionEncoding, SingleOwnerAccount},
-    providers::{
-        jsonrpc::{HttpTransport, JsonRpcClient},
-        Provider, ProviderError,
-    },
-    signers::{LocalWallet, SigningKey},
+    accounts::{Account as _, AccountError, ConnectedAccount as _, SingleOwnerAccount},
+    providers::{jsonrpc::JsonRpcClient, Provider, ProviderError},
+    signers::{LocalWallet, Signer, SigningKey},
 };
 use starknet::{
     core::types::{
         BlockId,
         BlockTag::{Latest, Pending},
-        ExecutionResult, FieldElement, StarknetError,
+        ExecutionResult, FeeEstimate, FieldElement, StarknetError, TransactionExecutionStatus,
+        TransactionStatus,
     },
     providers::{MaybeUnknownErrorCode, StarknetErrorWithMessage},
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::io::{BufRead, BufReader, BufWriter, IsTerminal, Write};
 use std::ops::Deref;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
 use std::thread::sleep;
 use std::time::Duration;
 use std::{env, fs};
 use url::Url;
+use zeroize::Zeroizing;
 
+pub mod client;
 pub mod helpers;
+pub mod starknet_commands;
 
 #[derive(Deserialize, Serialize, Clone)]
 struct Account {
@@ -43,6 +59,8 @@ struct Account {
     salt: Option<String>,
     deployed: Option<bool>,
     class_hash: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    account_type: Option<AccountType>,
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -55,6 +73,63 @@ pub enum ValueFormat {
     Hex,
 }
 
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidatedWaitParams {
+    timeout: u16,
+    retry_interval: u8,
+}
+
+impl ValidatedWaitParams {
+    pub fn new(retry_interval: u8, timeout: u16) -> Result<Self> {
+        if retry_interval == 0 || timeout == 0 {
+            bail!("Wait timeout and retry interval must be greater than 0");
+        }
+        if u16::from(retry_interval) > timeout {
+            bail!("Wait retry interval ({retry_interval}s) cannot be greater than wait timeout ({timeout}s)");
+        }
+
+        Ok(ValidatedWaitParams {
+            timeout,
+            retry_interval,
+        })
+    }
+
+    #[must_use]
+    pub fn get_timeout(&self) -> u16 {
+        self.timeout
+    }
+
+    #[must_use]
+    pub fn get_retry_interval(&self) -> u8 {
+        self.retry_interval
+    }
+
+    #[must_use]
+    pub fn get_retries(&self) -> u16 {
+        self.timeout / u16::from(self.retry_interval)
+    }
+
+    #[must_use]
+    pub fn remaining_time(&self, retries_left: u16) -> u16 {
+        retries_left * u16::from(self.retry_interval)
+    }
+}
+
+impl Default for ValidatedWaitParams {
+    fn default() -> Self {
+        ValidatedWaitParams {
+            timeout: DEFAULT_WAIT_TIMEOUT,
+            retry_interval: DEFAULT_WAIT_RETRY_INTERVAL,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WaitForTx {
+    pub wait: bool,
+    pub wait_params: ValidatedWaitParams,
+}
+
 impl ValueFormat {
     #[must_use]
     pub fn format_u64(&self, input: u64) -> String {
@@ -66,83 +141,459 @@ impl ValueFormat {
 
     #[must_use]
     pub fn format_str(&self, input: &str) -> String {
-        if let Ok(field) = FieldElement::from_str(input) {
-            return match self {
-                ValueFormat::Int => format!("{field:#}"),
-                ValueFormat::Hex | ValueFormat::Default => format!("{field:#x}"),
-            };
-        }
-
-        input.to_string()
+        FormattedStr(*self, input).to_string()
     }
 
     #[must_use]
     pub fn format_json_value(&self, value: &Value) -> Option<String> {
+        self.format_json_value_with(value, false)
+    }
+
+    /// Formats the value, appending the decoded text to every felt which is a valid Cairo short string
+    #[must_use]
+    pub fn format_json_value_with_short_strings(&self, value: &Value) -> Option<String> {
+        self.format_json_value_with(value, true)
+    }
+
+    /// Replaces every felt which is a valid Cairo short string with the decoded text,
+    /// returns `None` if there are no such felts in the value
+    #[must_use]
+    pub fn decode_short_strings(&self, value: &Value) -> Option<Value> {
+        let mut found = false;
+        let decoded = self.replace_short_strings(value, &mut found);
+        found.then_some(decoded)
+    }
+
+    /// Formats every felt in the value while keeping its JSON structure;
+    /// `decoded` fields are already formatted and are left untouched
+    #[must_use]
+    pub fn format_json_structure(&self, value: &Value) -> Value {
+        match value {
+            Value::String(s) => Value::String(self.format_str(s)),
+            Value::Array(arr) => Value::Array(
+                arr.iter()
+                    .map(|item| self.format_json_structure(item))
+                    .collect(),
+            ),
+            Value::Object(obj) => Value::Object(
+                obj.iter()
+                    .map(|(key, item)| {
+                        let item = if key == DECODED_FIELD {
+                            item.clone()
+                        } else {
+                            self.format_json_structure(item)
+                        };
+                        (key.clone(), item)
+                    })
+                    .collect(),
+            ),
+            value => value.clone(),
+        }
+    }
+
+    fn replace_short_strings(&self, value: &Value, found: &mut bool) -> Value {
         match value {
+            Value::String(s) => Value::String(match felt_short_string(s) {
+                Some(text) => {
+                    *found = true;
+                    text
+                }
+                None => self.format_str(s),
+            }),
+            Value::Array(arr) => Value::Array(
+                arr.iter()
+                    .map(|item| self.replace_short_strings(item, found))
+                    .collect(),
+            ),
+            Value::Object(obj) => Value::Object(
+                obj.iter()
+                    .map(|(key, item)| (key.clone(), self.replace_short_strings(item, found)))
+                    .collect(),
+            ),
+            value => value.clone(),
+        }
+    }
+
+    fn format_json_value_with(&self, value: &Value, short_strings: bool) -> Option<String> {
+        (!value.is_null()).then(|| {
+            FormattedValue {
+                value,
+                value_format: *self,
+                short_strings,
+            }
+            .to_string()
+        })
+    }
+}
+
+/// Felt in the given format; other strings are written as they are
+struct FormattedStr<'a>(ValueFormat, &'a str);
+
+impl std::fmt::Display for FormattedStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let FormattedStr(value_format, input) = *self;
+        match FieldElement::from_str(input) {
+            Ok(field) => match value_format {
+                ValueFormat::Int => write!(f, "{field:#}"),
+                ValueFormat::Hex | ValueFormat::Default => write!(f, "{field:#x}"),
+            },
+            Err(_) => f.write_str(input),
+        }
+    }
+}
+
+/// Value formatted by `ValueFormat::format_json_value`; items of arrays and objects are written one by one,
+/// without collecting them into intermediate strings, which matters for responses with many felts.
+/// Null items are skipped
+#[derive(Clone, Copy)]
+struct FormattedValue<'a> {
+    value: &'a Value,
+    value_format: ValueFormat,
+    short_strings: bool,
+}
+
+impl<'a> FormattedValue<'a> {
+    fn fmt_items(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        items: impl Iterator<Item = (Option<&'a String>, &'a Value)>,
+    ) -> std::fmt::Result {
+        for (index, (key, value)) in items.filter(|(_, value)| !value.is_null()).enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            if let Some(key) = key {
+                write!(f, "{key}: ")?;
+            }
+            write!(f, "{}", FormattedValue { value, ..*self })?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for FormattedValue<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value {
             Value::Number(n) => {
                 let n = n
                     .as_u64()
                     .unwrap_or_else(|| panic!("failed to convert {n} to u64"));
-                Some(self.format_u64(n))
+                match self.value_format {
+                    ValueFormat::Default | ValueFormat::Int => write!(f, "{n}"),
+                    ValueFormat::Hex => write!(f, "{n:#x}"),
+                }
+            }
+            Value::String(s) => {
+                write!(f, "{}", FormattedStr(self.value_format, s))?;
+                match felt_short_string(s).filter(|_| self.short_strings) {
+                    Some(text) => write!(f, " ('{text}')"),
+                    None => Ok(()),
+                }
             }
-            Value::String(s) => Some(self.format_str(s)),
             Value::Array(arr) => {
-                let arr_as_string = arr
-                    .iter()
-                    .filter_map(|item| self.format_json_value(item))
-                    .collect::<Vec<String>>()
-                    .join(", ");
-                Some(format!("[{arr_as_string}]"))
+                f.write_str("[")?;
+                self.fmt_items(f, arr.iter().map(|item| (None, item)))?;
+                f.write_str("]")
+            }
+            Value::Object(obj) => {
+                f.write_str("{")?;
+                self.fmt_items(f, obj.iter().map(|(key, item)| (Some(key), item)))?;
+                f.write_str("}")
             }
-            _ => None,
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Null => Ok(()),
         }
     }
 }
 
-pub fn get_provider(url: &str) -> Result<JsonRpcClient<HttpTransport>> {
+pub fn get_provider(
+    url: &str,
+    max_retries: u8,
+    retry_base_delay_ms: u16,
+) -> Result<JsonRpcClient<RetryTransport>> {
     raise_if_empty(url, "RPC url")?;
     let parsed_url = Url::parse(url)?;
-    let provider = JsonRpcClient::new(HttpTransport::new(parsed_url));
+    let provider = JsonRpcClient::new(RetryTransport::new(
+        parsed_url,
+        max_retries,
+        retry_base_delay_ms,
+    ));
     Ok(provider)
 }
 
-pub async fn get_chain_id(provider: &JsonRpcClient<HttpTransport>) -> Result<FieldElement> {
-    provider.chain_id().await.context("Couldn't fetch chain_id")
+/// Returns provider for the first of `urls` which responds to a `chain_id` request.
+/// With a single url, it is used right away without probing it
+pub async fn get_provider_with_fallbacks(
+    urls: &[String],
+    max_retries: u8,
+    retry_base_delay_ms: u16,
+) -> Result<JsonRpcClient<RetryTransport>> {
+    match urls {
+        [] => bail!("RPC url not passed nor found in Scarb.toml. Pass it with --url or set {RPC_URL_ENV_VAR} or {STARKNET_RPC_ENV_VAR} environment variable"),
+        [url] => get_provider(url, max_retries, retry_base_delay_ms),
+        _ => {
+            let mut errors = vec![];
+            for url in urls {
+                // probe without retries, so that an unreachable url fails over quickly
+                let probe_result = match get_provider(url, 0, retry_base_delay_ms) {
+                    Ok(probe) => probe.chain_id().await.context("Couldn't fetch chain_id"),
+                    Err(error) => Err(error),
+                };
+                match probe_result {
+                    Ok(chain_id) => {
+                        warn_on_chain_id_mismatch(url, chain_id);
+                        // the probed provider is used from now on, so its chain id does not have to be fetched again
+                        FETCHED_CHAIN_ID.set(chain_id).ok();
+                        eprintln!("Using RPC url {url}");
+                        return get_provider(url, max_retries, retry_base_delay_ms);
+                    }
+                    Err(error) => errors.push(format!("{url}: {error:#}")),
+                }
+            }
+            bail!("All RPC urls failed:\n{}", errors.join("\n"))
+        }
+    }
+}
+
+/// Fails with a single clear error if the RPC url can't be reached, instead of failing at different points of the command.
+/// The url is probed without retries, so that the check fails fast. With multiple urls, the one that responds
+/// has already been found by `get_provider_with_fallbacks`
+pub async fn check_connection(urls: &[String], retry_base_delay_ms: u16) -> Result<()> {
+    if let [url] = urls {
+        let probe = get_provider(url, 0, retry_base_delay_ms)?;
+        let chain_id = probe
+            .chain_id()
+            .await
+            .map_err(|error| anyhow!("Cannot reach RPC at {url}: {error}"))?;
+        warn_on_chain_id_mismatch(url, chain_id);
+        FETCHED_CHAIN_ID.set(chain_id).ok();
+    }
+    Ok(())
+}
+
+fn warn_on_chain_id_mismatch(url: &str, chain_id: FieldElement) {
+    if let Some(expected) = CHAIN_ID.get().filter(|expected| **expected != chain_id) {
+        eprintln!(
+            "Warning: Chain id {} returned by {url} differs from --chain-id {}",
+            decode_chain_id(chain_id),
+            decode_chain_id(*expected)
+        );
+    }
+}
+
+static CHAIN_ID: OnceLock<FieldElement> = OnceLock::new();
+static FETCHED_CHAIN_ID: OnceLock<FieldElement> = OnceLock::new();
+
+/// Makes `get_chain_id` return `chain_id` instead of fetching it from the provider
+pub fn set_chain_id(chain_id: FieldElement) {
+    CHAIN_ID.set(chain_id).ok();
+}
+
+/// Returns the chain id set with `--chain-id`, or fetches it from the provider.
+/// The fetched chain id is cached, so the provider is queried at most once per process
+pub async fn get_chain_id(provider: &JsonRpcClient<RetryTransport>) -> Result<FieldElement> {
+    match CHAIN_ID.get() {
+        Some(chain_id) => Ok(*chain_id),
+        None => get_or_fetch_chain_id(&FETCHED_CHAIN_ID, || provider.chain_id()).await,
+    }
+}
+
+/// Returns the network name (e.g. `alpha-goerli`) of the provider, using the cached chain id
+pub async fn get_network_name(provider: &JsonRpcClient<RetryTransport>) -> Result<String> {
+    Ok(chain_id_to_network_name(get_chain_id(provider).await?))
+}
+
+async fn get_or_fetch_chain_id<F, Fut, E>(
+    cache: &OnceLock<FieldElement>,
+    fetch: F,
+) -> Result<FieldElement>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = std::result::Result<FieldElement, E>>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    if let Some(chain_id) = cache.get() {
+        return Ok(*chain_id);
+    }
+    let chain_id = fetch().await.context("Couldn't fetch chain_id")?;
+    Ok(*cache.get_or_init(|| chain_id))
+}
+
+/// Parses a chain id passed as a hex value, a short string (e.g. `SN_GOERLI`) or a network name (e.g. `alpha-goerli`)
+pub fn parse_chain_id(value: &str) -> Result<FieldElement> {
+    if value.starts_with("0x") {
+        return FieldElement::from_hex_be(value)
+            .with_context(|| format!("Failed to parse chain id {value}"));
+    }
+    let name = KNOWN_NETWORKS
+        .iter()
+        .find(|(network_name, _)| *network_name == value)
+        .map_or(value, |(_, chain_id)| chain_id);
+    cairo_short_string_to_felt(name).with_context(|| format!("Failed to parse chain id {value}"))
 }
 
-fn get_account_info(name: &str, chain_id: FieldElement, path: &Utf8PathBuf) -> Result<Account> {
+fn get_account_info(
+    name: &str,
+    chain_id: FieldElement,
+    accounts_files: &[Utf8PathBuf],
+) -> Result<Account> {
     raise_if_empty(name, "Account name")?;
+    if accounts_files.iter().all(|path| !path.exists()) {
+        account_file_exists(&accounts_files.first().cloned().unwrap_or_default())?;
+    }
+    let network_name = chain_id_to_network_name(chain_id);
+
+    find_account(name, &network_name, accounts_files)?
+        .map(|(account, _)| account)
+        .ok_or_else(|| anyhow!("Account {} not found under network {}", name, network_name))
+}
+
+/// Returns the first of the accounts files in which the account is defined, if any
+pub fn get_accounts_file_with_account(
+    name: &str,
+    chain_id: FieldElement,
+    accounts_files: &[Utf8PathBuf],
+) -> Result<Option<Utf8PathBuf>> {
+    let network_name = chain_id_to_network_name(chain_id);
+    Ok(find_account(name, &network_name, accounts_files)?.map(|(_, accounts_file)| accounts_file))
+}
+
+/// Accounts files are searched in order and the first one defining the account takes precedence.
+/// Fails if the account is defined with different addresses in more than one file
+fn find_account(
+    name: &str,
+    network_name: &str,
+    accounts_files: &[Utf8PathBuf],
+) -> Result<Option<(Account, Utf8PathBuf)>> {
+    let mut found: Option<(Account, Utf8PathBuf)> = None;
+    for path in accounts_files.iter().filter(|path| path.exists()) {
+        let Some(account) = read_account_info(name, network_name, path)? else {
+            continue;
+        };
+        match &found {
+            None => found = Some((account, path.clone())),
+            Some((found_account, found_path)) => {
+                if parse_account_address(found_account)? != parse_account_address(&account)? {
+                    bail!("Account {name} is defined with different addresses in {found_path} and {path}");
+                }
+            }
+        }
+    }
+
+    Ok(found)
+}
+
+fn read_account_info(
+    name: &str,
+    network_name: &str,
+    path: &Utf8PathBuf,
+) -> Result<Option<Account>> {
     let file_content =
         fs::read_to_string(path).with_context(|| format!("Cannot read a file {path}"))?;
     let accounts: HashMap<String, HashMap<String, Account>> =
         serde_json::from_str(&file_content)
             .with_context(|| format!("Cannot parse file {path} to JSON"))?;
-    let network_name = chain_id_to_network_name(chain_id);
-    let account = accounts
-        .get(&network_name)
+
+    Ok(accounts
+        .get(network_name)
         .and_then(|accounts_map| accounts_map.get(name))
-        .cloned();
+        .cloned())
+}
+
+/// Where the keystore password is read from instead of the environment or the interactive prompt
+#[derive(Clone, Debug)]
+pub enum KeystorePasswordSource {
+    /// First line of the file
+    File(Utf8PathBuf),
+    /// First line of stdin, which can be read only once per process
+    Stdin,
+}
+
+static KEYSTORE_PASSWORD_SOURCE: OnceLock<KeystorePasswordSource> = OnceLock::new();
 
-    account.ok_or_else(|| anyhow!("Account {} not found under network {}", name, network_name))
+/// Makes `get_keystore_password` read the password from `source` (e.g. `--password-file`)
+pub fn set_keystore_password_source(source: KeystorePasswordSource) {
+    KEYSTORE_PASSWORD_SOURCE.set(source).ok();
+}
+
+/// Reads the keystore password from the source set with `set_keystore_password_source` or from the environment,
+/// prompting for it (with hidden input) if neither is set. Fails instead of prompting when there is no terminal
+/// to read the password from. The password is zeroed in memory when the returned value is dropped
+pub fn get_keystore_password(env_var: &str) -> Result<Zeroizing<String>> {
+    match KEYSTORE_PASSWORD_SOURCE.get() {
+        Some(KeystorePasswordSource::File(path)) => {
+            let file = fs::File::open(path)
+                .with_context(|| format!("Failed to read keystore password file {path}"))?;
+            return read_password_line(BufReader::new(file))
+                .with_context(|| format!("Failed to read keystore password file {path}"));
+        }
+        Some(KeystorePasswordSource::Stdin) => {
+            return read_password_line(std::io::stdin().lock())
+                .context("Failed to read keystore password from stdin");
+        }
+        None => {}
+    }
+    if let Ok(password) = env::var(env_var).or_else(|_| env::var(SNCAST_KEYSTORE_PASSWORD_ENV_VAR))
+    {
+        return Ok(Zeroizing::new(password));
+    }
+    if !std::io::stdin().is_terminal() {
+        bail!("Keystore password is required, but there is no terminal to prompt for it. Set the {SNCAST_KEYSTORE_PASSWORD_ENV_VAR} environment variable or pass --password-file or --password-stdin instead");
+    }
+    rpassword::prompt_password("Enter keystore password: ")
+        .map(Zeroizing::new)
+        .context("Failed to read keystore password")
 }
 
-pub fn get_keystore_password(env_var: &str) -> std::io::Result<String> {
-    match env::var(env_var) {
-        Ok(password) => Ok(password),
-        _ => rpassword::prompt_password("Enter password: "),
+/// Reads the first line without the line ending; the buffer is zeroed if reading fails
+fn read_password_line(mut reader: impl BufRead) -> Result<Zeroizing<String>> {
+    let mut password = Zeroizing::new(String::new());
+    reader.read_line(&mut password)?;
+    // truncating does not reallocate, so the line ending does not leave a copy of the password behind
+    let length = password.trim_end_matches(['\n', '\r']).len();
+    password.truncate(length);
+    if password.is_empty() {
+        bail!("Keystore password is empty");
     }
+    Ok(password)
+}
+
+/// Returns the value of the environment variable, treating empty values as not set
+#[must_use]
+pub fn get_env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+static CUSTOM_NETWORKS: OnceLock<Vec<(String, FieldElement)>> = OnceLock::new();
+
+/// Makes `chain_id_to_network_name` use the names of custom networks (e.g. from `[tool.sncast.networks]` in Scarb.toml)
+pub fn set_custom_networks(networks: Vec<(String, FieldElement)>) {
+    CUSTOM_NETWORKS.set(networks).ok();
 }
 
+/// Returns the name of a custom network with the chain id if there is one, otherwise the built-in network name
+/// or the decoded chain id
 #[must_use]
 pub fn chain_id_to_network_name(chain_id: FieldElement) -> String {
-    let decoded = decode_chain_id(chain_id);
+    network_name(
+        chain_id,
+        CUSTOM_NETWORKS.get().map_or(&[][..], Vec::as_slice),
+    )
+}
 
-    match &decoded[..] {
-        "SN_GOERLI" => "alpha-goerli".into(),
-        "SN_GOERLI2" => "alpha-goerli2".into(),
-        "SN_MAIN" => "alpha-mainnet".into(),
-        _ => decoded,
+fn network_name(chain_id: FieldElement, custom_networks: &[(String, FieldElement)]) -> String {
+    if let Some((name, _)) = custom_networks
+        .iter()
+        .find(|(_, custom_chain_id)| *custom_chain_id == chain_id)
+    {
+        return name.clone();
     }
+    let decoded = decode_chain_id(chain_id);
+
+    KNOWN_NETWORKS
+        .iter()
+        .find(|(_, known_chain_id)| *known_chain_id == decoded)
+        .map_or(decoded, |(network_name, _)| (*network_name).to_string())
 }
 
 #[must_use]
@@ -159,25 +610,53 @@ pub fn decode_chain_id(chain_id: FieldElement) -> String {
 
 pub async fn get_account<'a>(
     account: &str,
-    accounts_file: &Utf8PathBuf,
-    provider: &'a JsonRpcClient<HttpTransport>,
+    accounts_files: &[Utf8PathBuf],
+    provider: &'a JsonRpcClient<RetryTransport>,
     keystore: &Utf8PathBuf,
-) -> Result<SingleOwnerAccount<&'a JsonRpcClient<HttpTransport>, LocalWallet>> {
+    signer_command: &str,
+) -> Result<SingleOwnerAccount<&'a JsonRpcClient<RetryTransport>, CastSigner>> {
+    let (account, _) =
+        get_account_with_signer(account, accounts_files, provider, keystore, signer_command)
+            .await?;
+    Ok(account)
+}
+
+/// Same as `get_account`, but also returns the signer, so that transactions can be signed without being sent
+pub async fn get_account_with_signer<'a>(
+    account: &str,
+    accounts_files: &[Utf8PathBuf],
+    provider: &'a JsonRpcClient<RetryTransport>,
+    keystore: &Utf8PathBuf,
+    signer_command: &str,
+) -> Result<(
+    SingleOwnerAccount<&'a JsonRpcClient<RetryTransport>, CastSigner>,
+    CastSigner,
+)> {
     let chain_id = get_chain_id(provider).await?;
-    let account = if keystore == &Utf8PathBuf::default() {
-        get_account_from_accounts_file(account, accounts_file, provider, chain_id)?
+    let (signer, address, account_type) = if !signer_command.is_empty() {
+        get_signer_from_command(signer_command, account)?
+    } else if keystore == &Utf8PathBuf::default() {
+        let (signer, address, account_type) =
+            get_signer_from_accounts_file(account, accounts_files, chain_id)?;
+        (CastSigner::Local(signer), address, account_type)
     } else {
-        get_account_from_keystore(provider, chain_id, keystore, account)?
+        get_signer_from_keystore(keystore, account)?
     };
-    Ok(account)
+    let account = SingleOwnerAccount::new(
+        provider,
+        signer.clone(),
+        address,
+        chain_id,
+        account_type.execution_encoding(),
+    );
+    Ok((account, signer))
 }
 
-fn get_account_from_keystore<'a>(
-    provider: &'a JsonRpcClient<HttpTransport>,
-    chain_id: FieldElement,
+/// The keystore is decrypted only when the key is needed for signing, so that the password is not prompted for otherwise
+fn get_signer_from_keystore(
     keystore_path: &Utf8PathBuf,
     account: &str,
-) -> Result<SingleOwnerAccount<&'a JsonRpcClient<HttpTransport>, LocalWallet>> {
+) -> Result<(CastSigner, FieldElement, AccountType)> {
     if !keystore_path.exists() {
         bail!("keystore file does not exist");
     }
@@ -189,11 +668,37 @@ fn get_account_from_keystore<'a>(
         bail!("account file does not exist; when using --keystore, --account argument should be a path to the starkli JSON account file");
     }
 
-    let signer = LocalWallet::from(SigningKey::from_keystore(
-        keystore_path,
-        get_keystore_password(KEYSTORE_PASSWORD_ENV_VAR)?.as_str(),
-    )?);
+    let address = get_address_from_account_file(&path_to_account)?;
+
+    Ok((
+        CastSigner::Keystore(KeystoreSigner::new(keystore_path)),
+        address,
+        get_account_type_from_account_file(&path_to_account)?,
+    ))
+}
+
+fn get_signer_from_command(
+    signer_command: &str,
+    account: &str,
+) -> Result<(CastSigner, FieldElement, AccountType)> {
+    if account.is_empty() {
+        bail!("Path passed with --account cannot be empty!");
+    }
+    let path_to_account = Utf8PathBuf::from(account);
+    if !path_to_account.exists() {
+        bail!("account file does not exist; when using --signer-command, --account argument should be a path to the starkli JSON account file");
+    }
+
+    let address = get_address_from_account_file(&path_to_account)?;
+
+    Ok((
+        CastSigner::Command(CommandSigner::new(signer_command)),
+        address,
+        get_account_type_from_account_file(&path_to_account)?,
+    ))
+}
 
+fn get_address_from_account_file(path_to_account: &Utf8PathBuf) -> Result<FieldElement> {
     let file_content = fs::read_to_string(path_to_account.clone())
         .with_context(|| format!("Cannot read a file {}", &path_to_account))?;
     let account_info: serde_json::Value = serde_json::from_str(&file_content)
@@ -206,23 +711,31 @@ fn get_account_from_keystore<'a>(
             .ok_or_else(|| anyhow::anyhow!("Failed to get address from account JSON file - make sure the account is deployed"))?
     )?;
 
-    Ok(SingleOwnerAccount::new(
-        provider,
-        signer,
-        address,
-        chain_id,
-        ExecutionEncoding::Legacy,
-    ))
+    Ok(address)
+}
+
+/// Account files without a variant type are treated as OpenZeppelin accounts
+fn get_account_type_from_account_file(path_to_account: &Utf8PathBuf) -> Result<AccountType> {
+    let file_content = fs::read_to_string(path_to_account)
+        .with_context(|| format!("Cannot read a file {path_to_account}"))?;
+    let account_info: serde_json::Value = serde_json::from_str(&file_content)
+        .with_context(|| format!("Cannot parse file {path_to_account} to JSON"))?;
+
+    account_info
+        .pointer("/variant/type")
+        .and_then(serde_json::Value::as_str)
+        .map_or(
+            Ok(AccountType::default()),
+            AccountType::from_starkli_variant,
+        )
 }
 
-fn get_account_from_accounts_file<'a>(
+fn get_signer_from_accounts_file(
     name: &str,
-    accounts_file_path: &Utf8PathBuf,
-    provider: &'a JsonRpcClient<HttpTransport>,
+    accounts_files: &[Utf8PathBuf],
     chain_id: FieldElement,
-) -> Result<SingleOwnerAccount<&'a JsonRpcClient<HttpTransport>, LocalWallet>> {
-    account_file_exists(accounts_file_path)?;
-    let account_info = get_account_info(name, chain_id, accounts_file_path)?;
+) -> Result<(LocalWallet, FieldElement, AccountType)> {
+    let account_info = get_account_info(name, chain_id, accounts_files)?;
     let signer = LocalWallet::from(SigningKey::from_secret_scalar(
         FieldElement::from_hex_be(&account_info.private_key).with_context(|| {
             format!(
@@ -231,28 +744,49 @@ fn get_account_from_accounts_file<'a>(
             )
         })?,
     ));
-    let address = FieldElement::from_hex_be(&account_info.address).with_context(|| {
+    let address = parse_account_address(&account_info)?;
+
+    Ok((
+        signer,
+        address,
+        account_info.account_type.unwrap_or_default(),
+    ))
+}
+
+fn parse_account_address(account_info: &Account) -> Result<FieldElement> {
+    FieldElement::from_hex_be(&account_info.address).with_context(|| {
         format!(
             "Failed to convert account address: {} to FieldElement",
             &account_info.address
         )
-    })?;
-    let account = SingleOwnerAccount::new(
-        provider,
-        signer,
-        address,
-        chain_id,
-        ExecutionEncoding::Legacy,
-    );
+    })
+}
 
-    Ok(account)
+/// Returns the address of the account without loading its private key
+pub async fn get_account_address(
+    account: &str,
+    accounts_files: &[Utf8PathBuf],
+    provider: &JsonRpcClient<RetryTransport>,
+    keystore: &Utf8PathBuf,
+    signer_command: &str,
+) -> Result<FieldElement> {
+    if keystore == &Utf8PathBuf::default() && signer_command.is_empty() {
+        let chain_id = get_chain_id(provider).await?;
+        parse_account_address(&get_account_info(account, chain_id, accounts_files)?)
+    } else {
+        raise_if_empty(account, "Account")?;
+        get_address_from_account_file(&Utf8PathBuf::from(account))
+    }
 }
 
+/// `pending` and `latest` are tags, `0x` prefixed values are block hashes and integers are block numbers
 pub fn get_block_id(value: &str) -> Result<BlockId> {
     match value {
         "pending" => Ok(BlockId::Tag(Pending)),
         "latest" => Ok(BlockId::Tag(Latest)),
-        _ if value.starts_with("0x") => Ok(BlockId::Hash(FieldElement::from_hex_be(value)?)),
+        _ if value.starts_with("0x") => FieldElement::from_hex_be(value)
+            .map(BlockId::Hash)
+            .map_err(|_| anyhow!("Invalid block hash {value}! Block hash should be a felt in hex (prefixed with 0x)")),
         _ => match value.parse::<u64>() {
             Ok(value) => Ok(BlockId::Number(value)),
             Err(_) => Err(anyhow::anyhow!(
@@ -263,36 +797,79 @@ pub fn get_block_id(value: &str) -> Result<BlockId> {
     }
 }
 
+/// Returned by `wait_for_tx` when the transaction was not accepted nor rejected before the timeout
+#[derive(Debug)]
+pub struct WaitTimeoutError {
+    pub transaction_hash: FieldElement,
+    pub last_status: &'static str,
+    pub timeout: u16,
+}
+
+impl std::fmt::Display for WaitTimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Could not get transaction with hash: {:#x}. Last known status: {}. \
+            Waiting timed out after {}s, check the transaction status later",
+            self.transaction_hash, self.last_status, self.timeout
+        )
+    }
+}
+
+impl std::error::Error for WaitTimeoutError {}
+
 pub async fn wait_for_tx(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<RetryTransport>,
     tx_hash: FieldElement,
-    retries: u8,
+    wait_params: ValidatedWaitParams,
 ) -> Result<&str> {
-    for i in (1..retries).rev() {
-        match provider.get_transaction_receipt(tx_hash).await {
-            Ok(receipt) => match receipt.execution_result() {
-                ExecutionResult::Succeeded => {
-                    return Ok("Transaction accepted");
-                }
-                ExecutionResult::Reverted { reason } => {
-                    return Err(anyhow!("Transaction has been reverted: {}", reason));
-                }
-            },
+    let mut last_status = "not received";
+    for i in (1..=wait_params.get_retries()).rev() {
+        match provider.get_transaction_status(tx_hash).await {
+            Ok(TransactionStatus::Received) => last_status = "received",
+            Ok(TransactionStatus::Rejected) => {
+                return Err(anyhow!("Transaction has been rejected"));
+            }
+            Ok(
+                TransactionStatus::AcceptedOnL2(execution_status)
+                | TransactionStatus::AcceptedOnL1(execution_status),
+            ) => {
+                return match execution_status {
+                    TransactionExecutionStatus::Succeeded => Ok("Transaction accepted"),
+                    TransactionExecutionStatus::Reverted => {
+                        let receipt = provider.get_transaction_receipt(tx_hash).await?;
+                        match receipt.execution_result() {
+                            ExecutionResult::Reverted { reason } => Err(anyhow!(
+                                "Transaction has been reverted: {}",
+                                decode_revert_reason(reason)
+                            )),
+                            ExecutionResult::Succeeded => {
+                                Err(anyhow!("Transaction has been reverted"))
+                            }
+                        }
+                    }
+                };
+            }
             Err(ProviderError::StarknetError(StarknetErrorWithMessage {
                 code: MaybeUnknownErrorCode::Known(StarknetError::TransactionHashNotFound),
                 message: _,
-            })) => {
-                println!("Waiting for transaction to be received ({i} retries left)");
-            }
+            })) => {}
             Err(err) => return Err(err.into()),
         };
 
-        sleep(Duration::from_secs(5));
+        let remaining_time = wait_params.remaining_time(i);
+        eprintln!(
+            "Waiting for transaction to be accepted, current status: {last_status} ({remaining_time}s left until timeout)"
+        );
+        sleep(Duration::from_secs(wait_params.get_retry_interval().into()));
     }
 
-    Err(anyhow!(
-        "Could not get transaction with hash: {tx_hash:#x}. Transaction rejected or not received."
-    ))
+    Err(WaitTimeoutError {
+        transaction_hash: tx_hash,
+        last_status,
+        timeout: wait_params.get_timeout(),
+    }
+    .into())
 }
 
 #[must_use]
@@ -319,6 +896,46 @@ pub fn get_rpc_error_message(error: &StarknetError) -> &'static str {
     }
 }
 
+#[must_use]
+pub fn get_rpc_error_code(error: &StarknetError) -> Option<i64> {
+    match error {
+        StarknetError::FailedToReceiveTransaction => Some(1),
+        StarknetError::ContractNotFound => Some(CONTRACT_NOT_FOUND_ERROR_CODE),
+        StarknetError::BlockNotFound => Some(24),
+        StarknetError::InvalidTransactionIndex => Some(27),
+        StarknetError::ClassHashNotFound => Some(28),
+        StarknetError::TransactionHashNotFound => Some(29),
+        StarknetError::ContractError => Some(40),
+        StarknetError::ClassAlreadyDeclared => Some(51),
+        StarknetError::InvalidTransactionNonce => Some(52),
+        StarknetError::InsufficientMaxFee => Some(53),
+        StarknetError::InsufficientAccountBalance => Some(54),
+        _ => None,
+    }
+}
+
+/// Error returned by the RPC node, along with its JSON-RPC error code
+#[derive(Debug)]
+pub struct RpcErrorWithCode {
+    pub code: i64,
+    pub message: String,
+}
+
+impl std::fmt::Display for RpcErrorWithCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for RpcErrorWithCode {}
+
+fn rpc_error(code: Option<i64>, message: String) -> Error {
+    match code {
+        Some(code) => RpcErrorWithCode { code, message }.into(),
+        None => anyhow!(message),
+    }
+}
+
 pub fn handle_rpc_error<T>(error: ProviderError) -> std::result::Result<T, Error> {
     match error {
         Other(x) => {
@@ -328,57 +945,374 @@ pub fn handle_rpc_error<T>(error: ProviderError) -> std::result::Result<T, Error
                 .downcast_ref::<starknet::providers::jsonrpc::RpcError>()
             {
                 match err {
-                    Code(error) => Err(anyhow!(get_rpc_error_message(error))),
-                    Unknown(error) => Err(anyhow!(error.message.clone())),
+                    Code(error) => Err(rpc_error(
+                        get_rpc_error_code(error),
+                        get_rpc_error_message(error).to_string(),
+                    )),
+                    Unknown(error) => Err(rpc_error(Some(error.code), error.message.clone())),
                 }
             } else {
                 Err(anyhow!("Unknown RPC error"))
             }
         }
-        ProviderError::StarknetError(error) => Err(anyhow!(error.message)),
+        ProviderError::StarknetError(error) => {
+            let code = match &error.code {
+                MaybeUnknownErrorCode::Known(code) => get_rpc_error_code(code),
+                MaybeUnknownErrorCode::Unknown(code) => Some(*code),
+            };
+            Err(rpc_error(code, error.message))
+        }
         _ => Err(anyhow!("Unknown RPC error")),
     }
 }
 
-pub async fn handle_wait_for_tx<T>(
-    provider: &JsonRpcClient<HttpTransport>,
-    transaction_hash: FieldElement,
-    return_value: T,
-    wait: bool,
-) -> Result<T> {
-    if wait {
-        return match wait_for_tx(provider, transaction_hash, DEFAULT_RETRIES).await {
-            Ok(_) => Ok(return_value),
-            Err(message) => Err(anyhow!(message)),
-        };
+pub fn validate_fee_multiplier(fee_multiplier: f64) -> Result<f64> {
+    if fee_multiplier.is_nan() || fee_multiplier <= 0.0 {
+        bail!("Fee multiplier must be greater than 0");
     }
-
-    Ok(return_value)
+    Ok(fee_multiplier)
 }
 
-pub fn print_formatted(output: Vec<(&str, String)>, json: bool, error: bool) -> Result<()> {
-    if json {
-        let json_output: HashMap<&str, String> = output.into_iter().collect();
-        let json_value: Value = serde_json::to_value(json_output)?;
-
-        write_to_output(serde_json::to_string_pretty(&json_value)?, error);
-    } else {
-        for (key, value) in &output {
-            write_to_output(format!("{key}: {value}"), error);
+/// Max fee used when it was not passed explicitly: estimated overall fee multiplied by `fee_multiplier`
+pub fn max_fee_from_estimate<S: std::fmt::Display>(
+    fee_estimate: std::result::Result<FeeEstimate, AccountError<S>>,
+    fee_multiplier: f64,
+) -> Result<FieldElement> {
+    let fee_estimate = match fee_estimate {
+        Ok(fee_estimate) => fee_estimate,
+        Err(AccountError::Provider(error)) => {
+            return handle_rpc_error(error).context(FEE_ESTIMATION_FAILED_MESSAGE)
         }
-    }
+        Err(AccountError::Signing(error)) => {
+            bail!("{FEE_ESTIMATION_FAILED_MESSAGE}: Failed to sign transaction: {error}")
+        }
+        Err(_) => bail!(FEE_ESTIMATION_FAILED_MESSAGE),
+    };
 
-    Ok(())
+    let max_fee = apply_fee_multiplier(fee_estimate.overall_fee, fee_multiplier);
+    eprintln!(
+        "Estimated fee: {}, using max fee: {max_fee} (fee multiplier: {fee_multiplier})",
+        fee_estimate.overall_fee
+    );
+    Ok(FieldElement::from(max_fee))
 }
 
-pub fn print_command_result<T: Serialize>(
+static CHECK_BALANCE: AtomicBool = AtomicBool::new(false);
+
+/// Makes transactions be sent only if the ETH balance of the account covers their max fee
+pub fn set_check_balance(check_balance: bool) {
+    CHECK_BALANCE.store(check_balance, Ordering::Relaxed);
+}
+
+/// Called right before sending a transaction, once its max fee is known; does nothing unless
+/// balance checking was enabled with `set_check_balance`
+pub async fn ensure_balance_covers_fee(
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    max_fee: FieldElement,
+) -> Result<()> {
+    if !CHECK_BALANCE.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    let token = FieldElement::from_hex_be(ETH_ADDRESS)?;
+    let balance = starknet_commands::balance::balance(
+        account.address(),
+        token,
+        account.provider(),
+        &BlockId::Tag(Pending),
+    )
+    .await
+    .context("Failed to check the balance of the account")?;
+
+    if balance.balance_high == FieldElement::ZERO && balance.balance_low < max_fee {
+        bail!(
+            "Account {:#x} has insufficient ETH balance to cover the max fee: balance is {} wei, max fee is {max_fee} wei. \
+            Fund the account or lower the max fee",
+            account.address(),
+            balance.balance_low,
+        );
+    }
+    Ok(())
+}
+
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn apply_fee_multiplier(fee: u64, fee_multiplier: f64) -> u64 {
+    // multiplier is applied in thousandths to avoid precision loss on large fees
+    let multiplier_thousandths = (fee_multiplier * 1000.0).round() as u128;
+    let max_fee = (u128::from(fee) * multiplier_thousandths + 999) / 1000;
+    u64::try_from(max_fee).unwrap_or(u64::MAX)
+}
+
+pub async fn sign_transaction_hash(
+    signer: &CastSigner,
+    transaction_hash: FieldElement,
+) -> Result<Vec<FieldElement>> {
+    let signature = signer
+        .sign_hash(&transaction_hash)
+        .await
+        .map_err(|error| anyhow!("Failed to sign transaction: {error}"))?;
+    Ok(vec![signature.r, signature.s])
+}
+
+/// Appends the decoded text to every felt in the revert reason which is a valid Cairo short string,
+/// e.g. `0x4e6f2062616c616e6365` becomes `0x4e6f2062616c616e6365 ('No balance')`
+#[must_use]
+pub fn decode_revert_reason(reason: &str) -> String {
+    let mut decoded = String::with_capacity(reason.len());
+    let mut rest = reason;
+    while let Some(start) = rest.find("0x") {
+        let (before, from_prefix) = rest.split_at(start);
+        decoded.push_str(before);
+
+        let hex_digits = from_prefix[2..]
+            .chars()
+            .take_while(char::is_ascii_hexdigit)
+            .count();
+        let (felt, after) = from_prefix.split_at(2 + hex_digits);
+        decoded.push_str(felt);
+        // newer nodes decode short strings themselves
+        if !after.starts_with(" ('") {
+            if let Some(text) = decode_short_string(felt) {
+                decoded.push_str(&format!(" ('{text}')"));
+            }
+        }
+        rest = after;
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+/// Decodes felts serialized as hex strings, other strings (e.g. decimal numbers or statuses) are never decoded
+fn felt_short_string(value: &str) -> Option<String> {
+    value
+        .starts_with("0x")
+        .then(|| decode_short_string(value))
+        .flatten()
+}
+
+fn decode_short_string(felt: &str) -> Option<String> {
+    let bytes: Vec<u8> = FieldElement::from_hex_be(felt)
+        .ok()?
+        .to_bytes_be()
+        .into_iter()
+        .skip_while(|byte| *byte == 0)
+        .collect();
+    if bytes.is_empty()
+        || bytes.len() > 31
+        || !bytes.iter().all(|byte| (0x20..=0x7e).contains(byte))
+    {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+pub async fn handle_wait_for_tx<T>(
+    provider: &JsonRpcClient<RetryTransport>,
+    transaction_hash: FieldElement,
+    return_value: T,
+    wait_config: WaitForTx,
+) -> Result<T> {
+    if wait_config.wait {
+        return wait_for_tx(provider, transaction_hash, wait_config.wait_params)
+            .await
+            .map(|_| return_value);
+    }
+
+    Ok(return_value)
+}
+
+pub fn print_formatted(output: Vec<(&str, Value)>, json: bool, error: bool) -> Result<()> {
+    let output: Vec<(&str, OutputValue)> = output
+        .into_iter()
+        .map(|(key, value)| (key, OutputValue::Value(value)))
+        .collect();
+    print_output(&output, json, error)
+}
+
+/// Value of a single output field; `Formatted` values are written straight to the output
+/// while it is written, without formatting them into intermediate strings
+enum OutputValue<'a> {
+    Value(Value),
+    Formatted(FormattedValue<'a>),
+}
+
+impl std::fmt::Display for OutputValue<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputValue::Value(Value::String(value)) => f.write_str(value),
+            OutputValue::Value(value) => write!(f, "{value}"),
+            OutputValue::Formatted(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl Serialize for OutputValue<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            OutputValue::Value(value) => value.serialize(serializer),
+            OutputValue::Formatted(value) => serializer.collect_str(value),
+        }
+    }
+}
+
+/// Prints the output to stdout (or to stderr if `error` is set) unless `--quiet` is passed,
+/// and adds it to the output file if one is set
+fn print_output(output: &[(&str, OutputValue)], json: bool, error: bool) -> Result<()> {
+    let mut output_file = OUTPUT_FILE
+        .get()
+        .map(|file| file.lock().expect("Failed to lock output file"));
+    let quiet = output_file.as_ref().is_some_and(|file| file.quiet);
+
+    if let Some(file) = output_file.as_mut() {
+        let mut contents = std::mem::take(&mut file.contents).into_bytes();
+        let written = write_formatted(output, json, false, &mut contents);
+        file.contents = String::from_utf8(contents)?;
+        written?;
+    }
+
+    if error {
+        write_formatted(output, json, use_color(true), std::io::stderr().lock())
+    } else if quiet {
+        Ok(())
+    } else {
+        write_formatted(output, json, use_color(false), std::io::stdout().lock())
+    }
+}
+
+/// Writes the output as a pretty-printed JSON object (with sorted keys) followed by a newline,
+/// or as `key: value` lines, in chunks while it is formatted instead of building the whole string first
+fn write_formatted(
+    output: &[(&str, OutputValue)],
+    json: bool,
+    color: bool,
+    writer: impl Write,
+) -> Result<()> {
+    let mut writer = BufWriter::new(writer);
+    if json {
+        let json_output: BTreeMap<&str, &OutputValue> =
+            output.iter().map(|(key, value)| (*key, value)).collect();
+        serde_json::to_writer_pretty(&mut writer, &json_output)?;
+        writer.write_all(b"\n")?;
+    } else {
+        for (key, value) in output {
+            match value_color(key).filter(|_| color) {
+                Some(color) => writeln!(writer, "{key}: \x1b[{color}m{value}\x1b[0m")?,
+                None => writeln!(writer, "{key}: {value}")?,
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+static COLOR_CHOICE: OnceLock<ColorChoice> = OnceLock::new();
+
+/// Makes `print_command_result` highlight transaction hashes, addresses and errors in the human-readable output.
+/// With `ColorChoice::Auto`, colors are used only when printing to a terminal and `NO_COLOR` is not set
+pub fn set_color_choice(color_choice: ColorChoice) {
+    COLOR_CHOICE.set(color_choice).ok();
+}
+
+fn use_color(error: bool) -> bool {
+    match COLOR_CHOICE.get().copied().unwrap_or(ColorChoice::Never) {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            get_env_var(NO_COLOR_ENV_VAR).is_none()
+                && if error {
+                    std::io::stderr().is_terminal()
+                } else {
+                    std::io::stdout().is_terminal()
+                }
+        }
+    }
+}
+
+fn value_color(key: &str) -> Option<&'static str> {
+    // ANSI codes of red, yellow and cyan foreground
+    match key {
+        "error" => Some("31"),
+        _ if key.ends_with("transaction_hash") => Some("33"),
+        _ if key.ends_with("address") => Some("36"),
+        _ => None,
+    }
+}
+
+/// Returned by `print_command_result` in json mode, after the error has already been printed to stdout
+#[derive(Debug)]
+pub struct ErrorPrinted;
+
+impl std::fmt::Display for ErrorPrinted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Command failed")
+    }
+}
+
+impl std::error::Error for ErrorPrinted {}
+
+const DECODED_FIELD: &str = "decoded";
+const ALREADY_DECLARED_FIELD: &str = "already_declared";
+const RESULTS_FIELD: &str = "results";
+const TRACE_FIELD: &str = "trace";
+const STATE_DIFF_FIELD: &str = "state_diff";
+
+static DECODE_STRINGS: AtomicBool = AtomicBool::new(false);
+
+/// Makes `print_command_result` show the decoded text of felts which are valid Cairo short strings
+pub fn set_decode_strings(decode_strings: bool) {
+    DECODE_STRINGS.store(decode_strings, Ordering::Relaxed);
+}
+
+fn decode_strings() -> bool {
+    DECODE_STRINGS.load(Ordering::Relaxed)
+}
+
+/// In json mode, values containing short strings are printed as objects with `raw` and `decoded` fields,
+/// otherwise the decoded text is printed next to the felt
+fn format_with_short_strings(
+    value: &Value,
+    value_format: ValueFormat,
+    json: bool,
+) -> Option<Value> {
+    if !json {
+        return value_format
+            .format_json_value_with_short_strings(value)
+            .map(Value::String);
+    }
+
+    let raw = value_format.format_json_value(value)?;
+    Some(match value_format.decode_short_strings(value) {
+        Some(decoded) => json!({ "raw": raw, "decoded": decoded }),
+        None => Value::String(raw),
+    })
+}
+
+pub fn print_command_result<T: Serialize>(
+    command: &str,
+    result: &Result<T>,
+    value_format: ValueFormat,
+    json: bool,
+) -> Result<()> {
+    write_command_result(command, result, value_format, json, |output, error| {
+        print_output(output, json, error)
+    })
+}
+
+/// Collects the output fields of the command result and passes them to `print` together with
+/// whether they should be printed as an error
+fn write_command_result<T: Serialize>(
     command: &str,
-    result: &mut Result<T>,
+    result: &Result<T>,
     value_format: ValueFormat,
     json: bool,
+    print: impl FnOnce(&[(&str, OutputValue)], bool) -> Result<()>,
 ) -> Result<()> {
-    let mut output = vec![("command", command.to_string())];
     let json_value: Value;
+    let mut output = vec![(
+        "command",
+        OutputValue::Value(Value::String(command.to_string())),
+    )];
 
     let mut error = false;
     match result {
@@ -391,34 +1325,161 @@ pub fn print_command_result<T: Serialize>(
                     .as_object()
                     .expect("Invalid JSON value")
                     .iter()
-                    .filter_map(|(k, v)| value_format.format_json_value(v).map(|v| (k.as_str(), v)))
-                    .collect::<Vec<(&str, String)>>(),
+                    .filter_map(|(k, v)| match k.as_str() {
+                        // decoded values are already formatted and keep their JSON types
+                        DECODED_FIELD => Some((k.as_str(), OutputValue::Value(v.clone()))),
+                        // printed as a JSON boolean, so that scripts can check it directly
+                        ALREADY_DECLARED_FIELD => Some((k.as_str(), OutputValue::Value(v.clone()))),
+                        // per-item results and simulation traces keep their structure, so that they can be told apart
+                        RESULTS_FIELD | TRACE_FIELD | STATE_DIFF_FIELD => Some((
+                            k.as_str(),
+                            OutputValue::Value(value_format.format_json_structure(v)),
+                        )),
+                        _ if decode_strings() => format_with_short_strings(v, value_format, json)
+                            .map(|v| (k.as_str(), OutputValue::Value(v))),
+                        _ => (!v.is_null()).then(|| {
+                            (
+                                k.as_str(),
+                                OutputValue::Formatted(FormattedValue {
+                                    value: v,
+                                    value_format,
+                                    short_strings: false,
+                                }),
+                            )
+                        }),
+                    }),
             );
         }
         Err(message) => {
-            output.push(("error", format!("{message:#}")));
+            output.push((
+                "error",
+                OutputValue::Value(Value::String(format!("{message:#}"))),
+            ));
+            if json {
+                if let Some(rpc_error) = message
+                    .chain()
+                    .find_map(|cause| cause.downcast_ref::<RpcErrorWithCode>())
+                {
+                    output.push((
+                        "code",
+                        OutputValue::Value(Value::String(rpc_error.code.to_string())),
+                    ));
+                }
+            }
             error = true;
         }
     };
-    print_formatted(output, json, error)
+
+    // in json mode, errors are printed to stdout as well, so that the output can always be parsed
+    print(&output, error && !json)?;
+    if error && json {
+        return Err(ErrorPrinted.into());
+    }
+    Ok(())
+}
+
+struct OutputFile {
+    path: Utf8PathBuf,
+    contents: String,
+    quiet: bool,
+}
+
+impl OutputFile {
+    /// Contents are written to a temporary file which then replaces the output file,
+    /// so that the output file is never left partially written
+    fn write(&self) -> Result<()> {
+        let directory = self
+            .path
+            .parent()
+            .filter(|directory| !directory.as_str().is_empty())
+            .unwrap_or_else(|| Utf8Path::new("."));
+        let mut temp_file = tempfile::NamedTempFile::new_in(directory)?;
+        temp_file.write_all(self.contents.as_bytes())?;
+        temp_file.persist(&self.path)?;
+        Ok(())
+    }
+}
+
+static OUTPUT_FILE: OnceLock<Mutex<OutputFile>> = OnceLock::new();
+
+/// Makes `print_command_result` write its output to the file at `path` as well,
+/// overwriting the file unless `append` is set. If `quiet` is set, the output is not printed to stdout.
+/// The output is collected in memory and written to the file by `write_output_file`
+pub fn set_output_file(path: &Utf8PathBuf, append: bool, quiet: bool) -> Result<()> {
+    let contents = if append && path.exists() {
+        fs::read_to_string(path).with_context(|| format!("Failed to read output file {path}"))?
+    } else {
+        String::new()
+    };
+    let output_file = OutputFile {
+        path: path.clone(),
+        contents,
+        quiet,
+    };
+    output_file
+        .write()
+        .with_context(|| format!("Failed to write output file {path}"))?;
+
+    OUTPUT_FILE
+        .set(Mutex::new(output_file))
+        .map_err(|_| anyhow!("Output file has already been set"))
 }
 
-fn write_to_output<T: std::fmt::Display>(value: T, error: bool) {
+/// Prints the line to stdout (or to stderr if `error` is set) unless `--quiet` is passed,
+/// and adds it to the output file if one is set
+pub fn write_to_output<T: std::fmt::Display>(value: T, error: bool) {
+    let mut output_file = OUTPUT_FILE
+        .get()
+        .map(|file| file.lock().expect("Failed to lock output file"));
+    let quiet = output_file.as_ref().is_some_and(|file| file.quiet);
+
     if error {
         eprintln!("{value}");
-    } else {
+    } else if !quiet {
         println!("{value}");
     }
+
+    if let Some(file) = output_file.as_mut() {
+        file.contents.push_str(&value.to_string());
+        file.contents.push('\n');
+    }
+}
+
+/// Writes the output collected since `set_output_file` to the output file at once;
+/// called when the command finishes, also when it fails
+pub fn write_output_file() -> Result<()> {
+    match OUTPUT_FILE.get() {
+        Some(file) => {
+            let file = file.lock().expect("Failed to lock output file");
+            file.write()
+                .with_context(|| format!("Failed to write output file {}", file.path))
+        }
+        None => Ok(()),
+    }
 }
 
 pub fn parse_number(number_as_str: &str) -> Result<FieldElement> {
-    let contract_address = match &number_as_str[..2] {
-        "0x" => FieldElement::from_hex_be(number_as_str)?,
-        _ => FieldElement::from_dec_str(number_as_str)?,
+    let contract_address = if number_as_str.starts_with("0x") {
+        FieldElement::from_hex_be(number_as_str)?
+    } else {
+        FieldElement::from_dec_str(number_as_str)?
     };
     Ok(contract_address)
 }
 
+/// Returns the selector passed with `--selector` as is, or computes it from the function name
+pub fn get_entry_point_selector(
+    function: Option<&str>,
+    selector: Option<FieldElement>,
+) -> Result<FieldElement> {
+    match (function, selector) {
+        (None, Some(selector)) => Ok(selector),
+        (Some(function), None) => get_selector_from_name(function)
+            .context("Failed to convert entry point selector to FieldElement"),
+        _ => bail!("Exactly one of --function and --selector must be passed"),
+    }
+}
+
 pub fn raise_if_empty(value: &str, value_name: &str) -> Result<()> {
     if value.is_empty() {
         bail!("{value_name} not passed nor found in Scarb.toml")
@@ -436,7 +1497,34 @@ pub fn account_file_exists(accounts_file_path: &Utf8PathBuf) -> Result<()> {
 
 #[must_use]
 pub fn extract_or_generate_salt(salt: Option<FieldElement>) -> FieldElement {
-    salt.unwrap_or(FieldElement::from(OsRng.next_u64()))
+    salt.unwrap_or_else(generate_random_salt)
+}
+
+/// Generates a cryptographically random salt covering the whole felt range
+#[must_use]
+pub fn generate_random_salt() -> FieldElement {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    // clear the top bits, so that the value is always smaller than the field modulus
+    bytes[0] &= 0x03;
+    FieldElement::from_bytes_be(&bytes).expect("Random salt out of felt range")
+}
+
+/// Derives a deterministic salt from a human readable label, so that the same contract address
+/// can be obtained on different networks
+#[must_use]
+pub fn salt_from_label(label: &str) -> FieldElement {
+    starknet_keccak(label.as_bytes())
+}
+
+/// Parses `--salt` value, either a felt or `random`
+pub fn parse_salt(salt: &str) -> Result<FieldElement> {
+    if salt == "random" {
+        Ok(generate_random_salt())
+    } else {
+        FieldElement::from_str(salt)
+            .map_err(|_| anyhow!("Invalid salt, expected a felt or `random`"))
+    }
 }
 
 #[must_use]
@@ -454,24 +1542,140 @@ pub fn udc_uniqueness(unique: bool, account_address: FieldElement) -> UdcUniquen
 #[cfg(test)]
 mod tests {
     use crate::{
-        chain_id_to_network_name, extract_or_generate_salt, get_account_from_accounts_file,
-        get_block_id, udc_uniqueness, ValueFormat,
+        apply_fee_multiplier, chain_id_to_network_name, decode_revert_reason,
+        extract_or_generate_salt, find_account, get_block_id, get_entry_point_selector,
+        get_or_fetch_chain_id, get_signer_from_accounts_file, network_name, parse_chain_id,
+        parse_salt, read_password_line, salt_from_label, udc_uniqueness, validate_fee_multiplier,
+        value_color, write_command_result, write_formatted, OutputValue, ValidatedWaitParams,
+        ValueFormat,
     };
     use camino::Utf8PathBuf;
     use serde::Serialize;
-    use serde_json::json;
+    use serde_json::{json, Value};
+    use starknet::core::types::{
+        BlockId,
+        BlockTag::{Latest, Pending},
+        FieldElement,
+    };
     use starknet::core::utils::UdcUniqueSettings;
     use starknet::core::utils::UdcUniqueness::{NotUnique, Unique};
-    use starknet::{
-        core::types::{
-            BlockId,
-            BlockTag::{Latest, Pending},
-            FieldElement,
-        },
-        providers::{jsonrpc::HttpTransport, JsonRpcClient},
-    };
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::OnceLock;
     use test_case::test_case;
-    use url::Url;
+
+    thread_local! {
+        static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+        static PEAK_ALLOCATED: Cell<isize> = const { Cell::new(0) };
+    }
+
+    /// Counts bytes currently allocated by each thread and the most allocated at once,
+    /// so that tests can check how much memory some code needs
+    struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATED
+                .try_with(|allocated| {
+                    let size = isize::try_from(layout.size()).unwrap_or(isize::MAX);
+                    allocated.set(allocated.get() + size);
+                    PEAK_ALLOCATED
+                        .try_with(|peak| peak.set(peak.get().max(allocated.get())))
+                        .ok();
+                })
+                .ok();
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            ALLOCATED
+                .try_with(|allocated| {
+                    let size = isize::try_from(layout.size()).unwrap_or(isize::MAX);
+                    allocated.set(allocated.get() - size);
+                })
+                .ok();
+            System.dealloc(ptr, layout);
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Returns the result of `f` and how many more bytes than before it were allocated at once while it ran
+    fn peak_allocated<R>(f: impl FnOnce() -> R) -> (R, isize) {
+        let allocated_before = ALLOCATED.with(Cell::get);
+        PEAK_ALLOCATED.with(|peak| peak.set(allocated_before));
+        let result = f();
+        (result, PEAK_ALLOCATED.with(Cell::get) - allocated_before)
+    }
+
+    #[tokio::test]
+    async fn test_chain_id_fetched_once() {
+        let cache = OnceLock::new();
+        let requests = AtomicUsize::new(0);
+        let fetch = || async {
+            requests.fetch_add(1, Ordering::Relaxed);
+            Ok::<_, std::io::Error>(FieldElement::from_byte_slice_be(b"SN_GOERLI").unwrap())
+        };
+
+        let chain_id = get_or_fetch_chain_id(&cache, fetch).await.unwrap();
+        let network_name =
+            chain_id_to_network_name(get_or_fetch_chain_id(&cache, fetch).await.unwrap());
+
+        assert_eq!(
+            chain_id,
+            FieldElement::from_byte_slice_be(b"SN_GOERLI").unwrap()
+        );
+        assert_eq!(network_name, "alpha-goerli");
+        assert_eq!(requests.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_chain_id_fetch_is_not_cached() {
+        let cache = OnceLock::new();
+        let error = get_or_fetch_chain_id(&cache, || async {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "unreachable",
+            ))
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.to_string(), "Couldn't fetch chain_id");
+        assert!(cache.get().is_none());
+    }
+
+    #[test]
+    fn test_parse_chain_id() {
+        let goerli = FieldElement::from_hex_be("0x534e5f474f45524c49").unwrap();
+
+        assert_eq!(parse_chain_id("SN_GOERLI").unwrap(), goerli);
+        assert_eq!(parse_chain_id("alpha-goerli").unwrap(), goerli);
+        assert_eq!(parse_chain_id("0x534e5f474f45524c49").unwrap(), goerli);
+        assert!(parse_chain_id("0xzz").is_err());
+    }
+
+    #[test]
+    fn test_get_entry_point_selector() {
+        let get_selector = FieldElement::from_hex_be(
+            "0x17c00f03de8b5bd58d2016b59d251c13056b989171c5852949903bc043bc27",
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_entry_point_selector(Some("get"), None).unwrap(),
+            get_selector
+        );
+        // the selector is used as is, without hashing it again
+        assert_eq!(
+            get_entry_point_selector(None, Some(get_selector)).unwrap(),
+            get_selector
+        );
+        assert!(get_entry_point_selector(None, None).is_err());
+        assert!(get_entry_point_selector(Some("get"), Some(get_selector)).is_err());
+    }
 
     #[test]
     fn test_get_block_id() {
@@ -497,6 +1701,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_block_id_full_hash() {
+        let hash = "0x04d893935543cb5a2b5b8df2b5e1ca37d2e0b3a6af8b7c4d2a1b2a9c5b8f7e61";
+        let block = get_block_id(hash).unwrap();
+
+        assert_eq!(
+            block,
+            BlockId::Hash(FieldElement::from_hex_be(hash).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_get_block_id_short_hash() {
+        let block = get_block_id("0x123").unwrap();
+
+        assert_eq!(block, BlockId::Hash(FieldElement::from(0x123_u32)));
+    }
+
+    #[test]
+    fn test_get_block_id_invalid_hash() {
+        let error = get_block_id("0x12g").unwrap_err();
+
+        assert!(error.to_string().contains("Invalid block hash 0x12g!"));
+    }
+
     #[test]
     fn test_get_block_id_num() {
         let block = get_block_id("0").unwrap();
@@ -504,6 +1733,20 @@ mod tests {
         assert_eq!(block, BlockId::Number(0));
     }
 
+    #[test]
+    fn test_get_block_id_large_num() {
+        let block = get_block_id("123456").unwrap();
+
+        assert_eq!(block, BlockId::Number(123_456));
+    }
+
+    #[test]
+    fn test_get_block_id_negative_num() {
+        let error = get_block_id("-1").unwrap_err();
+
+        assert!(error.to_string().contains("No such block id -1!"));
+    }
+
     #[test]
     fn test_get_block_id_invalid() {
         let block = get_block_id("mariusz").unwrap_err();
@@ -512,6 +1755,21 @@ mod tests {
             .contains("No such block id mariusz! Possible values are pending, latest, block hash (hex) and block number (u64)."));
     }
 
+    #[test]
+    fn test_validated_wait_params() {
+        let wait_params = ValidatedWaitParams::new(5, 60).unwrap();
+
+        assert_eq!(wait_params.get_retries(), 12);
+        assert_eq!(wait_params.remaining_time(3), 15);
+    }
+
+    #[test_case(0, 60 ; "when retry interval is zero")]
+    #[test_case(5, 0 ; "when timeout is zero")]
+    #[test_case(10, 5 ; "when retry interval is greater than timeout")]
+    fn test_validated_wait_params_invalid(retry_interval: u8, timeout: u16) {
+        assert!(ValidatedWaitParams::new(retry_interval, timeout).is_err());
+    }
+
     #[test]
     fn test_generate_salt() {
         let salt = extract_or_generate_salt(None);
@@ -519,6 +1777,26 @@ mod tests {
         assert!(salt >= FieldElement::ZERO);
     }
 
+    #[test]
+    fn test_parse_salt() {
+        assert_eq!(parse_salt("0x3").unwrap(), FieldElement::THREE);
+        assert_eq!(parse_salt("3").unwrap(), FieldElement::THREE);
+        assert_ne!(parse_salt("random").unwrap(), parse_salt("random").unwrap());
+        assert!(parse_salt("abc").is_err());
+    }
+
+    #[test]
+    fn test_salt_from_label() {
+        assert_eq!(
+            salt_from_label("treasury-v2"),
+            salt_from_label("treasury-v2")
+        );
+        assert_ne!(
+            salt_from_label("treasury-v2"),
+            salt_from_label("treasury-v3")
+        );
+    }
+
     #[test]
     fn test_extract_salt() {
         let salt = extract_or_generate_salt(Some(FieldElement::THREE));
@@ -552,14 +1830,52 @@ mod tests {
         assert_eq!(network_name_katana, "KATANA");
     }
 
+    #[test]
+    fn test_read_password_line() {
+        let password = read_password_line("secret\r\nsecond line\n".as_bytes()).unwrap();
+        assert_eq!(password.as_str(), "secret");
+
+        let password = read_password_line("secret".as_bytes()).unwrap();
+        assert_eq!(password.as_str(), "secret");
+
+        let error = read_password_line("\n".as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "Keystore password is empty");
+    }
+
+    #[test]
+    fn test_custom_network_name_takes_precedence() {
+        let goerli = FieldElement::from_byte_slice_be("SN_GOERLI".as_bytes()).unwrap();
+        let appchain = FieldElement::from_byte_slice_be("MY_APPCHAIN".as_bytes()).unwrap();
+        let custom_networks = vec![
+            (String::from("my-appchain"), appchain),
+            (String::from("devnet"), goerli),
+        ];
+
+        assert_eq!(network_name(appchain, &custom_networks), "my-appchain");
+        assert_eq!(network_name(goerli, &custom_networks), "devnet");
+        assert_eq!(network_name(goerli, &[]), "alpha-goerli");
+    }
+
+    #[test_case(1000, 1.5, 1500)]
+    #[test_case(1000, 1.0, 1000)]
+    #[test_case(999, 1.1, 1099)]
+    #[test_case(u64::MAX, 2.0, u64::MAX)]
+    fn test_apply_fee_multiplier(fee: u64, fee_multiplier: f64, expected: u64) {
+        assert_eq!(apply_fee_multiplier(fee, fee_multiplier), expected);
+    }
+
+    #[test_case(0.0 ; "when multiplier is zero")]
+    #[test_case(-1.5 ; "when multiplier is negative")]
+    #[test_case(f64::NAN ; "when multiplier is nan")]
+    fn test_validate_fee_multiplier_invalid(fee_multiplier: f64) {
+        assert!(validate_fee_multiplier(fee_multiplier).is_err());
+    }
+
     #[test]
     fn test_get_account_wrong_chain_id() {
-        let mock_url = Url::parse("https://example.net").unwrap();
-        let mock_provider = JsonRpcClient::new(HttpTransport::new(mock_url));
-        let account = get_account_from_accounts_file(
+        let account = get_signer_from_accounts_file(
             "user1",
-            &Utf8PathBuf::from("tests/data/accounts/accounts.json"),
-            &mock_provider,
+            &[Utf8PathBuf::from("tests/data/accounts/accounts.json")],
             FieldElement::from_hex_be("0x435553544f4d5f434841494e5f4944")
                 .expect("Should convert from hex"),
         );
@@ -569,6 +1885,40 @@ mod tests {
             .contains("Account user1 not found under network CUSTOM_CHAIN_ID"));
     }
 
+    #[test]
+    fn test_get_account_from_multiple_accounts_files() {
+        let accounts_files = [
+            Utf8PathBuf::from("tests/data/accounts/nonexistent.json"),
+            Utf8PathBuf::from("tests/data/accounts/accounts.json"),
+            Utf8PathBuf::from("tests/data/accounts/shared_accounts.json"),
+        ];
+
+        let (account, accounts_file) = find_account("user1", "alpha-goerli", &accounts_files)
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.private_key, "0xffd33878eed7767e7c546ce3fc026295");
+        assert_eq!(accounts_file, accounts_files[1]);
+
+        let (account, accounts_file) = find_account("shared_user", "alpha-goerli", &accounts_files)
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.address, "0x1234");
+        assert_eq!(accounts_file, accounts_files[2]);
+    }
+
+    #[test]
+    fn test_get_account_conflicting_addresses() {
+        let accounts_files = [
+            Utf8PathBuf::from("tests/data/accounts/accounts.json"),
+            Utf8PathBuf::from("tests/data/accounts/conflicting_accounts.json"),
+        ];
+
+        let err = find_account("user1", "alpha-goerli", &accounts_files).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Account user1 is defined with different addresses"));
+    }
+
     #[test_case(
         "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7",
         "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7";
@@ -582,6 +1932,12 @@ mod tests {
         "[0x49d3657224e46f48e99674bd3fcc84644ddd6b96f7c221b1562b82f9e004dc7, 0x49d36333d4e46f48e99674bd3fcc84333ddd6b96f7c741b1562b82f9e004dc7]";
         "when value is array of contract addresses"
     )]
+    #[test_case(
+        json!({ "keys": ["0x1", 2] }),
+        "{keys: [0x1, 2]}";
+        "when value is an object"
+    )]
+    #[test_case(true, "true" ; "when value is bool")]
     fn test_format_json_value_not_none<T: Serialize>(value: T, expected: &str) {
         let value_format = ValueFormat::Default;
         let json_value = serde_json::to_value(value).unwrap();
@@ -591,8 +1947,6 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
-    #[test_case(true ; "when value is bool")]
-    #[test_case(json!({ "an": "object" }) ; "when value is an object")]
     #[test_case(json!(null) ; "when value is null")]
     fn test_format_json_value_is_none<T: Serialize>(value: T) {
         let value_format = ValueFormat::Default;
@@ -602,4 +1956,137 @@ mod tests {
 
         assert_eq!(actual, None);
     }
+
+    #[test]
+    fn test_write_command_result_large_array() {
+        let items: Vec<String> = (0..100_000_u64).map(|item| format!("{item:#x}")).collect();
+        let result: anyhow::Result<Value> = Ok(json!({ "response": items }));
+
+        let mut written = vec![];
+        write_command_result("call", &result, ValueFormat::Default, true, |output, _| {
+            write_formatted(output, true, false, &mut written)
+        })
+        .unwrap();
+        let written: Value = serde_json::from_slice(&written).unwrap();
+        assert_eq!(written["command"], "call");
+        let written_response = written["response"].as_str().unwrap();
+        assert_eq!(written_response.split(", ").count(), 100_000);
+        assert!(written_response.ends_with(", 0x1869f]"));
+
+        let mut written = vec![];
+        write_command_result("call", &result, ValueFormat::Default, false, |output, _| {
+            write_formatted(output, false, false, &mut written)
+        })
+        .unwrap();
+        let written = String::from_utf8(written).unwrap();
+        assert!(written.starts_with("command: call\nresponse: [0x0, 0x1, "));
+        assert!(written.ends_with(", 0x1869f]\n"));
+
+        // besides converting the result to a JSON value, the output is formatted and written in chunks,
+        // so printing it needs much less memory than its size
+        let (_, result_value_size) =
+            peak_allocated(|| serde_json::to_value(result.as_ref().unwrap()).unwrap());
+        for json in [true, false] {
+            let (printed, printing_size) = peak_allocated(|| {
+                write_command_result("call", &result, ValueFormat::Default, json, |output, _| {
+                    write_formatted(output, json, false, std::io::sink())
+                })
+            });
+            printed.unwrap();
+            assert!(
+                printing_size < result_value_size + 64 * 1024,
+                "Printing the result needed {printing_size} bytes, while the result value takes {result_value_size} bytes"
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_command_result_already_declared() {
+        let result: anyhow::Result<Value> =
+            Ok(json!({ "already_declared": true, "class_hash": "0x1" }));
+
+        let mut written = vec![];
+        write_command_result(
+            "declare",
+            &result,
+            ValueFormat::Default,
+            true,
+            |output, _| write_formatted(output, true, false, &mut written),
+        )
+        .unwrap();
+        let written: Value = serde_json::from_slice(&written).unwrap();
+
+        assert_eq!(written["already_declared"], true);
+        assert_eq!(written["class_hash"], "0x1");
+    }
+
+    #[test]
+    fn test_format_json_value_with_short_strings() {
+        let json_value = json!(["0x455448", "0x12", "4554", "ETH"]);
+
+        let actual = ValueFormat::Default
+            .format_json_value_with_short_strings(&json_value)
+            .unwrap();
+
+        assert_eq!(actual, "[0x455448 ('ETH'), 0x12, 0x11ca, ETH]");
+    }
+
+    #[test]
+    fn test_decode_short_strings() {
+        let value_format = ValueFormat::Int;
+
+        assert_eq!(
+            value_format.decode_short_strings(&json!({ "response": ["0x455448", "0x12"] })),
+            Some(json!({ "response": ["ETH", "18"] }))
+        );
+        assert_eq!(
+            value_format.decode_short_strings(&json!(["0x0", "0x12"])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_decode_revert_reason() {
+        assert_eq!(
+            decode_revert_reason("Execution failed. Failure reason: 0x4e6f2062616c616e6365."),
+            "Execution failed. Failure reason: 0x4e6f2062616c616e6365 ('No balance')."
+        );
+        assert_eq!(
+            decode_revert_reason("Failure reason: 0x4e6f2062616c616e6365 ('No balance')."),
+            "Failure reason: 0x4e6f2062616c616e6365 ('No balance')."
+        );
+    }
+
+    #[test]
+    fn test_decode_revert_reason_skips_non_strings() {
+        let reason = "Error in the called contract (0x01):\nError at pc=0:12:\n0x0";
+        assert_eq!(decode_revert_reason(reason), reason);
+
+        let reason = "Error in the called contract (0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7)";
+        assert_eq!(decode_revert_reason(reason), reason);
+    }
+
+    #[test]
+    fn test_value_color() {
+        assert_eq!(value_color("transaction_hash"), Some("33"));
+        assert_eq!(value_color("contract_address"), Some("36"));
+        assert_eq!(value_color("error"), Some("31"));
+        assert_eq!(value_color("class_hash"), None);
+    }
+
+    #[test]
+    fn test_write_formatted_with_color() {
+        let output = [
+            ("command", OutputValue::Value(json!("invoke"))),
+            ("transaction_hash", OutputValue::Value(json!("0x1"))),
+        ];
+
+        let mut written = vec![];
+        write_formatted(&output, false, true, &mut written).unwrap();
+
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            "command: invoke\ntransaction_hash: \x1b[33m0x1\x1b[0m\n"
+        );
+    }
 }
@@ -1,32 +1,55 @@
-use crate::starknet_commands::account::Account;
-use crate::starknet_commands::show_config::ShowConfig;
-use crate::starknet_commands::{
-    account, call::Call, declare::Declare, deploy::Deploy, invoke::Invoke, multicall::Multicall,
-    script::Script,
+use anyhow::{anyhow, bail, Result};
+use cast::starknet_commands;
+use cast::starknet_commands::account::Account;
+use cast::starknet_commands::show_config::{ShowConfig, ShowConfigFormat};
+use cast::starknet_commands::{
+    account, balance::Balance, call::Call, class_hash_at::ClassHashAt, completions::Completions,
+    declare::Declare, deploy::Deploy, get_transaction_receipt::GetTransactionReceipt,
+    invoke::Invoke, multicall::Multicall, networks::Networks, nonce::Nonce, script::Script,
+    tx_status::TxStatus, verify::Verify, verify_signature::VerifySignature,
 };
-use anyhow::{anyhow, Result};
 
 use camino::Utf8PathBuf;
-use cast::helpers::constants::{DEFAULT_ACCOUNTS_FILE, DEFAULT_MULTICALL_CONTENTS};
-use cast::helpers::scarb_utils::{parse_scarb_config, CastConfig};
+use cast::client::{SncastClient, TransactionOptions};
+use cast::helpers::calldata::{parse_calldata_values, read_calldata_file};
+use cast::helpers::constants::{
+    ACCOUNTS_FILE_ENV_VAR, ACCOUNT_ENV_VAR, DEFAULT_ACCOUNTS_FILE, DEFAULT_MULTICALL_CONTENTS,
+    KEYSTORE_ENV_VAR, RPC_URL_ENV_VAR, STARKNET_RPC_ENV_VAR,
+};
+use cast::helpers::logging::init_logging;
+use cast::helpers::nonce_cache::NonceCache;
+use cast::helpers::response_structs::{
+    DeclareManyResponse, DeclareResponse, DeclareStatus, MulticallNewResponse,
+    MulticallRunResponse, StepStatus,
+};
+use cast::helpers::retry_transport::RetryTransport;
+use cast::helpers::scarb_utils::{
+    get_profile_name, parse_config, split_paths, split_urls, CastConfig,
+};
+use cast::helpers::signer::CastSigner;
 use cast::{
-    chain_id_to_network_name, get_account, get_block_id, get_chain_id, get_provider,
-    print_command_result, ValueFormat,
+    check_connection, get_account_address, get_accounts_file_with_account, get_block_id,
+    get_chain_id, get_entry_point_selector, get_env_var, get_network_name,
+    get_provider_with_fallbacks, parse_chain_id, print_command_result, salt_from_label,
+    set_chain_id, set_check_balance, set_color_choice, set_custom_networks, set_decode_strings,
+    set_keystore_password_source, set_output_file, validate_fee_multiplier, write_output_file,
+    write_to_output, ErrorPrinted, KeystorePasswordSource, ValidatedWaitParams, ValueFormat,
+    WaitForTx, WaitTimeoutError,
 };
-use clap::{Parser, Subcommand};
-use starknet::providers::jsonrpc::HttpTransport;
+use clap::{ArgMatches, ColorChoice, CommandFactory, FromArgMatches, Parser, Subcommand};
+use starknet::accounts::{Account as _, Call, SingleOwnerAccount};
+use starknet::core::types::{BlockId, BlockTag::Pending, FieldElement};
 use starknet::providers::JsonRpcClient;
+use std::time::Instant;
 use tokio::runtime::Runtime;
 
-mod starknet_commands;
-
 #[derive(Parser)]
 #[command(version)]
 #[command(about = "Cast - a Starknet Foundry CLI", long_about = None)]
 #[clap(name = "sncast")]
 #[allow(clippy::struct_excessive_bools)]
 struct Cli {
-    /// Profile name in Scarb.toml config file
+    /// Profile name in snfoundry.toml or Scarb.toml config file; can also be set with SNCAST_PROFILE environment variable
     #[clap(short, long)]
     profile: Option<String>,
 
@@ -34,24 +57,47 @@ struct Cli {
     #[clap(short = 's', long)]
     path_to_scarb_toml: Option<Utf8PathBuf>,
 
-    /// RPC provider url address; overrides url from Scarb.toml
-    #[clap(short = 'u', long = "url")]
-    rpc_url: Option<String>,
+    /// Path to a config file with the [sncast] section (in the format of snfoundry.toml) that is to be used;
+    /// overrides default behaviour of searching for snfoundry.toml and Scarb.toml in current or parent directories
+    #[clap(long)]
+    config_file: Option<Utf8PathBuf>,
+
+    /// RPC provider url address; overrides SNCAST_RPC_URL environment variable and url from Scarb.toml.
+    /// Can be passed multiple times (or as a comma separated list), in which case
+    /// the first url that responds is used
+    #[clap(short = 'u', long = "url", value_delimiter = ',')]
+    rpc_urls: Vec<String>,
+
+    /// Chain id used instead of fetching it from the RPC provider, either as a hex value,
+    /// a short string (e.g. SN_GOERLI) or a network name (e.g. alpha-goerli)
+    #[clap(long, value_parser = parse_chain_id)]
+    chain_id: Option<FieldElement>,
 
     /// Account to be used for contract declaration;
     /// When using keystore (`--keystore`), this should be a path to account file    
-    /// When using accounts file, this should be an account name
+    /// When using accounts file, this should be an account name;
+    /// overrides SNCAST_ACCOUNT environment variable and account from Scarb.toml
     #[clap(short = 'a', long)]
     account: Option<String>,
 
-    /// Path to the file holding accounts info
-    #[clap(short = 'f', long = "accounts-file")]
-    accounts_file_path: Option<Utf8PathBuf>,
+    /// Path to the file holding accounts info; overrides SNCAST_ACCOUNTS_FILE environment variable and accounts-file from Scarb.toml.
+    /// Can be passed multiple times (or as a colon separated list), in which case accounts are looked up
+    /// in the files in order and new accounts are written to the first one
+    #[clap(short = 'f', long = "accounts-file", value_delimiter = ':')]
+    accounts_file_paths: Vec<Utf8PathBuf>,
 
-    /// Path to keystore file; if specified, --account should be a path to starkli JSON account file
+    /// Path to keystore file; if specified, --account should be a path to starkli JSON account file;
+    /// overrides SNCAST_KEYSTORE environment variable and keystore from Scarb.toml
     #[clap(short, long)]
     keystore: Option<Utf8PathBuf>,
 
+    /// Command used to sign transactions instead of a private key, e.g. to use a hardware wallet;
+    /// if specified, --account should be a path to starkli JSON account file.
+    /// The hash to sign is written to its stdin and it should print `r` and `s` of the signature to stdout;
+    /// overrides signer-command from Scarb.toml
+    #[clap(long, conflicts_with = "keystore")]
+    signer_command: Option<String>,
+
     /// If passed, values will be displayed as integers
     #[clap(long, conflicts_with = "hex_format")]
     int_format: bool,
@@ -64,10 +110,85 @@ struct Cli {
     #[clap(short, long)]
     json: bool,
 
+    /// If passed, felts which are valid Cairo short strings are displayed together with the decoded text;
+    /// in json mode such values are displayed as objects with `raw` and `decoded` fields
+    #[clap(long)]
+    decode_strings: bool,
+
+    /// When to highlight transaction hashes, addresses and errors in the output;
+    /// `auto` enables colors only on a terminal, unless NO_COLOR environment variable is set
+    #[clap(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// If passed, it is checked that the RPC url can be reached before running the command
+    #[clap(long)]
+    check_connection: bool,
+
+    /// Path to a file with the keystore password in the first line, read instead of prompting for the password
+    #[clap(long, conflicts_with = "password_stdin")]
+    password_file: Option<Utf8PathBuf>,
+
+    /// If passed, the keystore password is read from the first line of stdin instead of prompting for it
+    #[clap(long)]
+    password_stdin: bool,
+
     /// If passed, command will wait until transaction is accepted or rejected
     #[clap(short, long)]
     wait: bool,
 
+    /// Timeout in seconds after which waiting for transaction (`--wait`) is aborted; overrides value from Scarb.toml [default: 300]
+    #[clap(long)]
+    wait_timeout: Option<u16>,
+
+    /// Maximum number of retries of idempotent RPC requests failing with transient errors; overrides value from Scarb.toml [default: 3]
+    #[clap(long, alias = "rpc-max-retries")]
+    max_rpc_retries: Option<u8>,
+
+    /// Delay in milliseconds before the first retry of a failed RPC request, doubled after every attempt; overrides value from Scarb.toml [default: 500]
+    #[clap(long)]
+    rpc_retry_base_delay: Option<u16>,
+
+    /// Interval in seconds between consecutive transaction status checks when using `--wait`; overrides value from Scarb.toml [default: 5]
+    #[clap(long)]
+    wait_retry_interval: Option<u8>,
+
+    /// Multiplier applied to the estimated fee to get the max fee, used when `--max-fee` is not passed; overrides value from Scarb.toml [default: 1.5]
+    #[clap(long)]
+    fee_multiplier: Option<f64>,
+
+    /// If passed, `declare`, `deploy`, `invoke` and `multicall run` will build and sign the transaction
+    /// and print it without sending it to the network
+    #[clap(long, conflicts_with = "wait")]
+    dry_run: bool,
+
+    /// If passed, nonces of `declare`, `deploy` and `invoke` transactions are taken from a local cache
+    /// (stored next to the accounts file) and incremented after every transaction, so that multiple
+    /// transactions can be sent in a row without `--wait`
+    #[clap(long)]
+    manage_nonce: bool,
+
+    /// If passed, the ETH balance of the account is checked before sending `declare`, `deploy` and `invoke`
+    /// transactions, and the command fails without sending the transaction if it doesn't cover the max fee
+    #[clap(long)]
+    check_balance: bool,
+
+    /// If passed, the command output will also be written to this file (formatted the same way as on stdout)
+    #[clap(long)]
+    output_file: Option<Utf8PathBuf>,
+
+    /// If passed, the command output will be appended to `--output-file` instead of overwriting it
+    #[clap(long, requires = "output_file")]
+    output_file_append: bool,
+
+    /// If passed, the command output will only be written to `--output-file` and not printed to stdout
+    #[clap(long, requires = "output_file")]
+    quiet: bool,
+
+    /// Log the resolved configuration and timing to stderr; pass twice (`-vv`) to log RPC requests
+    /// and three times (`-vvv`) to log full request and response bodies
+    #[clap(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -97,10 +218,88 @@ enum Commands {
 
     /// Run a deployment script
     Script(Script),
+
+    /// Get the status of a transaction
+    TxStatus(TxStatus),
+
+    /// Get the receipt of a transaction
+    GetTransactionReceipt(GetTransactionReceipt),
+
+    /// Get the token balance of an account
+    Balance(Balance),
+
+    /// Get the nonce of an account
+    Nonce(Nonce),
+
+    /// Get the class hash of a contract
+    #[command(alias = "get-class-hash-at")]
+    ClassHashAt(ClassHashAt),
+
+    /// Verify the source code of a contract on a block explorer
+    Verify(Verify),
+
+    /// List known networks and their chain ids
+    Networks(Networks),
+
+    /// Check whether a signature is valid for an account
+    VerifySignature(VerifySignature),
+
+    /// Generate a shell completion script
+    #[command(hide = true)]
+    Completions(Completions),
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    let json = cli.json;
+    init_logging(cli.verbose);
+
+    let start = Instant::now();
+    let result = run(cli);
+    tracing::info!("Command finished in {}ms", start.elapsed().as_millis());
+
+    match result {
+        Err(error) if json => {
+            // errors returned from commands are already printed by `print_command_result`
+            if error.downcast_ref::<ErrorPrinted>().is_none() {
+                let result: Result<()> = Err(error);
+                print_command_result(&command_name(&matches), &result, ValueFormat::Default, true)
+                    .ok();
+            }
+            exit_with_failure();
+        }
+        result => {
+            let written = write_output_file();
+            result.and(written)
+        }
+    }
+}
+
+/// The output file is written only once the command finishes, so it has to be written before exiting early
+fn exit_with_failure() -> ! {
+    if let Err(error) = write_output_file() {
+        eprintln!("{error:#}");
+    }
+    std::process::exit(1);
+}
+
+fn command_name(matches: &ArgMatches) -> String {
+    match matches.subcommand() {
+        Some((name, subcommand_matches)) => match subcommand_matches.subcommand_name() {
+            Some(subcommand_name) => format!("{name} {subcommand_name}"),
+            None => name.to_string(),
+        },
+        None => String::new(),
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    // Completion scripts are generated from the CLI definition only, without reading any configuration
+    if let Commands::Completions(completions) = &cli.command {
+        starknet_commands::completions::completions(completions.shell, &mut Cli::command());
+        return Ok(());
+    }
 
     // Clap validates that both are not passed at same time
     let value_format = if cli.hex_format {
@@ -111,14 +310,98 @@ fn main() -> Result<()> {
         ValueFormat::Default
     };
 
-    let mut config = parse_scarb_config(&cli.profile, &cli.path_to_scarb_toml)?;
-    update_cast_config(&mut config, &cli);
+    if let Some(output_file) = &cli.output_file {
+        set_output_file(output_file, cli.output_file_append, cli.quiet)?;
+    }
+    set_color_choice(cli.color);
+    set_check_balance(cli.check_balance);
+
+    let mut config = parse_config(&cli.profile, &cli.path_to_scarb_toml, &cli.config_file)?;
+    update_cast_config(&mut config, &cli)?;
+    set_decode_strings(config.decode_strings);
+    set_custom_networks(config.networks.clone());
+    if let Some(chain_id) = cli.chain_id {
+        set_chain_id(chain_id);
+    }
+    if let Some(password_file) = &cli.password_file {
+        set_keystore_password_source(KeystorePasswordSource::File(password_file.clone()));
+    } else if cli.password_stdin {
+        check_calldata_file_not_stdin(&cli.command)?;
+        set_keystore_password_source(KeystorePasswordSource::Stdin);
+    }
+    tracing::info!("Resolved config: {config:?}");
+
+    // Known networks are built in, so listing them doesn't require RPC url
+    if let Commands::Networks(_) = &cli.command {
+        let result = starknet_commands::networks::networks();
+        return starknet_commands::networks::print_networks(&result, value_format, cli.json);
+    }
+
+    // Listing accounts only reads the accounts file, so it doesn't require RPC url (unless nonces are fetched)
+    if let Commands::Account(Account {
+        command: account::Commands::List(list),
+    }) = &cli.command
+    {
+        if list.with_nonce {
+            let runtime = Runtime::new().expect("Could not instantiate Runtime");
+            return runtime.block_on(async {
+                let provider = get_provider_with_fallbacks(
+                    &config.rpc_urls,
+                    config.rpc_retries,
+                    config.rpc_retry_base_delay,
+                )
+                .await?;
+                let result = match starknet_commands::account::list::list(
+                    &config.accounts_files,
+                    list.show_private_keys,
+                    list.network.as_deref(),
+                ) {
+                    Ok(response) => {
+                        let network_name = get_network_name(&provider).await?;
+                        starknet_commands::account::list::add_nonces(
+                            response,
+                            &provider,
+                            &network_name,
+                        )
+                        .await
+                    }
+                    error => error,
+                };
+                starknet_commands::account::list::print_account_list(
+                    &result,
+                    value_format,
+                    cli.json,
+                )
+            });
+        }
+
+        let result = starknet_commands::account::list::list(
+            &config.accounts_files,
+            list.show_private_keys,
+            list.network.as_deref(),
+        );
+        return starknet_commands::account::list::print_account_list(
+            &result,
+            value_format,
+            cli.json,
+        );
+    }
 
-    let provider = get_provider(&config.rpc_url)?;
     let runtime = Runtime::new().expect("Could not instantiate Runtime");
+    let provider = runtime.block_on(get_provider_with_fallbacks(
+        &config.rpc_urls,
+        config.rpc_retries,
+        config.rpc_retry_base_delay,
+    ))?;
+    if cli.check_connection {
+        runtime.block_on(check_connection(
+            &config.rpc_urls,
+            config.rpc_retry_base_delay,
+        ))?;
+    }
 
     if let Commands::Script(script) = cli.command {
-        let mut result = starknet_commands::script::run(
+        let result = starknet_commands::script::run(
             &script.script_module_name,
             &cli.path_to_scarb_toml,
             &provider,
@@ -126,7 +409,7 @@ fn main() -> Result<()> {
             &config,
         );
 
-        print_command_result("script", &mut result, value_format, cli.json)?;
+        print_command_result("script", &result, value_format, cli.json)?;
         Ok(())
     } else {
         runtime.block_on(run_async_command(cli, config, provider, value_format))
@@ -137,116 +420,387 @@ fn main() -> Result<()> {
 async fn run_async_command(
     cli: Cli,
     mut config: CastConfig,
-    provider: JsonRpcClient<HttpTransport>,
+    provider: JsonRpcClient<RetryTransport>,
     value_format: ValueFormat,
 ) -> Result<()> {
+    let wait_config = WaitForTx {
+        wait: cli.wait,
+        wait_params: config.wait_params,
+    };
+    let nonce_cache = if cli.manage_nonce {
+        Some(NonceCache::for_accounts_file(
+            &config.accounts_file(),
+            &get_network_name(&provider).await?,
+        ))
+    } else {
+        None
+    };
+    let client = SncastClient::new(&provider, config.clone()).wait(cli.wait);
+
     match cli.command {
         Commands::Declare(declare) => {
-            let account = get_account(
-                &config.account,
-                &config.accounts_file,
-                &provider,
-                &config.keystore,
-            )
-            .await?;
-            let mut result = starknet_commands::declare::declare(
-                &declare.contract,
-                declare.max_fee,
-                &account,
-                &cli.path_to_scarb_toml,
-                cli.wait,
-            )
-            .await;
+            check_estimate_only(declare.estimate_only, cli.wait)?;
+            check_simulate(declare.simulate, cli.wait, cli.dry_run)?;
+            if declare.declares_many() {
+                if declare.estimate_only || declare.simulate || cli.dry_run {
+                    bail!("--estimate-only, --simulate and --dry-run can be used to declare a single contract only");
+                }
+                let account = client.account().await?;
+                let managed_nonce = managed_nonce(&nonce_cache, declare.nonce, &account).await?;
+                let result = starknet_commands::declare::declare_many(
+                    &declare.contract,
+                    declare.all,
+                    declare.max_fee,
+                    config.fee_multiplier,
+                    declare.nonce.or(managed_nonce),
+                    &account,
+                    &cli.path_to_scarb_toml,
+                    wait_config,
+                    declare.force_declare,
+                )
+                .await;
+                // the batch may use any number of nonces, so the cached one is refetched next time
+                if let Some(nonce_cache) = &nonce_cache {
+                    nonce_cache.invalidate(account.address())?;
+                }
+
+                starknet_commands::declare::print_declare_many_result(
+                    &result,
+                    value_format,
+                    cli.json,
+                )?;
+                exit_on_failed_declarations(&result);
+                return Ok(());
+            }
+            let contract = declare.contract_source()?;
+            let (account, signer) = client.account_with_signer().await?;
+            if declare.estimate_only {
+                let result = starknet_commands::declare::estimate_declare(
+                    &contract,
+                    declare.max_fee,
+                    config.fee_multiplier,
+                    &account,
+                    &cli.path_to_scarb_toml,
+                )
+                .await;
 
-            print_command_result("declare", &mut result, value_format, cli.json)?;
+                print_command_result("declare", &result, value_format, cli.json)?;
+                return Ok(());
+            }
+            if declare.simulate {
+                let result = starknet_commands::declare::simulate_declare(
+                    &contract,
+                    declare.max_fee,
+                    declare.nonce,
+                    &account,
+                    &cli.path_to_scarb_toml,
+                )
+                .await;
+
+                print_command_result("declare", &result, value_format, cli.json)?;
+                return Ok(());
+            }
+            if cli.dry_run {
+                let result = starknet_commands::declare::dry_run_declare(
+                    &contract,
+                    declare.max_fee,
+                    config.fee_multiplier,
+                    declare.nonce,
+                    &account,
+                    &signer,
+                    &cli.path_to_scarb_toml,
+                )
+                .await;
+
+                print_command_result("declare", &result, value_format, cli.json)?;
+                return Ok(());
+            }
+            let managed_nonce = managed_nonce(&nonce_cache, declare.nonce, &account).await?;
+            let options = TransactionOptions {
+                max_fee: declare.max_fee,
+                nonce: declare.nonce.or(managed_nonce),
+            };
+            let result = client
+                .declare(
+                    &contract,
+                    options,
+                    &cli.path_to_scarb_toml,
+                    declare.force_declare,
+                )
+                .await;
+            // no nonce is used if the declaration is skipped
+            if !matches!(
+                result,
+                Ok(DeclareResponse {
+                    transaction_hash: None,
+                    ..
+                })
+            ) {
+                update_nonce_cache(&nonce_cache, managed_nonce, &account, &result)?;
+            }
+
+            print_command_result("declare", &result, value_format, cli.json)?;
+            exit_on_wait_timeout(&result);
             Ok(())
         }
         Commands::Deploy(deploy) => {
-            let account = get_account(
-                &config.account,
-                &config.accounts_file,
-                &provider,
-                &config.keystore,
-            )
-            .await?;
-            let mut result = starknet_commands::deploy::deploy(
-                deploy.class_hash,
-                deploy.constructor_calldata,
-                deploy.salt,
-                deploy.unique,
-                deploy.max_fee,
-                &account,
-                cli.wait,
-            )
-            .await;
+            check_estimate_only(deploy.estimate_only, cli.wait)?;
+            check_simulate(deploy.simulate, cli.wait, cli.dry_run)?;
+            let salt = deploy
+                .salt
+                .or_else(|| deploy.salt_from_label.as_deref().map(salt_from_label));
+            let constructor_calldata = match &deploy.calldata_file {
+                Some(calldata_file) => read_calldata_file(calldata_file)?,
+                None => parse_calldata_values(&deploy.constructor_calldata)?,
+            };
+            let (account, signer) = client.account_with_signer().await?;
+            if deploy.estimate_only {
+                let result = starknet_commands::deploy::estimate_deploy(
+                    deploy.class_hash,
+                    &constructor_calldata,
+                    salt,
+                    deploy.unique,
+                    deploy.max_fee,
+                    config.fee_multiplier,
+                    deploy.skip_validation,
+                    &account,
+                )
+                .await;
+
+                print_command_result("deploy", &result, value_format, cli.json)?;
+                return Ok(());
+            }
+            if deploy.simulate {
+                let result = starknet_commands::deploy::simulate_deploy(
+                    deploy.class_hash,
+                    &constructor_calldata,
+                    salt,
+                    deploy.unique,
+                    deploy.max_fee,
+                    deploy.nonce,
+                    deploy.skip_validation,
+                    &account,
+                )
+                .await;
+
+                print_command_result("deploy", &result, value_format, cli.json)?;
+                return Ok(());
+            }
+            if cli.dry_run {
+                let result = starknet_commands::deploy::dry_run_deploy(
+                    deploy.class_hash,
+                    constructor_calldata,
+                    salt,
+                    deploy.unique,
+                    deploy.max_fee,
+                    config.fee_multiplier,
+                    deploy.nonce,
+                    deploy.skip_validation,
+                    &account,
+                    &signer,
+                )
+                .await;
 
-            print_command_result("deploy", &mut result, value_format, cli.json)?;
+                print_command_result("deploy", &result, value_format, cli.json)?;
+                return Ok(());
+            }
+            let managed_nonce = managed_nonce(&nonce_cache, deploy.nonce, &account).await?;
+            let options = TransactionOptions {
+                max_fee: deploy.max_fee,
+                nonce: deploy.nonce.or(managed_nonce),
+            };
+            let result = client
+                .deploy(
+                    deploy.class_hash,
+                    constructor_calldata,
+                    salt,
+                    deploy.unique,
+                    deploy.skip_validation,
+                    options,
+                )
+                .await;
+            update_nonce_cache(&nonce_cache, managed_nonce, &account, &result)?;
+
+            print_command_result("deploy", &result, value_format, cli.json)?;
+            exit_on_wait_timeout(&result);
             Ok(())
         }
         Commands::Call(call) => {
-            let block_id = get_block_id(&call.block_id)?;
+            if call.raw {
+                // felts which are valid short strings are printed as they are as well
+                set_decode_strings(false);
+            }
+            let block_id =
+                get_block_id(call.block_id.as_ref().unwrap_or(&config.default_block_id))?;
+            let selector = get_entry_point_selector(call.function.as_deref(), call.selector)?;
 
-            let mut result = starknet_commands::call::call(
-                call.contract_address,
-                call.function.as_ref(),
-                call.calldata,
+            if let Some(addresses_file) = &call.contract_addresses_file {
+                let contract_addresses =
+                    starknet_commands::call::read_contract_addresses(addresses_file)?;
+                let result = starknet_commands::call::call_many(
+                    &call,
+                    contract_addresses,
+                    selector,
+                    &provider,
+                    &block_id,
+                )
+                .await;
+
+                print_command_result("call", &result, value_format, cli.json)?;
+                return Ok(());
+            }
+
+            let contract_address = call
+                .contract_address
+                .expect("--contract-address is required without --contract-addresses-file");
+            let result = starknet_commands::call::call_contract(
+                &call,
+                contract_address,
+                selector,
                 &provider,
-                block_id.as_ref(),
+                &block_id,
             )
             .await;
 
-            print_command_result("call", &mut result, value_format, cli.json)?;
+            print_command_result("call", &result, value_format, cli.json)?;
             Ok(())
         }
         Commands::Invoke(invoke) => {
-            let account = get_account(
-                &config.account,
-                &config.accounts_file,
-                &provider,
-                &config.keystore,
-            )
-            .await?;
-            let mut result = starknet_commands::invoke::invoke(
-                invoke.contract_address,
-                &invoke.function,
-                invoke.calldata,
-                invoke.max_fee,
-                &account,
-                cli.wait,
-            )
-            .await;
+            check_estimate_only(invoke.estimate_only, cli.wait)?;
+            check_simulate(invoke.simulate, cli.wait, cli.dry_run)?;
+            let block_id = get_block_id(&invoke.block_id)?;
+            let (mut account, signer) = client.account_with_signer().await?;
+            let calls = match invoke.contract_address {
+                Some(contract_address) => {
+                    let selector =
+                        get_entry_point_selector(invoke.function.as_deref(), invoke.selector)?;
+                    let calldata = invoke
+                        .calldata_args
+                        .resolve(
+                            &provider,
+                            contract_address,
+                            invoke.function.as_deref(),
+                            &BlockId::Tag(Pending),
+                        )
+                        .await?;
+                    vec![Call {
+                        to: contract_address,
+                        selector,
+                        calldata,
+                    }]
+                }
+                None => invoke.calls,
+            };
+            if invoke.estimate_only {
+                account.set_block_id(block_id);
+                let result = starknet_commands::invoke::estimate_calls(
+                    &account,
+                    calls,
+                    invoke.max_fee,
+                    config.fee_multiplier,
+                )
+                .await;
 
-            print_command_result("invoke", &mut result, value_format, cli.json)?;
+                print_command_result("invoke", &result, value_format, cli.json)?;
+                return Ok(());
+            }
+            if invoke.simulate {
+                account.set_block_id(block_id);
+                let result = starknet_commands::invoke::simulate_calls(
+                    &account,
+                    calls,
+                    invoke.max_fee,
+                    invoke.nonce,
+                )
+                .await;
+
+                print_command_result("invoke", &result, value_format, cli.json)?;
+                return Ok(());
+            }
+            if cli.dry_run {
+                let result = starknet_commands::invoke::dry_run_calls(
+                    &account,
+                    &signer,
+                    calls,
+                    invoke.max_fee,
+                    config.fee_multiplier,
+                    invoke.nonce,
+                )
+                .await;
+
+                print_command_result("invoke", &result, value_format, cli.json)?;
+                return Ok(());
+            }
+            let managed_nonce = managed_nonce(&nonce_cache, invoke.nonce, &account).await?;
+            let options = TransactionOptions {
+                max_fee: invoke.max_fee,
+                nonce: invoke.nonce.or(managed_nonce),
+            };
+            let result = client.execute(calls, block_id, options).await;
+            update_nonce_cache(&nonce_cache, managed_nonce, &account, &result)?;
+
+            print_command_result("invoke", &result, value_format, cli.json)?;
+            exit_on_wait_timeout(&result);
             Ok(())
         }
         Commands::Multicall(multicall) => {
             match &multicall.command {
                 starknet_commands::multicall::Commands::New(new) => {
                     if let Some(output_path) = &new.output_path {
-                        let mut result =
+                        let result =
                             starknet_commands::multicall::new::new(output_path, new.overwrite);
-                        print_command_result("multicall new", &mut result, value_format, cli.json)?;
+                        print_command_result("multicall new", &result, value_format, cli.json)?;
+                    } else if cli.json {
+                        let result = Ok(MulticallNewResponse {
+                            path: None,
+                            content: DEFAULT_MULTICALL_CONTENTS.to_string(),
+                        });
+                        print_command_result("multicall new", &result, value_format, cli.json)?;
                     } else {
                         println!("{DEFAULT_MULTICALL_CONTENTS}");
                     }
                 }
                 starknet_commands::multicall::Commands::Run(run) => {
-                    let account = get_account(
-                        &config.account,
-                        &config.accounts_file,
-                        &provider,
-                        &config.keystore,
-                    )
-                    .await?;
-                    let mut result = starknet_commands::multicall::run::run(
-                        &run.path,
-                        &account,
-                        run.max_fee,
-                        cli.wait,
-                    )
-                    .await;
+                    let (account, signer) = client.account_with_signer().await?;
+                    if run.simulate {
+                        let result = starknet_commands::multicall::run::simulate(
+                            &run.path,
+                            &account,
+                            run.max_fee,
+                        )
+                        .await;
 
-                    print_command_result("multicall run", &mut result, value_format, cli.json)?;
+                        print_command_result("multicall run", &result, value_format, cli.json)?;
+                    } else if cli.dry_run {
+                        let result = starknet_commands::multicall::run::dry_run(
+                            &run.path,
+                            &account,
+                            &signer,
+                            run.max_fee,
+                            config.fee_multiplier,
+                        )
+                        .await;
+
+                        print_command_result("multicall run", &result, value_format, cli.json)?;
+                    } else {
+                        let result = starknet_commands::multicall::run::run(
+                            &run.path,
+                            &account,
+                            run.max_fee,
+                            config.fee_multiplier,
+                            wait_config,
+                            run.continue_on_error,
+                        )
+                        .await;
+
+                        starknet_commands::multicall::run::print_run_result(
+                            &result,
+                            value_format,
+                            cli.json,
+                        )?;
+                        exit_on_wait_timeout(&result);
+                        exit_on_failed_steps(&result);
+                    }
                 }
             }
             Ok(())
@@ -254,17 +808,30 @@ async fn run_async_command(
         Commands::Account(account) => match account.command {
             account::Commands::Add(add) => {
                 config.account = add.name.clone();
-                let mut result = starknet_commands::account::add::add(
-                    &config.rpc_url,
+                let result = starknet_commands::account::add::add(
+                    &config.rpc_urls,
                     &config.account,
-                    &config.accounts_file,
+                    &config.accounts_file(),
                     &cli.path_to_scarb_toml,
                     &provider,
                     &add,
                 )
                 .await;
 
-                print_command_result("account add", &mut result, value_format, cli.json)?;
+                print_command_result("account add", &result, value_format, cli.json)?;
+                Ok(())
+            }
+            account::Commands::Import(import) => {
+                config.account = import.name.clone();
+                let result = starknet_commands::account::import::import(
+                    &config.account,
+                    &config.accounts_file(),
+                    &provider,
+                    &import,
+                )
+                .await;
+
+                print_command_result("account import", &result, value_format, cli.json)?;
                 Ok(())
             }
             account::Commands::Create(create) => {
@@ -274,21 +841,27 @@ async fn run_async_command(
                         .name
                         .ok_or_else(|| anyhow!("required argument --name not provided"))?;
                 }
-                let mut result = starknet_commands::account::create::create(
-                    &config.rpc_url,
+                let result = starknet_commands::account::create::create(
+                    &config.rpc_urls,
                     &config.account,
-                    &config.accounts_file,
+                    &config.accounts_file(),
                     &config.keystore,
                     &provider,
                     cli.path_to_scarb_toml,
                     chain_id,
                     create.salt,
                     create.add_profile,
+                    create.overwrite,
+                    create.account_type,
                     create.class_hash,
+                    create.fund_from_faucet,
+                    &config.faucets,
+                    create.constructor_calldata,
+                    config.wait_params,
                 )
                 .await;
 
-                print_command_result("account create", &mut result, value_format, cli.json)?;
+                print_command_result("account create", &result, value_format, cli.json)?;
                 Ok(())
             }
             account::Commands::Deploy(deploy) => {
@@ -302,20 +875,34 @@ async fn run_async_command(
                         .name
                         .ok_or_else(|| anyhow!("required argument --name not provided"))?;
                 }
-                let mut result = starknet_commands::account::deploy::deploy(
+                // the account is marked as deployed in the accounts file it is defined in
+                let accounts_file = if config.keystore == Utf8PathBuf::default() {
+                    get_accounts_file_with_account(
+                        &config.account,
+                        chain_id,
+                        &config.accounts_files,
+                    )?
+                    .unwrap_or_else(|| config.accounts_file())
+                } else {
+                    config.accounts_file()
+                };
+                let result = starknet_commands::account::deploy::deploy(
                     &provider,
-                    config.accounts_file,
+                    accounts_file,
                     config.account,
                     chain_id,
                     deploy.max_fee,
-                    cli.wait,
+                    config.fee_multiplier,
+                    wait_config,
                     deploy.class_hash,
+                    deploy.constructor_calldata,
                     keystore_path,
                     account_path,
                 )
                 .await;
 
-                print_command_result("account deploy", &mut result, value_format, cli.json)?;
+                print_command_result("account deploy", &result, value_format, cli.json)?;
+                exit_on_wait_timeout(&result);
                 Ok(())
             }
             account::Commands::Delete(delete) => {
@@ -324,51 +911,352 @@ async fn run_async_command(
                     .ok_or_else(|| anyhow!("required argument --name not provided"))?;
                 let network_name = match delete.network {
                     Some(network) => network,
-                    None => chain_id_to_network_name(get_chain_id(&provider).await?),
+                    None => get_network_name(&provider).await?,
                 };
 
-                let mut result = starknet_commands::account::delete::delete(
+                let result = starknet_commands::account::delete::delete(
                     &config.account,
-                    &config.accounts_file,
+                    &config.accounts_file(),
                     &cli.path_to_scarb_toml,
                     delete.delete_profile,
                     &network_name,
                 );
 
-                print_command_result("account delete", &mut result, value_format, cli.json)?;
+                print_command_result("account delete", &result, value_format, cli.json)?;
+                Ok(())
+            }
+            account::Commands::Export(export) => {
+                let network_name = match &export.network {
+                    Some(network) => network.clone(),
+                    None => get_network_name(&provider).await?,
+                };
+
+                let result = starknet_commands::account::export::export(
+                    &config.accounts_files,
+                    &network_name,
+                    &export,
+                );
+
+                print_command_result("account export", &result, value_format, cli.json)?;
                 Ok(())
             }
+            account::Commands::List(_) => unreachable!(),
         },
-        Commands::ShowConfig(_) => {
-            let mut result = starknet_commands::show_config::show_config(
+        Commands::ShowConfig(show_config) => {
+            if show_config.format == ShowConfigFormat::Toml {
+                if cli.json {
+                    bail!("--json cannot be used with --format toml");
+                }
+                let toml = starknet_commands::show_config::show_config_toml(
+                    &config,
+                    &get_profile_name(&cli.profile),
+                )?;
+                write_to_output(toml.trim_end(), false);
+                return Ok(());
+            }
+            let json = cli.json || show_config.format == ShowConfigFormat::Json;
+            let result = starknet_commands::show_config::show_config(
                 &provider,
                 config,
-                cli.profile,
+                get_profile_name(&cli.profile),
                 cli.path_to_scarb_toml,
             )
             .await;
-            print_command_result("show-config", &mut result, value_format, cli.json)?;
+            if let (Ok(response), false) = (&result, json) {
+                for warning in &response.warnings {
+                    eprintln!("Warning: {warning}");
+                }
+            }
+            print_command_result("show-config", &result, value_format, json)?;
+            Ok(())
+        }
+        Commands::TxStatus(tx_status) => {
+            let result =
+                starknet_commands::tx_status::tx_status(&provider, tx_status.transaction_hash)
+                    .await;
+
+            print_command_result("tx-status", &result, value_format, cli.json)?;
+            Ok(())
+        }
+        Commands::GetTransactionReceipt(get_transaction_receipt) => {
+            let result = starknet_commands::get_transaction_receipt::get_transaction_receipt(
+                &provider,
+                get_transaction_receipt.transaction_hash,
+            )
+            .await;
+
+            print_command_result("get-transaction-receipt", &result, value_format, cli.json)?;
             Ok(())
         }
-        Commands::Script(_) => unreachable!(),
+        Commands::Balance(balance) => {
+            let block_id = get_block_id(&balance.block_id)?;
+            let address = match balance.address {
+                Some(address) => address,
+                None => {
+                    get_account_address(
+                        &config.account,
+                        &config.accounts_files,
+                        &provider,
+                        &config.keystore,
+                        &config.signer_command,
+                    )
+                    .await?
+                }
+            };
+
+            let result =
+                starknet_commands::balance::balance(address, balance.token, &provider, &block_id)
+                    .await;
+
+            print_command_result("balance", &result, value_format, cli.json)?;
+            Ok(())
+        }
+        Commands::Nonce(nonce) => {
+            let block_id = get_block_id(&nonce.block_id)?;
+            let address = match nonce.address {
+                Some(address) => address,
+                None => {
+                    get_account_address(
+                        &config.account,
+                        &config.accounts_files,
+                        &provider,
+                        &config.keystore,
+                        &config.signer_command,
+                    )
+                    .await?
+                }
+            };
+
+            let result = starknet_commands::nonce::nonce(address, &provider, &block_id).await;
+
+            print_command_result("nonce", &result, value_format, cli.json)?;
+            Ok(())
+        }
+        Commands::ClassHashAt(class_hash_at) => {
+            let block_id = get_block_id(&class_hash_at.block_id)?;
+
+            let result = starknet_commands::class_hash_at::class_hash_at(
+                class_hash_at.contract_address,
+                &provider,
+                &block_id,
+            )
+            .await;
+
+            print_command_result("class-hash-at", &result, value_format, cli.json)?;
+            Ok(())
+        }
+        Commands::Verify(verify) => {
+            let api_url = match verify.verifier_url {
+                Some(url) => url,
+                None => starknet_commands::verify::verifier_api_url(
+                    verify.verifier,
+                    &get_network_name(&provider).await?,
+                )?
+                .to_string(),
+            };
+            let result = starknet_commands::verify::verify(
+                verify.contract_address,
+                verify.class_hash,
+                verify.contract_name,
+                verify.verifier,
+                &api_url,
+                &cli.path_to_scarb_toml,
+                config.wait_params,
+            )
+            .await;
+
+            print_command_result("verify", &result, value_format, cli.json)?;
+            Ok(())
+        }
+        Commands::VerifySignature(verify_signature) => {
+            let block_id = get_block_id(&verify_signature.block_id)?;
+            let address = match verify_signature.address {
+                Some(address) => address,
+                None => {
+                    get_account_address(
+                        &config.account,
+                        &config.accounts_files,
+                        &provider,
+                        &config.keystore,
+                        &config.signer_command,
+                    )
+                    .await?
+                }
+            };
+
+            let result = starknet_commands::verify_signature::verify_signature(
+                address,
+                verify_signature.hash,
+                &verify_signature.signature,
+                &provider,
+                &block_id,
+            )
+            .await;
+
+            print_command_result("verify-signature", &result, value_format, cli.json)?;
+            Ok(())
+        }
+        Commands::Script(_) | Commands::Networks(_) | Commands::Completions(_) => unreachable!(),
     }
 }
 
-fn update_cast_config(config: &mut CastConfig, cli: &Cli) {
-    macro_rules! clone_or_else {
-        ($field:expr, $config_field:expr) => {
-            $field.clone().unwrap_or_else(|| $config_field.clone())
+/// With `--manage-nonce`, returns the cached nonce of the account, unless the nonce is passed explicitly
+async fn managed_nonce(
+    nonce_cache: &Option<NonceCache>,
+    nonce: Option<FieldElement>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+) -> Result<Option<FieldElement>> {
+    match nonce_cache {
+        Some(nonce_cache) if nonce.is_none() => Ok(Some(nonce_cache.next_nonce(account).await?)),
+        _ => Ok(None),
+    }
+}
+
+fn update_nonce_cache<T>(
+    nonce_cache: &Option<NonceCache>,
+    managed_nonce: Option<FieldElement>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    result: &Result<T>,
+) -> Result<()> {
+    if let (Some(nonce_cache), Some(nonce)) = (nonce_cache, managed_nonce) {
+        nonce_cache.update(account.address(), nonce, result)?;
+    }
+    Ok(())
+}
+
+fn check_estimate_only(estimate_only: bool, wait: bool) -> Result<()> {
+    if estimate_only && wait {
+        bail!("--estimate-only cannot be used with --wait, as the transaction is not sent");
+    }
+    Ok(())
+}
+
+fn check_calldata_file_not_stdin(command: &Commands) -> Result<()> {
+    let calldata_file = match command {
+        Commands::Call(call) => call.calldata_args.calldata_file.as_ref(),
+        Commands::Invoke(invoke) => invoke.calldata_args.calldata_file.as_ref(),
+        Commands::Deploy(deploy) => deploy.calldata_file.as_ref(),
+        _ => None,
+    };
+    if calldata_file.is_some_and(|path| path.as_str() == "-") {
+        bail!(
+            "--calldata-file - cannot be used with --password-stdin, as both are read from stdin"
+        );
+    }
+    Ok(())
+}
+
+fn check_simulate(simulate: bool, wait: bool, dry_run: bool) -> Result<()> {
+    if simulate && wait {
+        bail!("--simulate cannot be used with --wait, as the transaction is not sent");
+    }
+    if simulate && dry_run {
+        bail!("--simulate cannot be used with --dry-run");
+    }
+    Ok(())
+}
+
+// Transaction has been sent, but its status is unknown, so the command should not be treated as successful
+fn exit_on_wait_timeout<T>(result: &Result<T>) {
+    if let Err(error) = result {
+        if error.downcast_ref::<WaitTimeoutError>().is_some() {
+            exit_with_failure();
+        }
+    }
+}
+
+// Failed calls are reported in the summary printed with `--continue-on-error`, but the command should not be
+// treated as successful
+fn exit_on_failed_steps(result: &Result<MulticallRunResponse>) {
+    if let Ok(MulticallRunResponse {
+        steps: Some(steps), ..
+    }) = result
+    {
+        if steps
+            .iter()
+            .any(|step| step.status != StepStatus::Succeeded)
+        {
+            exit_with_failure();
+        }
+    }
+}
+
+// Contracts which failed to be declared are reported in the summary, but the command should not be treated as successful
+fn exit_on_failed_declarations(result: &Result<DeclareManyResponse>) {
+    if let Ok(response) = result {
+        if response
+            .results
+            .iter()
+            .any(|result| result.status == DeclareStatus::Failed)
+        {
+            exit_with_failure();
+        }
+    }
+}
+
+/// Values are taken from (in order of precedence): CLI flags, environment variables, Scarb.toml
+/// (the profile merged with the profiles it inherits from), defaults
+fn update_cast_config(config: &mut CastConfig, cli: &Cli) -> Result<()> {
+    macro_rules! cli_env_or_config {
+        ($cli_field:expr, $env_var:expr, $config_field:expr) => {
+            $cli_field
+                .clone()
+                .or_else(|| get_env_var($env_var).map(Into::into))
+                .unwrap_or_else(|| $config_field.clone())
         };
     }
 
-    config.rpc_url = clone_or_else!(cli.rpc_url, config.rpc_url);
-    config.account = clone_or_else!(cli.account, config.account);
-    config.keystore = clone_or_else!(cli.keystore, config.keystore);
+    if !cli.rpc_urls.is_empty() {
+        config.rpc_urls = cli
+            .rpc_urls
+            .iter()
+            .map(|url| url.trim().to_string())
+            .collect();
+    } else if let Some(rpc_urls) = get_env_var(RPC_URL_ENV_VAR) {
+        config.rpc_urls = split_urls(&rpc_urls);
+    } else if config.rpc_urls.is_empty() {
+        if let Some(rpc_urls) = get_env_var(STARKNET_RPC_ENV_VAR) {
+            config.rpc_urls = split_urls(&rpc_urls);
+        }
+    }
+    config.account = cli_env_or_config!(cli.account, ACCOUNT_ENV_VAR, config.account);
+    config.keystore = cli_env_or_config!(cli.keystore, KEYSTORE_ENV_VAR, config.keystore);
+    config.signer_command = cli
+        .signer_command
+        .clone()
+        .unwrap_or_else(|| config.signer_command.clone());
+    if !config.signer_command.is_empty() && config.keystore != Utf8PathBuf::default() {
+        bail!("--signer-command (or signer-command) cannot be used together with --keystore (or keystore)");
+    }
+    config.decode_strings = cli.decode_strings || config.decode_strings;
+    config.rpc_retries = cli.max_rpc_retries.unwrap_or(config.rpc_retries);
+    config.rpc_retry_base_delay = cli
+        .rpc_retry_base_delay
+        .unwrap_or(config.rpc_retry_base_delay);
+    if let Some(fee_multiplier) = cli.fee_multiplier {
+        config.fee_multiplier = validate_fee_multiplier(fee_multiplier)?;
+    }
 
-    if config.accounts_file == Utf8PathBuf::default() {
-        config.accounts_file = Utf8PathBuf::from(DEFAULT_ACCOUNTS_FILE);
+    if !cli.accounts_file_paths.is_empty() {
+        config.accounts_files = cli.accounts_file_paths.clone();
+    } else if let Some(accounts_files) = get_env_var(ACCOUNTS_FILE_ENV_VAR) {
+        config.accounts_files = split_paths(&accounts_files);
     }
-    let new_accounts_file = clone_or_else!(cli.accounts_file_path, config.accounts_file);
+    if config.accounts_files.is_empty() {
+        config.accounts_files = vec![Utf8PathBuf::from(DEFAULT_ACCOUNTS_FILE)];
+    }
+    config.accounts_files = config
+        .accounts_files
+        .iter()
+        .map(|accounts_file| Utf8PathBuf::from(shellexpand::tilde(accounts_file).to_string()))
+        .collect();
+
+    config.wait_params = ValidatedWaitParams::new(
+        cli.wait_retry_interval
+            .unwrap_or_else(|| config.wait_params.get_retry_interval()),
+        cli.wait_timeout
+            .unwrap_or_else(|| config.wait_params.get_timeout()),
+    )?;
 
-    config.accounts_file = Utf8PathBuf::from(shellexpand::tilde(&new_accounts_file).to_string());
+    Ok(())
 }
@@ -1,18 +1,16 @@
+use crate::helpers::response_structs::AccountAddResponse;
+use crate::helpers::retry_transport::RetryTransport;
+use crate::helpers::scarb_utils::CastConfig;
 use crate::starknet_commands::account::{
     add_created_profile_to_configuration, prepare_account_json, write_account_to_accounts_file,
 };
+use crate::{get_chain_id, parse_number};
 use anyhow::{ensure, Context, Result};
 use camino::Utf8PathBuf;
-use cast::helpers::response_structs::AccountAddResponse;
-use cast::helpers::scarb_utils::CastConfig;
-use cast::{get_chain_id, parse_number};
 use clap::Args;
 use starknet::core::types::BlockTag::Pending;
 use starknet::core::types::{BlockId, FieldElement};
-use starknet::providers::{
-    jsonrpc::{HttpTransport, JsonRpcClient},
-    Provider,
-};
+use starknet::providers::{jsonrpc::JsonRpcClient, Provider};
 use starknet::signers::SigningKey;
 
 #[derive(Args, Debug)]
@@ -57,11 +55,11 @@ pub struct Add {
 }
 
 pub async fn add(
-    rpc_url: &str,
+    rpc_urls: &[String],
     account: &str,
     accounts_file: &Utf8PathBuf,
     path_to_scarb_toml: &Option<Utf8PathBuf>,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<RetryTransport>,
     add: &Add,
 ) -> Result<AccountAddResponse> {
     let private_key = match &add.private_key_file_path {
@@ -91,12 +89,13 @@ pub async fn add(
 
     if add.add_profile {
         let config = CastConfig {
-            rpc_url: rpc_url.into(),
+            rpc_urls: rpc_urls.to_vec(),
             account: account.into(),
-            accounts_file: accounts_file.into(),
+            accounts_files: vec![accounts_file.clone()],
             keystore: Utf8PathBuf::default(),
+            ..Default::default()
         };
-        add_created_profile_to_configuration(path_to_scarb_toml, &config)?;
+        add_created_profile_to_configuration(path_to_scarb_toml, &config, false)?;
     }
 
     Ok(AccountAddResponse {
@@ -1,20 +1,27 @@
+use crate::helpers::account_factory::{AccountType, CastAccountFactory};
+use crate::helpers::constants::{CREATE_KEYSTORE_PASSWORD_ENV_VAR, ETH_ADDRESS, FAUCET_URLS};
+use crate::helpers::response_structs::AccountCreateResponse;
+use crate::helpers::retry_transport::RetryTransport;
+use crate::helpers::scarb_utils::CastConfig;
 use crate::starknet_commands::account::{
     add_created_profile_to_configuration, prepare_account_json, write_account_to_accounts_file,
 };
-use anyhow::{anyhow, bail, Result};
+use crate::starknet_commands::balance::balance;
+use crate::{
+    chain_id_to_network_name, extract_or_generate_salt, get_chain_id, get_keystore_password,
+    parse_number, parse_salt, ValidatedWaitParams,
+};
+use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
-use cast::helpers::constants::{CREATE_KEYSTORE_PASSWORD_ENV_VAR, OZ_CLASS_HASH};
-use cast::helpers::response_structs::AccountCreateResponse;
-use cast::helpers::scarb_utils::CastConfig;
-use cast::{extract_or_generate_salt, get_chain_id, get_keystore_password, parse_number};
 use clap::Args;
 use serde_json::json;
-use starknet::accounts::{AccountFactory, OpenZeppelinAccountFactory};
-use starknet::core::types::{FeeEstimate, FieldElement};
+use starknet::accounts::AccountFactory;
+use starknet::core::types::{BlockId, BlockTag, FeeEstimate, FieldElement};
 use starknet::core::utils::get_contract_address;
-use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 use starknet::signers::{LocalWallet, SigningKey};
+use std::time::Duration;
+use tokio::time::sleep;
 
 #[derive(Args, Debug)]
 #[command(about = "Create an account with all important secrets")]
@@ -23,41 +30,71 @@ pub struct Create {
     #[clap(short, long)]
     pub name: Option<String>,
 
-    /// Salt for the address
-    #[clap(short, long)]
+    /// Salt for the address; pass `random` to generate a random one.
+    /// If not passed, a random salt is used
+    #[clap(short, long, value_parser = parse_salt)]
     pub salt: Option<FieldElement>,
 
     /// If passed, a profile with corresponding data will be created in Scarb.toml
     #[clap(long)]
     pub add_profile: bool,
-    // TODO (#253): think about supporting different account providers
-    /// Custom open zeppelin contract class hash of declared contract
+
+    /// If passed with --add-profile, an existing profile with the same name in Scarb.toml will be overwritten
+    #[clap(long, requires = "add_profile")]
+    pub overwrite: bool,
+
+    /// Type of the account contract, which determines its default class hash and constructor calldata
+    #[clap(long = "type", value_enum, default_value_t = AccountType::Oz)]
+    pub account_type: AccountType,
+
+    /// Custom class hash of the declared account contract of the chosen type
     #[clap(short, long)]
     pub class_hash: Option<String>,
+
+    /// If passed, the account is funded from the faucet of the network (if one is known) and
+    /// the command waits until the funds arrive
+    #[clap(long)]
+    pub fund_from_faucet: bool,
+
+    /// Calldata for the account constructor (list of hex or decimal felts), used instead of the
+    /// default calldata of the account type to compute the address. It is saved with the account,
+    /// so that `account deploy` uses it as well
+    #[clap(long, num_args = 1..)]
+    pub constructor_calldata: Option<Vec<FieldElement>>,
 }
 
 #[allow(clippy::too_many_arguments)]
 pub async fn create(
-    rpc_url: &str,
+    rpc_urls: &[String],
     account: &str,
     accounts_file: &Utf8PathBuf,
     keystore: &Utf8PathBuf,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<RetryTransport>,
     path_to_scarb_toml: Option<Utf8PathBuf>,
     chain_id: FieldElement,
     salt: Option<FieldElement>,
     add_profile: bool,
+    overwrite: bool,
+    account_type: AccountType,
     class_hash: Option<String>,
+    fund_from_faucet: bool,
+    faucets: &[(String, String)],
+    constructor_calldata: Option<Vec<FieldElement>>,
+    wait_params: ValidatedWaitParams,
 ) -> Result<AccountCreateResponse> {
     let salt = extract_or_generate_salt(salt);
-    let class_hash = {
-        let ch = match &class_hash {
-            Some(class_hash) => class_hash,
-            None => OZ_CLASS_HASH,
-        };
-        parse_number(ch)?
+    let class_hash = match &class_hash {
+        Some(class_hash) => parse_number(class_hash)?,
+        None => account_type.default_class_hash(),
     };
-    let (account_json, max_fee) = generate_account(provider, salt, class_hash).await?;
+    let (account_json, max_fee) = generate_account(
+        provider,
+        salt,
+        account_type,
+        class_hash,
+        constructor_calldata.clone(),
+    )
+    .await?;
 
     let address = parse_number(
         account_json["address"]
@@ -78,68 +115,182 @@ pub async fn create(
                 .as_str()
                 .ok_or_else(|| anyhow!("Invalid private_key"))?,
         )?;
-        create_to_keystore(private_key, salt, class_hash, keystore, &account_path)?;
+        create_to_keystore(
+            private_key,
+            salt,
+            account_type,
+            class_hash,
+            constructor_calldata.as_deref(),
+            keystore,
+            &account_path,
+        )?;
     }
 
     if add_profile {
         let config = CastConfig {
-            rpc_url: rpc_url.into(),
+            rpc_urls: rpc_urls.to_vec(),
             account: account.into(),
-            accounts_file: accounts_file.into(),
+            accounts_files: vec![accounts_file.clone()],
             keystore: keystore.into(),
+            ..Default::default()
         };
-        add_created_profile_to_configuration(&path_to_scarb_toml, &config)?;
+        add_created_profile_to_configuration(&path_to_scarb_toml, &config, overwrite)?;
     }
 
+    let deployed = account_json["deployed"] != json!(false);
+    let funded = fund_from_faucet
+        && !deployed
+        && match fund_account_from_faucet(
+            provider,
+            chain_id,
+            faucets,
+            address,
+            max_fee,
+            wait_params,
+        )
+        .await
+        {
+            Ok(()) => true,
+            Err(error) => {
+                eprintln!("Warning: Failed to fund the account from the faucet, prefund it manually: {error:#}");
+                false
+            }
+        };
+
     Ok(AccountCreateResponse {
         address,
         max_fee,
+        salt,
         add_profile: if add_profile {
             "Profile successfully added to Scarb.toml".to_string()
         } else {
             "--add-profile flag was not set. No profile added to Scarb.toml".to_string()
         },
-        message: if account_json["deployed"] == json!(false) {
-            "Account successfully created. Prefund generated address with at least <max_fee> tokens. It is good to send more in the case of higher demand.".to_string()
-        } else {
+        message: if deployed {
             "Account already deployed".to_string()
+        } else if funded {
+            "Account successfully created and funded from the faucet. It can be deployed with `account deploy`".to_string()
+        } else {
+            "Account successfully created. Prefund generated address with at least <max_fee> tokens. It is good to send more in the case of higher demand.".to_string()
         },
     })
 }
 
+/// Faucet of the network from `[tool.sncast.faucets]`, or the built-in one
+fn faucet_url(chain_id: FieldElement, faucets: &[(String, String)]) -> Result<String> {
+    let network_name = chain_id_to_network_name(chain_id);
+    faucets
+        .iter()
+        .find(|(network, _)| *network == network_name)
+        .map(|(_, url)| url.clone())
+        .or_else(|| {
+            FAUCET_URLS
+                .iter()
+                .find(|(network, _)| *network == network_name)
+                .map(|(_, url)| (*url).to_string())
+        })
+        .ok_or_else(|| anyhow!("No faucet is known for network {network_name}"))
+}
+
+/// Requests funds for the address from the faucet of the network and waits until its ETH balance covers `amount`
+async fn fund_account_from_faucet(
+    provider: &JsonRpcClient<RetryTransport>,
+    chain_id: FieldElement,
+    faucets: &[(String, String)],
+    address: FieldElement,
+    amount: u64,
+    wait_params: ValidatedWaitParams,
+) -> Result<()> {
+    let faucet_url = faucet_url(chain_id, faucets)?;
+
+    let response = reqwest::Client::new()
+        .post(faucet_url.as_str())
+        .header("Content-Type", "application/json")
+        .body(json!({ "address": format!("{address:#x}") }).to_string())
+        .send()
+        .await
+        .with_context(|| format!("Failed to send request to the faucet at {faucet_url}"))?;
+    if !response.status().is_success() {
+        bail!("Faucet responded with status {}", response.status());
+    }
+
+    let token = FieldElement::from_hex_be(ETH_ADDRESS)?;
+    let amount = FieldElement::from(amount);
+    for i in (1..=wait_params.get_retries()).rev() {
+        let balance = balance(address, token, provider, &BlockId::Tag(BlockTag::Pending)).await?;
+        if balance.balance_high != FieldElement::ZERO
+            || (balance.balance_low != FieldElement::ZERO && balance.balance_low >= amount)
+        {
+            return Ok(());
+        }
+
+        eprintln!(
+            "Waiting for the funds from the faucet ({}s left until timeout)",
+            wait_params.remaining_time(i)
+        );
+        sleep(Duration::from_secs(wait_params.get_retry_interval().into())).await;
+    }
+
+    bail!(
+        "Funds from the faucet did not arrive within {}s",
+        wait_params.get_timeout()
+    )
+}
+
 async fn generate_account(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<RetryTransport>,
     salt: FieldElement,
+    account_type: AccountType,
     class_hash: FieldElement,
+    constructor_calldata: Option<Vec<FieldElement>>,
 ) -> Result<(serde_json::Value, u64)> {
     let private_key = SigningKey::from_random();
 
     let address: FieldElement = get_contract_address(
         salt,
-        class_hash,
-        &[private_key.verifying_key().scalar()],
+        account_type.deployment_class_hash(class_hash),
+        &constructor_calldata.clone().unwrap_or_else(|| {
+            account_type.constructor_calldata(private_key.verifying_key().scalar())
+        }),
         FieldElement::ZERO,
     );
 
-    let account_json =
+    let mut account_json =
         prepare_account_json(&private_key, address, false, Some(class_hash), Some(salt));
+    account_json["type"] = json!(account_type);
+    if let Some(constructor_calldata) = &constructor_calldata {
+        account_json["constructor_calldata"] = json!(format_calldata(constructor_calldata));
+    }
 
-    let max_fee = get_account_deployment_fee(&private_key, class_hash, salt, provider)
-        .await?
-        .overall_fee;
+    let max_fee = get_account_deployment_fee(
+        &private_key,
+        account_type,
+        class_hash,
+        salt,
+        constructor_calldata,
+        provider,
+    )
+    .await?
+    .overall_fee;
 
     Ok((account_json, max_fee))
 }
 
 async fn get_account_deployment_fee(
     private_key: &SigningKey,
+    account_type: AccountType,
     class_hash: FieldElement,
     salt: FieldElement,
-    provider: &JsonRpcClient<HttpTransport>,
+    constructor_calldata: Option<Vec<FieldElement>>,
+    provider: &JsonRpcClient<RetryTransport>,
 ) -> Result<FeeEstimate> {
     let signer = LocalWallet::from_signing_key(private_key.clone());
     let chain_id = get_chain_id(provider).await?;
-    let factory = OpenZeppelinAccountFactory::new(class_hash, chain_id, signer, provider).await?;
+    let mut factory =
+        CastAccountFactory::new(account_type, class_hash, chain_id, signer, provider).await?;
+    if let Some(constructor_calldata) = constructor_calldata {
+        factory = factory.with_constructor_calldata(constructor_calldata);
+    }
     let deployment = factory.deploy(salt);
 
     let fee_estimate = deployment.estimate_fee().await;
@@ -160,7 +311,9 @@ async fn get_account_deployment_fee(
 fn create_to_keystore(
     private_key: FieldElement,
     salt: FieldElement,
+    account_type: AccountType,
     class_hash: FieldElement,
+    constructor_calldata: Option<&[FieldElement]>,
     keystore_path: &Utf8PathBuf,
     account_path: &Utf8PathBuf,
 ) -> Result<()> {
@@ -174,21 +327,49 @@ fn create_to_keystore(
     let private_key = SigningKey::from_secret_scalar(private_key);
     private_key.save_as_keystore(keystore_path, &password)?;
 
-    let oz_account_json = json!({
-        "version": 1,
-        "variant": {
-            "type": "open_zeppelin",
+    let public_key = format!("{:#x}", private_key.verifying_key().scalar());
+    // starkli names the key of argent accounts after the owner, which is the only signer without a guardian,
+    // and keeps the account class of braavos accounts apart from the base class they are deployed with
+    let variant = match account_type {
+        AccountType::Oz => json!({
+            "type": account_type.starkli_variant(),
             "version": 1,
-            "public_key": format!("{:#x}", private_key.verifying_key().scalar()),
-        },
+            "public_key": public_key,
+        }),
+        AccountType::Argent => json!({
+            "type": account_type.starkli_variant(),
+            "version": 1,
+            "owner": public_key,
+            "guardian": "0x0",
+        }),
+        AccountType::Braavos => json!({
+            "type": account_type.starkli_variant(),
+            "version": 1,
+            "implementation": format!("{class_hash:#x}"),
+            "multisig": { "status": "off" },
+            "signers": [{ "type": "stark", "public_key": public_key }],
+        }),
+    };
+    let deployment_class_hash = account_type.deployment_class_hash(class_hash);
+    let mut account_json = json!({
+        "version": 1,
+        "variant": variant,
         "deployment": {
             "status": "undeployed",
-            "class_hash": format!("{class_hash:#x}"),
+            "class_hash": format!("{deployment_class_hash:#x}"),
             "salt": format!("{salt:#x}"),
         }
     });
+    if let Some(constructor_calldata) = constructor_calldata {
+        account_json["deployment"]["constructor_calldata"] =
+            json!(format_calldata(constructor_calldata));
+    }
+
+    write_account_to_file(&account_json, account_path)
+}
 
-    write_account_to_file(&oz_account_json, account_path)
+fn format_calldata(calldata: &[FieldElement]) -> Vec<String> {
+    calldata.iter().map(|felt| format!("{felt:#x}")).collect()
 }
 
 fn write_account_to_file(
@@ -202,3 +383,81 @@ fn write_account_to_file(
     )?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{faucet_url, fund_account_from_faucet};
+    use crate::helpers::mock_server::MockServer;
+    use crate::{get_provider, parse_chain_id, ValidatedWaitParams};
+    use starknet::core::types::FieldElement;
+
+    #[test]
+    fn test_faucet_url_from_config() {
+        let chain_id = parse_chain_id("SN_GOERLI").unwrap();
+        let faucets = vec![(
+            String::from("alpha-goerli"),
+            String::from("http://127.0.0.1:5055/faucet"),
+        )];
+
+        assert_eq!(
+            faucet_url(chain_id, &faucets).unwrap(),
+            "http://127.0.0.1:5055/faucet"
+        );
+        assert_eq!(
+            faucet_url(chain_id, &[]).unwrap(),
+            "https://faucet.goerli.starknet.io/api/v1/faucet"
+        );
+    }
+
+    #[test]
+    fn test_faucet_url_unknown_network() {
+        let error = faucet_url(parse_chain_id("MOCK_CHAIN").unwrap(), &[]).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "No faucet is known for network MOCK_CHAIN"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fund_account_from_faucet() {
+        let server = MockServer::start(vec![
+            ("/faucet", vec!["{}"]),
+            (
+                "/rpc",
+                vec![
+                    r#"{"jsonrpc": "2.0", "id": 1, "result": ["0x0", "0x0"]}"#,
+                    r#"{"jsonrpc": "2.0", "id": 1, "result": ["0x3e8", "0x0"]}"#,
+                ],
+            ),
+        ]);
+        let provider = get_provider(&format!("{}/rpc", server.url()), 0, 0).unwrap();
+        let faucets = vec![(
+            String::from("MOCK_CHAIN"),
+            format!("{}/faucet", server.url()),
+        )];
+
+        fund_account_from_faucet(
+            &provider,
+            parse_chain_id("MOCK_CHAIN").unwrap(),
+            &faucets,
+            FieldElement::from_hex_be("0x123").unwrap(),
+            1000,
+            ValidatedWaitParams::new(1, 5).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/faucet");
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["address"], "0x123");
+        assert_eq!(
+            requests
+                .iter()
+                .filter(|request| request.path == "/rpc")
+                .count(),
+            2
+        );
+    }
+}
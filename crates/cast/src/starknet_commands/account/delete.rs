@@ -1,7 +1,7 @@
+use crate::helpers::response_structs::AccountDeleteResponse;
+use crate::helpers::scarb_utils::get_scarb_manifest;
 use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
-use cast::helpers::response_structs::AccountDeleteResponse;
-use cast::helpers::scarb_utils::get_scarb_manifest;
 use clap::Args;
 use promptly::prompt;
 use serde_json::Map;
@@ -1,26 +1,27 @@
+use crate::helpers::abi::{parse_abi, validate_constructor_calldata};
+use crate::helpers::account_factory::{AccountType, CastAccountFactory};
+use crate::helpers::constants::{FEE_ESTIMATION_FAILED_MESSAGE, KEYSTORE_PASSWORD_ENV_VAR};
+use crate::helpers::retry_transport::RetryTransport;
 use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
-use cast::helpers::constants::{KEYSTORE_PASSWORD_ENV_VAR, OZ_CLASS_HASH};
 use clap::Args;
 use serde_json::Map;
-use starknet::accounts::AccountFactoryError;
-use starknet::accounts::{AccountFactory, OpenZeppelinAccountFactory};
+use starknet::accounts::{AccountDeployment, AccountFactory, AccountFactoryError};
 use starknet::core::types::BlockTag::Pending;
-use starknet::core::types::{BlockId, FieldElement, StarknetError};
+use starknet::core::types::{BlockId, ContractClass, FieldElement, StarknetError};
 use starknet::core::utils::get_contract_address;
-use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::ProviderError::{self};
 use starknet::providers::{
     JsonRpcClient, MaybeUnknownErrorCode, Provider, StarknetErrorWithMessage,
 };
 use starknet::signers::{LocalWallet, SigningKey};
 
-use cast::{
-    account_file_exists, chain_id_to_network_name, get_keystore_password, handle_rpc_error,
-    handle_wait_for_tx, parse_number,
+use crate::{
+    account_file_exists, apply_fee_multiplier, chain_id_to_network_name, get_keystore_password,
+    handle_rpc_error, handle_wait_for_tx, parse_number, WaitForTx,
 };
 
-use cast::helpers::response_structs::InvokeResponse;
+use crate::helpers::response_structs::InvokeResponse;
 
 #[derive(Args, Debug)]
 #[command(about = "Deploy an account to the Starknet")]
@@ -29,24 +30,33 @@ pub struct Deploy {
     #[clap(short, long)]
     pub name: Option<String>,
 
-    /// Max fee for the transaction
+    /// Max fee for the transaction. If not provided, max fee will be automatically estimated
     #[clap(short, long)]
-    pub max_fee: FieldElement,
+    pub max_fee: Option<FieldElement>,
 
-    /// Custom open zeppelin contract class hash of declared contract
+    /// Custom class hash of the declared account contract; defaults to the class hash
+    /// saved when the account was created
     #[clap(short, long)]
     pub class_hash: Option<String>,
+
+    /// Calldata for the account constructor (list of hex or decimal felts), used instead of the
+    /// calldata saved by `account create` or the default calldata of the account type. As it determines
+    /// the account address, the saved address has to be computed with the same calldata
+    #[clap(long, num_args = 1..)]
+    pub constructor_calldata: Option<Vec<FieldElement>>,
 }
 
 #[allow(clippy::too_many_arguments)]
 pub async fn deploy(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<RetryTransport>,
     accounts_file: Utf8PathBuf,
     name: String,
     chain_id: FieldElement,
-    max_fee: FieldElement,
-    wait: bool,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    wait_config: WaitForTx,
     class_hash: Option<String>,
+    constructor_calldata: Option<Vec<FieldElement>>,
     keystore_path: Option<Utf8PathBuf>,
     account_path: Option<Utf8PathBuf>,
 ) -> Result<InvokeResponse> {
@@ -59,9 +69,11 @@ pub async fn deploy(
             provider,
             chain_id,
             max_fee,
-            wait,
+            fee_multiplier,
+            wait_config,
             keystore_path_,
             account_path_,
+            constructor_calldata,
         )
         .await
     } else {
@@ -75,20 +87,24 @@ pub async fn deploy(
             name,
             chain_id,
             max_fee,
-            wait,
+            fee_multiplier,
+            wait_config,
             class_hash,
+            constructor_calldata,
         )
         .await
     }
 }
 
 async fn deploy_from_keystore(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<RetryTransport>,
     chain_id: FieldElement,
-    max_fee: FieldElement,
-    wait: bool,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    wait_config: WaitForTx,
     keystore_path: Utf8PathBuf,
     account_path: Utf8PathBuf,
+    constructor_calldata: Option<Vec<FieldElement>>,
 ) -> Result<InvokeResponse> {
     let contents =
         std::fs::read_to_string(account_path.clone()).context("Couldn't read account file")?;
@@ -114,12 +130,23 @@ async fn deploy_from_keystore(
             .and_then(serde_json::Value::as_str)
             .ok_or_else(|| anyhow::anyhow!("Failed to get salt from account JSON file"))?,
     )?;
-    let oz_class_hash = FieldElement::from_hex_be(
-        deployment
-            .get("class_hash")
+    // braavos accounts keep their account class in the variant, as they are deployed with the base class
+    let class_hash = FieldElement::from_hex_be(
+        items
+            .get("variant")
+            .and_then(|variant| variant.get("implementation"))
+            .or_else(|| deployment.get("class_hash"))
             .and_then(serde_json::Value::as_str)
             .ok_or_else(|| anyhow::anyhow!("Failed to get class_hash from account JSON file"))?,
     )?;
+    let account_type = match items
+        .get("variant")
+        .and_then(|variant| variant.get("type"))
+        .and_then(serde_json::Value::as_str)
+    {
+        Some(variant) => AccountType::from_starkli_variant(variant)?,
+        None => AccountType::default(),
+    };
 
     if !keystore_path.exists() {
         bail!("Couldn't read keystore file");
@@ -128,10 +155,20 @@ async fn deploy_from_keystore(
         keystore_path,
         get_keystore_password(KEYSTORE_PASSWORD_ENV_VAR)?.as_str(),
     )?;
+    let constructor_calldata = match constructor_calldata {
+        Some(constructor_calldata) => Some(constructor_calldata),
+        None => saved_constructor_calldata(deployment)?,
+    };
+
     let public_key: FieldElement = {
+        // argent accounts are controlled by the owner key, braavos accounts by their stark signer
         let pk = items
             .get("variant")
-            .and_then(|v| v.get("public_key"))
+            .and_then(|v| {
+                v.get("public_key")
+                    .or_else(|| v.get("owner"))
+                    .or_else(|| v.pointer("/signers/0/public_key"))
+            })
             .and_then(serde_json::Value::as_str)
             .ok_or_else(|| anyhow::anyhow!("No public_key in account JSON file"))?;
         parse_number(pk)?
@@ -142,10 +179,23 @@ async fn deploy_from_keystore(
 
     let address = get_contract_address(
         salt,
-        oz_class_hash,
-        &[private_key.verifying_key().scalar()],
+        account_type.deployment_class_hash(class_hash),
+        &constructor_calldata
+            .clone()
+            .unwrap_or_else(|| account_type.constructor_calldata(public_key)),
         FieldElement::ZERO,
     );
+    if let Some(saved_address) = deployment
+        .get("address")
+        .and_then(serde_json::Value::as_str)
+    {
+        let saved_address = parse_number(saved_address).context("Couldn't parse address")?;
+        if address != saved_address {
+            bail!(
+                "Account would be deployed at address {address:#x}, which differs from its address {saved_address:#x} in the account file"
+            );
+        }
+    }
 
     let result = if provider
         .get_class_hash_at(BlockId::Tag(Pending), address)
@@ -156,14 +206,17 @@ async fn deploy_from_keystore(
             transaction_hash: FieldElement::ZERO,
         }
     } else {
-        deploy_oz_account(
+        deploy_account(
             provider,
-            oz_class_hash,
+            account_type,
+            class_hash,
             private_key,
             salt,
             chain_id,
             max_fee,
-            wait,
+            fee_multiplier,
+            wait_config,
+            constructor_calldata,
         )
         .await?
     };
@@ -184,13 +237,15 @@ async fn deploy_from_keystore(
 }
 
 async fn deploy_from_accounts_file(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<RetryTransport>,
     accounts_file: Utf8PathBuf,
     name: String,
     chain_id: FieldElement,
-    max_fee: FieldElement,
-    wait: bool,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    wait_config: WaitForTx,
     class_hash: Option<String>,
+    constructor_calldata: Option<Vec<FieldElement>>,
 ) -> Result<InvokeResponse> {
     let network_name = chain_id_to_network_name(chain_id);
 
@@ -217,33 +272,65 @@ async fn deploy_from_accounts_file(
         .context("Couldn't parse private key")?,
     );
 
-    let oz_class_hash = {
-        if let Some(class_hash_) = &class_hash {
-            class_hash_.as_str()
-        } else if let Some(class_hash_) = account
+    // accounts created before account types were introduced are OpenZeppelin accounts
+    let account_type: AccountType = match account.get("type") {
+        Some(account_type) => serde_json::from_value(account_type.clone())
+            .with_context(|| format!("Unsupported account type {account_type} in accounts file"))?,
+        None => AccountType::default(),
+    };
+    let class_hash = if let Some(class_hash_) = class_hash.as_deref().or_else(|| {
+        account
             .get("class_hash")
             .and_then(serde_json::Value::as_str)
-        {
-            class_hash_
-        } else {
-            OZ_CLASS_HASH
-        }
+    }) {
+        parse_number(class_hash_).context("Couldn't parse account class hash")?
+    } else {
+        account_type.default_class_hash()
+    };
+    let salt = parse_number(
+        account
+            .get("salt")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow!("Couldn't get salt from accounts file"))?,
+    )
+    .context("Couldn't parse salt")?;
+    let constructor_calldata = match constructor_calldata {
+        Some(constructor_calldata) => Some(constructor_calldata),
+        None => saved_constructor_calldata(account)?,
     };
 
-    let result = deploy_oz_account(
-        provider,
-        parse_number(oz_class_hash).context("Couldn't parse account class hash")?,
-        private_key,
-        parse_number(
+    if let Some(constructor_calldata) = &constructor_calldata {
+        let saved_address = parse_number(
             account
-                .get("salt")
+                .get("address")
                 .and_then(serde_json::Value::as_str)
-                .ok_or_else(|| anyhow!("Couldn't get salt from accounts file"))?,
+                .ok_or_else(|| anyhow!("Couldn't get address from accounts file"))?,
         )
-        .context("Couldn't parse salt")?,
+        .context("Couldn't parse address")?;
+        let address = get_contract_address(
+            salt,
+            account_type.deployment_class_hash(class_hash),
+            constructor_calldata,
+            FieldElement::ZERO,
+        );
+        if address != saved_address {
+            bail!(
+                "Account {name} would be deployed at address {address:#x} with the constructor calldata, which differs from its address {saved_address:#x} in the accounts file"
+            );
+        }
+    }
+
+    let result = deploy_account(
+        provider,
+        account_type,
+        class_hash,
+        private_key,
+        salt,
         chain_id,
         max_fee,
-        wait,
+        fee_multiplier,
+        wait_config,
+        constructor_calldata,
     )
     .await?;
 
@@ -254,24 +341,64 @@ async fn deploy_from_accounts_file(
     Ok(result)
 }
 
-async fn deploy_oz_account(
-    provider: &JsonRpcClient<HttpTransport>,
-    oz_class_hash: FieldElement,
+/// Reads the constructor calldata saved by `account create --constructor-calldata`, if any
+fn saved_constructor_calldata(account: &serde_json::Value) -> Result<Option<Vec<FieldElement>>> {
+    account
+        .get("constructor_calldata")
+        .map(|constructor_calldata| {
+            constructor_calldata
+                .as_array()
+                .ok_or_else(|| anyhow!("Couldn't get constructor calldata from account file"))?
+                .iter()
+                .map(|felt| {
+                    felt.as_str()
+                        .ok_or_else(|| {
+                            anyhow!("Couldn't get constructor calldata from account file")
+                        })
+                        .and_then(parse_number)
+                })
+                .collect::<Result<Vec<_>>>()
+                .context("Couldn't parse constructor calldata")
+        })
+        .transpose()
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn deploy_account(
+    provider: &JsonRpcClient<RetryTransport>,
+    account_type: AccountType,
+    class_hash: FieldElement,
     private_key: SigningKey,
     salt: FieldElement,
     chain_id: FieldElement,
-    max_fee: FieldElement,
-    wait: bool,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    wait_config: WaitForTx,
+    constructor_calldata: Option<Vec<FieldElement>>,
 ) -> Result<InvokeResponse> {
-    let factory = OpenZeppelinAccountFactory::new(
-        oz_class_hash,
+    let mut factory = CastAccountFactory::new(
+        account_type,
+        class_hash,
         chain_id,
         LocalWallet::from_signing_key(private_key),
         provider,
     )
     .await?;
+    if let Some(constructor_calldata) = constructor_calldata {
+        validate_account_constructor_calldata(
+            provider,
+            account_type.deployment_class_hash(class_hash),
+            &constructor_calldata,
+        )
+        .await?;
+        factory = factory.with_constructor_calldata(constructor_calldata);
+    }
 
     let deployment = factory.deploy(salt);
+    let max_fee = match max_fee {
+        Some(max_fee) => max_fee,
+        None => estimate_max_fee(&deployment, fee_multiplier, class_hash).await?,
+    };
     let result = deployment.max_fee(max_fee).send().await;
 
     match result {
@@ -281,7 +408,7 @@ async fn deploy_oz_account(
                 message: _,
             }) => Err(anyhow!(
                 "Provided class hash {:#x} does not exist",
-                oz_class_hash,
+                class_hash,
             )),
             _ => handle_rpc_error(error),
         },
@@ -294,7 +421,7 @@ async fn deploy_oz_account(
                 provider,
                 result.transaction_hash,
                 return_value.clone(),
-                wait,
+                wait_config,
             )
             .await
             {
@@ -305,3 +432,55 @@ async fn deploy_oz_account(
         }
     }
 }
+
+/// Checks the calldata against the constructor in the ABI of the account class.
+/// Cairo 0 classes are not checked, as their ABI is not available
+async fn validate_account_constructor_calldata(
+    provider: &JsonRpcClient<RetryTransport>,
+    class_hash: FieldElement,
+    constructor_calldata: &[FieldElement],
+) -> Result<()> {
+    match provider.get_class(BlockId::Tag(Pending), class_hash).await {
+        Ok(ContractClass::Sierra(class)) => {
+            validate_constructor_calldata(&parse_abi(&class.abi)?, constructor_calldata)
+        }
+        Ok(ContractClass::Legacy(_)) => Ok(()),
+        Err(ProviderError::StarknetError(StarknetErrorWithMessage {
+            code: MaybeUnknownErrorCode::Known(StarknetError::ClassHashNotFound),
+            ..
+        })) => bail!("Provided class hash {class_hash:#x} does not exist"),
+        Err(error) => handle_rpc_error(error),
+    }
+}
+
+/// Deploy account transactions are estimated with their own transaction type, as the account
+/// contract doesn't exist yet and can't be called
+async fn estimate_max_fee(
+    deployment: &AccountDeployment<
+        '_,
+        CastAccountFactory<LocalWallet, &JsonRpcClient<RetryTransport>>,
+    >,
+    fee_multiplier: f64,
+    class_hash: FieldElement,
+) -> Result<FieldElement> {
+    let fee_estimate = match deployment.estimate_fee().await {
+        Ok(fee_estimate) => fee_estimate,
+        Err(AccountFactoryError::Provider(ProviderError::StarknetError(
+            StarknetErrorWithMessage {
+                code: MaybeUnknownErrorCode::Known(StarknetError::ClassHashNotFound),
+                ..
+            },
+        ))) => bail!("Provided class hash {class_hash:#x} does not exist"),
+        Err(AccountFactoryError::Provider(error)) => {
+            return handle_rpc_error(error).context(FEE_ESTIMATION_FAILED_MESSAGE)
+        }
+        Err(_) => bail!(FEE_ESTIMATION_FAILED_MESSAGE),
+    };
+
+    let max_fee = apply_fee_multiplier(fee_estimate.overall_fee, fee_multiplier);
+    eprintln!(
+        "Estimated fee: {}, using max fee: {max_fee} (fee multiplier: {fee_multiplier})",
+        fee_estimate.overall_fee
+    );
+    Ok(FieldElement::from(max_fee))
+}
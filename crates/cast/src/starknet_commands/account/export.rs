@@ -0,0 +1,77 @@
+use crate::helpers::response_structs::AccountExportResponse;
+use crate::{account_file_exists, find_account, raise_if_empty};
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use promptly::prompt;
+use serde_json::json;
+
+#[derive(Args, Debug)]
+#[command(about = "Export account information from the accounts files")]
+pub struct Export {
+    /// Name of the account to be exported
+    #[clap(short, long)]
+    pub name: String,
+
+    /// Network where the account exists; defaults to network of rpc node
+    #[clap(long)]
+    pub network: Option<String>,
+
+    /// If passed, the private key of the account is exported as well, after a confirmation
+    #[clap(long)]
+    pub include_private_key: bool,
+
+    /// Path to a file to which the account is written in the accounts file format, e.g. as a backup
+    #[clap(long)]
+    pub output_file: Option<Utf8PathBuf>,
+}
+
+pub fn export(
+    accounts_files: &[Utf8PathBuf],
+    network_name: &str,
+    export: &Export,
+) -> Result<AccountExportResponse> {
+    let name = &export.name;
+    raise_if_empty(name, "Account name")?;
+    if accounts_files.iter().all(|path| !path.exists()) {
+        account_file_exists(&accounts_files.first().cloned().unwrap_or_default())?;
+    }
+    let (account, _) = find_account(name, network_name, accounts_files)?
+        .ok_or_else(|| anyhow!("Account {name} not found under network {network_name}"))?;
+
+    if export.include_private_key {
+        let prompt_text = format!(
+            "Do you want to export the private key of the account {name} from network {network_name}? (Y/n)"
+        );
+        let input: String = prompt(prompt_text)?;
+        if !input.starts_with('Y') {
+            bail!("Export aborted");
+        }
+    }
+
+    if let Some(output_file) = &export.output_file {
+        if output_file.exists() {
+            bail!("File {output_file} already exists");
+        }
+        let mut account_json = serde_json::to_value(&account)?;
+        if !export.include_private_key {
+            account_json
+                .as_object_mut()
+                .expect("Account is serialized to an object")
+                .remove("private_key");
+        }
+        let contents = json!({ network_name: { name: account_json } });
+        std::fs::write(output_file, serde_json::to_string_pretty(&contents)?)
+            .with_context(|| format!("Failed to write account to {output_file}"))?;
+    }
+
+    Ok(AccountExportResponse {
+        address: account.address,
+        public_key: account.public_key,
+        class_hash: account.class_hash,
+        salt: account.salt,
+        deployed: account.deployed,
+        private_key: export.include_private_key.then_some(account.private_key),
+        output_file: export.output_file.clone(),
+    })
+}
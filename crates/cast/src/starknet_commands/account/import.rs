@@ -0,0 +1,262 @@
+use crate::helpers::account_factory::AccountType;
+use crate::helpers::constants::{CONTRACT_NOT_FOUND_ERROR_CODE, KEYSTORE_PASSWORD_ENV_VAR};
+use crate::helpers::response_structs::AccountImportResponse;
+use crate::helpers::retry_transport::RetryTransport;
+use crate::starknet_commands::account::{
+    prepare_account_json, read_accounts_file, write_accounts_file,
+};
+use crate::{
+    get_keystore_password, get_network_name, handle_rpc_error, parse_number, RpcErrorWithCode,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use serde_json::Value;
+use starknet::core::types::BlockTag::Pending;
+use starknet::core::types::{BlockId, FieldElement};
+use starknet::core::utils::get_contract_address;
+use starknet::providers::{JsonRpcClient, Provider};
+use starknet::signers::SigningKey;
+
+#[derive(Args, Debug)]
+#[command(
+    about = "Import an account created with starkli (keystore and account file) to the accounts file"
+)]
+pub struct Import {
+    /// Name under which the account will be saved in the accounts file
+    #[clap(short, long)]
+    pub name: String,
+
+    /// Path to the starkli keystore file holding the encrypted private key
+    #[clap(long, visible_alias = "keystore")]
+    pub keystore_path: Utf8PathBuf,
+
+    /// Path to the starkli account JSON file
+    #[clap(long, visible_alias = "account-json")]
+    pub account_path: Utf8PathBuf,
+
+    /// Network (e.g. alpha-goerli) under which the account will be saved;
+    /// if not passed, the network of the RPC provider is used
+    #[clap(long)]
+    pub network: Option<String>,
+
+    /// If passed, an existing account with the same name will be overwritten
+    #[clap(long)]
+    pub overwrite: bool,
+}
+
+struct StarkliAccount {
+    account_type: AccountType,
+    public_key: FieldElement,
+    class_hash: FieldElement,
+    address: FieldElement,
+    salt: Option<FieldElement>,
+    deployed: bool,
+}
+
+pub async fn import(
+    account: &str,
+    accounts_file: &Utf8PathBuf,
+    provider: &JsonRpcClient<RetryTransport>,
+    import: &Import,
+) -> Result<AccountImportResponse> {
+    let mut starkli_account = read_starkli_account(&import.account_path)?;
+
+    if !import.keystore_path.exists() {
+        bail!("Keystore file {} does not exist", import.keystore_path);
+    }
+    let private_key = SigningKey::from_keystore(
+        &import.keystore_path,
+        get_keystore_password(KEYSTORE_PASSWORD_ENV_VAR)?.as_str(),
+    )
+    .with_context(|| format!("Failed to decrypt keystore {}", import.keystore_path))?;
+    if private_key.verifying_key().scalar() != starkli_account.public_key {
+        bail!("Public key from the account file does not match the private key from the keystore");
+    }
+
+    let network_name = match &import.network {
+        Some(network) => network.clone(),
+        None => get_network_name(provider).await.context(
+            "Failed to get the network from the RPC provider, pass it with --network instead",
+        )?,
+    };
+
+    match provider
+        .get_class_hash_at(BlockId::Tag(Pending), starkli_account.address)
+        .await
+    {
+        Ok(class_hash) if class_hash != starkli_account.class_hash => bail!(
+            "Class hash of the contract deployed at {:#x} ({class_hash:#x}) does not match the class hash from the account file ({:#x})",
+            starkli_account.address,
+            starkli_account.class_hash
+        ),
+        Ok(_) => starkli_account.deployed = true,
+        Err(error) => match handle_rpc_error::<()>(error) {
+            Err(error)
+                if error
+                    .downcast_ref::<RpcErrorWithCode>()
+                    .is_some_and(|error| error.code == CONTRACT_NOT_FOUND_ERROR_CODE) =>
+            {
+                if starkli_account.deployed {
+                    bail!(
+                        "Account file marks the account as deployed, but there is no contract at address {:#x}",
+                        starkli_account.address
+                    );
+                }
+            }
+            Err(error) => eprintln!(
+                "Warning: Could not verify the account against the network: {error:#}"
+            ),
+            Ok(()) => {}
+        },
+    }
+
+    let mut items = read_accounts_file(accounts_file)?;
+    if !items[&network_name][account].is_null() && !import.overwrite {
+        bail!(
+            "Account with name {account} already exists in network {network_name}, pass --overwrite to replace it"
+        );
+    }
+    let mut account_json = prepare_account_json(
+        &private_key,
+        starkli_account.address,
+        starkli_account.deployed,
+        Some(starkli_account.class_hash),
+        starkli_account.salt,
+    );
+    account_json["type"] = serde_json::json!(starkli_account.account_type);
+    items[&network_name][account] = account_json;
+    write_accounts_file(accounts_file, &items)?;
+
+    Ok(AccountImportResponse {
+        address: starkli_account.address,
+        network: network_name,
+    })
+}
+
+fn read_starkli_account(path: &Utf8PathBuf) -> Result<StarkliAccount> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read account file {path}"))?;
+    let account: Value = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse account file {path}"))?;
+
+    let get_field = |field: &str, value: Option<&Value>| -> Result<FieldElement> {
+        let value = value
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("No {field} in account file {path}"))?;
+        parse_number(value).with_context(|| format!("Failed to parse {field} in account file"))
+    };
+
+    let account_type = match account.pointer("/variant/type").and_then(Value::as_str) {
+        Some(variant) => AccountType::from_starkli_variant(variant)?,
+        None => AccountType::default(),
+    };
+    // argent accounts are controlled by the owner key, braavos accounts by their stark signer
+    let public_key = get_field(
+        "public_key",
+        account
+            .pointer("/variant/public_key")
+            .or_else(|| account.pointer("/variant/owner"))
+            .or_else(|| account.pointer("/variant/signers/0/public_key")),
+    )?;
+    // braavos accounts keep their account class in the variant, as they are deployed with the base class
+    let class_hash = get_field(
+        "class_hash",
+        account
+            .pointer("/variant/implementation")
+            .or_else(|| account.pointer("/deployment/class_hash")),
+    )?;
+    let status = account
+        .pointer("/deployment/status")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("No deployment status in account file {path}"))?;
+
+    match status {
+        "deployed" => Ok(StarkliAccount {
+            account_type,
+            public_key,
+            class_hash,
+            address: get_field("address", account.pointer("/deployment/address"))?,
+            salt: None,
+            deployed: true,
+        }),
+        "undeployed" => {
+            let salt = get_field("salt", account.pointer("/deployment/salt"))?;
+            Ok(StarkliAccount {
+                account_type,
+                public_key,
+                class_hash,
+                address: get_contract_address(
+                    salt,
+                    account_type.deployment_class_hash(class_hash),
+                    &account_type.constructor_calldata(public_key),
+                    FieldElement::ZERO,
+                ),
+                salt: Some(salt),
+                deployed: false,
+            })
+        }
+        _ => bail!("Unknown deployment status {status} in account file {path}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_starkli_account;
+    use crate::helpers::account_factory::AccountType;
+    use crate::helpers::constants::{BRAAVOS_BASE_ACCOUNT_CLASS_HASH, BRAAVOS_CLASS_HASH};
+    use camino::Utf8PathBuf;
+    use starknet::core::types::FieldElement;
+    use starknet::core::utils::get_contract_address;
+
+    #[test]
+    fn test_read_deployed_starkli_account() {
+        let account =
+            read_starkli_account(&Utf8PathBuf::from("tests/data/keystore/my_account.json"))
+                .unwrap();
+
+        assert!(account.deployed);
+        assert_eq!(account.salt, None);
+        assert_eq!(
+            account.address,
+            FieldElement::from_hex_be(
+                "0xcce3217e4aea0ab738b55446b1b378750edfca617db549fda1ede28435206c"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read_undeployed_starkli_account() {
+        let account = read_starkli_account(&Utf8PathBuf::from(
+            "tests/data/keystore/my_account_undeployed.json",
+        ))
+        .unwrap();
+
+        assert!(!account.deployed);
+        assert!(account.salt.is_some());
+    }
+
+    #[test]
+    fn test_read_undeployed_braavos_starkli_account() {
+        let account = read_starkli_account(&Utf8PathBuf::from(
+            "tests/data/keystore/my_account_braavos_undeployed.json",
+        ))
+        .unwrap();
+
+        assert_eq!(account.account_type, AccountType::Braavos);
+        assert_eq!(
+            account.class_hash,
+            FieldElement::from_hex_be(BRAAVOS_CLASS_HASH).unwrap()
+        );
+        assert_eq!(
+            account.address,
+            get_contract_address(
+                account.salt.unwrap(),
+                FieldElement::from_hex_be(BRAAVOS_BASE_ACCOUNT_CLASS_HASH).unwrap(),
+                &[account.public_key],
+                FieldElement::ZERO,
+            )
+        );
+    }
+}
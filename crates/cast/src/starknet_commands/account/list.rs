@@ -0,0 +1,268 @@
+use crate::helpers::response_structs::{AccountListItem, AccountListResponse};
+use crate::helpers::retry_transport::RetryTransport;
+use crate::{
+    handle_rpc_error, print_command_result, print_formatted, write_to_output, ValueFormat,
+};
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use serde::Deserialize;
+use serde_json::Value;
+use starknet::core::types::{BlockId, BlockTag, FieldElement, StarknetError};
+use starknet::providers::{
+    JsonRpcClient, MaybeUnknownErrorCode, Provider, ProviderError, StarknetErrorWithMessage,
+};
+use std::collections::BTreeMap;
+
+#[derive(Args, Debug)]
+#[command(about = "List accounts from the accounts files")]
+pub struct List {
+    /// If passed, private keys of the accounts will be displayed
+    #[clap(long)]
+    pub show_private_keys: bool,
+
+    /// If passed, only accounts from this network (e.g. alpha-goerli) will be listed
+    #[clap(long)]
+    pub network: Option<String>,
+
+    /// If passed, current nonces of the accounts from the network of the RPC provider will be displayed
+    #[clap(long)]
+    pub with_nonce: bool,
+}
+
+#[derive(Deserialize)]
+struct AccountData {
+    private_key: String,
+    address: String,
+    class_hash: Option<String>,
+    deployed: Option<bool>,
+}
+
+/// Accounts defined in more than one file are listed as defined in the first one
+pub fn list(
+    accounts_files: &[Utf8PathBuf],
+    show_private_keys: bool,
+    network: Option<&str>,
+) -> Result<AccountListResponse> {
+    let mut accounts: BTreeMap<String, BTreeMap<String, AccountListItem>> = BTreeMap::new();
+
+    for accounts_file in accounts_files.iter().filter(|path| path.exists()) {
+        let contents = std::fs::read_to_string(accounts_file)
+            .with_context(|| format!("Cannot read a file {accounts_file}"))?;
+        let networks: BTreeMap<String, BTreeMap<String, AccountData>> =
+            serde_json::from_str(&contents)
+                .with_context(|| format!("Failed to parse accounts file at {accounts_file}"))?;
+
+        for (network_name, network_accounts) in networks {
+            if network.is_some_and(|network| network != network_name) {
+                continue;
+            }
+            let listed = accounts.entry(network_name).or_default();
+            for (name, account) in network_accounts {
+                listed.entry(name).or_insert_with(|| AccountListItem {
+                    address: account.address,
+                    class_hash: account.class_hash,
+                    deployed: account.deployed,
+                    private_key: show_private_keys.then_some(account.private_key),
+                    nonce: None,
+                });
+            }
+        }
+    }
+
+    Ok(AccountListResponse { accounts })
+}
+
+/// Fetches nonces of the accounts from `network_name`, which should be the network the provider is connected to;
+/// `not deployed` is set for accounts which are not marked as deployed or don't exist on the network
+pub async fn add_nonces(
+    mut response: AccountListResponse,
+    provider: &JsonRpcClient<RetryTransport>,
+    network_name: &str,
+) -> Result<AccountListResponse> {
+    let Some(accounts) = response.accounts.get_mut(network_name) else {
+        return Ok(response);
+    };
+
+    for (name, account) in accounts {
+        let nonce = if account.deployed == Some(false) {
+            None
+        } else {
+            let address = FieldElement::from_hex_be(&account.address)
+                .with_context(|| format!("Invalid address of account {name}"))?;
+            match provider
+                .get_nonce(BlockId::Tag(BlockTag::Pending), address)
+                .await
+            {
+                Ok(nonce) => Some(nonce),
+                Err(ProviderError::StarknetError(StarknetErrorWithMessage {
+                    code: MaybeUnknownErrorCode::Known(StarknetError::ContractNotFound),
+                    ..
+                })) => None,
+                Err(error) => return handle_rpc_error(error),
+            }
+        };
+        account.nonce = Some(nonce.map_or_else(
+            || String::from("not deployed"),
+            |nonce| format!("{nonce:#x}"),
+        ));
+    }
+
+    Ok(response)
+}
+
+/// Accounts are grouped by network, so they can't be displayed as flat key-value pairs like
+/// results of other commands
+pub fn print_account_list(
+    result: &Result<AccountListResponse>,
+    value_format: ValueFormat,
+    json: bool,
+) -> Result<()> {
+    let response = match result {
+        Ok(response) => response,
+        Err(_) => return print_command_result("account list", result, value_format, json),
+    };
+
+    if json {
+        return print_formatted(
+            vec![
+                ("command", Value::String(String::from("account list"))),
+                ("accounts", serde_json::to_value(&response.accounts)?),
+            ],
+            true,
+            false,
+        );
+    }
+
+    write_to_output("command: account list", false);
+    if response.accounts.is_empty() {
+        write_to_output("No accounts found", false);
+    }
+    for (network, accounts) in &response.accounts {
+        write_to_output(format!("{network}:"), false);
+        for (name, account) in accounts {
+            write_to_output(format!("  {name}:"), false);
+            write_to_output(
+                format!("    address: {}", value_format.format_str(&account.address)),
+                false,
+            );
+            if let Some(class_hash) = &account.class_hash {
+                write_to_output(
+                    format!("    class_hash: {}", value_format.format_str(class_hash)),
+                    false,
+                );
+            }
+            if let Some(deployed) = account.deployed {
+                write_to_output(format!("    deployed: {deployed}"), false);
+            }
+            if let Some(private_key) = &account.private_key {
+                write_to_output(format!("    private_key: {private_key}"), false);
+            }
+            if let Some(nonce) = &account.nonce {
+                write_to_output(
+                    format!("    nonce: {}", value_format.format_str(nonce)),
+                    false,
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::starknet_commands::account::list::list;
+    use camino::Utf8PathBuf;
+
+    #[test]
+    fn test_list_accounts() {
+        let response = list(
+            &[Utf8PathBuf::from("tests/data/accounts/accounts.json")],
+            false,
+            None,
+        )
+        .unwrap();
+
+        let accounts = response
+            .accounts
+            .get("alpha-goerli")
+            .expect("Network should be listed");
+        let user1 = accounts.get("user1").expect("Account should be listed");
+        assert!(user1.private_key.is_none());
+    }
+
+    #[test]
+    fn test_list_accounts_show_private_keys() {
+        let response = list(
+            &[Utf8PathBuf::from("tests/data/accounts/accounts.json")],
+            true,
+            None,
+        )
+        .unwrap();
+
+        assert!(response
+            .accounts
+            .values()
+            .flat_map(|accounts| accounts.values())
+            .all(|account| account.private_key.is_some()));
+    }
+
+    #[test]
+    fn test_list_multiple_accounts_files() {
+        let response = list(
+            &[
+                Utf8PathBuf::from("tests/data/accounts/accounts.json"),
+                Utf8PathBuf::from("tests/data/accounts/shared_accounts.json"),
+            ],
+            true,
+            None,
+        )
+        .unwrap();
+
+        let accounts = response
+            .accounts
+            .get("alpha-goerli")
+            .expect("Network should be listed");
+        let user1 = accounts.get("user1").expect("Account should be listed");
+        assert_eq!(
+            user1.private_key.as_deref(),
+            Some("0xffd33878eed7767e7c546ce3fc026295")
+        );
+        assert!(accounts.contains_key("shared_user"));
+    }
+
+    #[test]
+    fn test_list_accounts_filtered_by_network() {
+        let response = list(
+            &[Utf8PathBuf::from("tests/data/accounts/accounts.json")],
+            false,
+            Some("alpha-goerli"),
+        )
+        .unwrap();
+        assert_eq!(
+            response.accounts.keys().collect::<Vec<_>>(),
+            vec!["alpha-goerli"]
+        );
+
+        let response = list(
+            &[Utf8PathBuf::from("tests/data/accounts/accounts.json")],
+            false,
+            Some("nonexistent-network"),
+        )
+        .unwrap();
+        assert!(response.accounts.is_empty());
+    }
+
+    #[test]
+    fn test_list_accounts_file_does_not_exist() {
+        let response = list(
+            &[Utf8PathBuf::from("nonexistent/accounts.json")],
+            false,
+            None,
+        )
+        .unwrap();
+
+        assert!(response.accounts.is_empty());
+    }
+}
@@ -2,14 +2,15 @@ use crate::starknet_commands::account::add::Add;
 use crate::starknet_commands::account::create::Create;
 use crate::starknet_commands::account::delete::Delete;
 use crate::starknet_commands::account::deploy::Deploy;
-use anyhow::{anyhow, bail, Context, Result};
-use camino::Utf8PathBuf;
-use cast::{
+use crate::starknet_commands::account::export::Export;
+use crate::starknet_commands::account::import::Import;
+use crate::starknet_commands::account::list::List;
+use crate::{
     chain_id_to_network_name, decode_chain_id,
-    helpers::scarb_utils::{
-        get_package_tool_sncast, get_scarb_manifest, get_scarb_metadata, CastConfig,
-    },
+    helpers::scarb_utils::{get_scarb_manifest, CastConfig},
 };
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8PathBuf;
 use clap::{Args, Subcommand};
 use serde_json::json;
 use starknet::{core::types::FieldElement, signers::SigningKey};
@@ -20,6 +21,9 @@ pub mod add;
 pub mod create;
 pub mod delete;
 pub mod deploy;
+pub mod export;
+pub mod import;
+pub mod list;
 
 #[derive(Args)]
 #[command(about = "Creates and deploys an account to the Starknet")]
@@ -34,8 +38,12 @@ pub enum Commands {
     Create(Create),
     Deploy(Deploy),
     Delete(Delete),
+    List(List),
+    Import(Import),
+    Export(Export),
 }
 
+#[must_use]
 pub fn prepare_account_json(
     private_key: &SigningKey,
     address: FieldElement,
@@ -67,14 +75,7 @@ pub fn write_account_to_accounts_file(
     chain_id: FieldElement,
     account_json: serde_json::Value,
 ) -> Result<()> {
-    if !accounts_file.exists() {
-        std::fs::create_dir_all(accounts_file.clone().parent().unwrap())?;
-        std::fs::write(accounts_file.clone(), "{}")?;
-    }
-
-    let contents = std::fs::read_to_string(accounts_file.clone())?;
-    let mut items: serde_json::Value = serde_json::from_str(&contents)
-        .map_err(|_| anyhow!("Failed to parse accounts file at {}", accounts_file))?;
+    let mut items = read_accounts_file(accounts_file)?;
 
     let network_name = chain_id_to_network_name(chain_id);
 
@@ -87,82 +88,119 @@ pub fn write_account_to_accounts_file(
     }
     items[&network_name][account] = account_json;
 
+    write_accounts_file(accounts_file, &items)
+}
+
+/// Reads the accounts file, creating an empty one if it does not exist
+pub fn read_accounts_file(accounts_file: &Utf8PathBuf) -> Result<serde_json::Value> {
+    if !accounts_file.exists() {
+        std::fs::create_dir_all(accounts_file.clone().parent().unwrap())?;
+        std::fs::write(accounts_file.clone(), "{}")?;
+    }
+
+    let contents = std::fs::read_to_string(accounts_file.clone())?;
+    serde_json::from_str(&contents)
+        .map_err(|_| anyhow!("Failed to parse accounts file at {}", accounts_file))
+}
+
+pub fn write_accounts_file(accounts_file: &Utf8PathBuf, items: &serde_json::Value) -> Result<()> {
     std::fs::write(
         accounts_file.clone(),
-        serde_json::to_string_pretty(&items).unwrap(),
+        serde_json::to_string_pretty(items).unwrap(),
     )?;
     Ok(())
 }
 
+/// Adds a profile with the rpc urls, the account and its accounts file (or keystore) to Scarb.toml.
+/// An existing profile with the same name is replaced only if `overwrite` is set
 pub fn add_created_profile_to_configuration(
     path_to_scarb_toml: &Option<Utf8PathBuf>,
     config: &CastConfig,
+    overwrite: bool,
 ) -> Result<()> {
     let manifest_path = match path_to_scarb_toml.clone() {
         Some(path) => path,
         None => get_scarb_manifest().context("Failed to obtain manifest path from scarb")?,
     };
-    let metadata = get_scarb_metadata(&manifest_path)?;
-
-    if let Ok(tool_sncast) = get_package_tool_sncast(&metadata) {
-        let property = tool_sncast
-            .get(&config.account)
-            .and_then(|profile_| profile_.get("account"));
-        if property.is_some() {
-            bail!(
-                "Failed to add {} profile to the Scarb.toml. Profile already exists",
-                config.account
-            );
-        }
-    }
+    let account_path = Utf8PathBuf::from(&config.account);
+    let profile_name = account_path.file_stem().unwrap_or(&config.account);
 
-    let toml_string = {
-        let mut tool_sncast = toml::value::Table::new();
-        let mut new_profile = toml::value::Table::new();
-
-        new_profile.insert("url".to_string(), Value::String(config.rpc_url.clone()));
-        new_profile.insert("account".to_string(), Value::String(config.account.clone()));
-        if config.keystore == Utf8PathBuf::default() {
-            new_profile.insert(
-                "accounts-file".to_string(),
-                Value::String(config.accounts_file.to_string()),
-            );
-        } else {
-            new_profile.insert(
-                "keystore".to_string(),
-                Value::String(config.keystore.to_string()),
-            );
-        }
+    let mut new_profile = toml::value::Table::new();
+    let url = match &config.rpc_urls[..] {
+        [url] => Value::String(url.clone()),
+        urls => Value::Array(urls.iter().cloned().map(Value::String).collect()),
+    };
+    new_profile.insert("url".to_string(), url);
+    new_profile.insert("account".to_string(), Value::String(config.account.clone()));
+    if config.keystore == Utf8PathBuf::default() {
+        let accounts_file = match &config.accounts_files[..] {
+            [accounts_file] => Value::String(accounts_file.to_string()),
+            accounts_files => Value::Array(
+                accounts_files
+                    .iter()
+                    .map(|accounts_file| Value::String(accounts_file.to_string()))
+                    .collect(),
+            ),
+        };
+        new_profile.insert("accounts-file".to_string(), accounts_file);
+    } else {
+        new_profile.insert(
+            "keystore".to_string(),
+            Value::String(config.keystore.to_string()),
+        );
+    }
 
-        let account_path = Utf8PathBuf::from(&config.account);
-        let profile_name = account_path.file_stem().unwrap_or(&config.account);
-        tool_sncast.insert(profile_name.into(), Value::Table(new_profile));
+    let toml_content = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Couldn't read {manifest_path}"))?;
+    let mut parsed_toml: toml::Table = toml::from_str(&toml_content)
+        .with_context(|| format!("Failed to parse {manifest_path}"))?;
+    let existing_profile = parsed_toml
+        .get_mut("tool")
+        .and_then(|tool| tool.get_mut("sncast"))
+        .and_then(|tool_sncast| tool_sncast.get_mut(profile_name));
 
-        let mut tool = toml::value::Table::new();
-        tool.insert("sncast".to_string(), Value::Table(tool_sncast));
+    match existing_profile {
+        Some(_) if !overwrite => bail!(
+            "Failed to add {profile_name} profile to the Scarb.toml. Profile already exists, pass --overwrite to replace it"
+        ),
+        // the whole manifest has to be rewritten, as appending would define the profile twice
+        Some(existing_profile) => {
+            *existing_profile = Value::Table(new_profile);
+            let modified_toml =
+                toml::to_string(&parsed_toml).context("Couldn't convert toml to string")?;
+            std::fs::write(&manifest_path, modified_toml)
+                .context("Couldn't write to the Scarb.toml")?;
+        }
+        None => {
+            let mut tool_sncast = toml::value::Table::new();
+            tool_sncast.insert(profile_name.into(), Value::Table(new_profile));
 
-        let mut config = toml::value::Table::new();
-        config.insert("tool".to_string(), Value::Table(tool));
+            let mut tool = toml::value::Table::new();
+            tool.insert("sncast".to_string(), Value::Table(tool_sncast));
 
-        toml::to_string(&Value::Table(config)).context("Couldn't convert toml to string")?
-    };
+            let mut config = toml::value::Table::new();
+            config.insert("tool".to_string(), Value::Table(tool));
 
-    let mut scarb_toml = OpenOptions::new()
-        .append(true)
-        .open(manifest_path)
-        .context("Couldn't open Scarb.toml")?;
-    scarb_toml
-        .write_all(format!("\n{toml_string}").as_bytes())
-        .context("Couldn't write to the Scarb.toml")?;
+            let toml_string =
+                toml::to_string(&Value::Table(config)).context("Couldn't convert toml to string")?;
+            let mut scarb_toml = OpenOptions::new()
+                .append(true)
+                .open(manifest_path)
+                .context("Couldn't open Scarb.toml")?;
+            scarb_toml
+                .write_all(format!("\n{toml_string}").as_bytes())
+                .context("Couldn't write to the Scarb.toml")?;
+        }
+    }
 
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::helpers::constants::DEFAULT_ACCOUNTS_FILE;
+    use crate::helpers::scarb_utils::CastConfig;
     use camino::Utf8PathBuf;
-    use cast::helpers::constants::DEFAULT_ACCOUNTS_FILE;
-    use cast::helpers::scarb_utils::CastConfig;
     use sealed_test::prelude::rusty_fork_test;
     use sealed_test::prelude::sealed_test;
     use std::fs;
@@ -172,12 +210,13 @@ mod tests {
     #[sealed_test(files = ["tests/data/contracts/constructor_with_params/Scarb.toml"])]
     fn test_add_created_profile_to_configuration_happy_case() {
         let config = CastConfig {
-            rpc_url: String::from("http://some-url"),
+            rpc_urls: vec![String::from("http://some-url")],
             account: String::from("some-name"),
-            accounts_file: "accounts".into(),
+            accounts_files: vec!["accounts".into()],
             keystore: Utf8PathBuf::default(),
+            ..Default::default()
         };
-        let res = add_created_profile_to_configuration(&None, &config);
+        let res = add_created_profile_to_configuration(&None, &config, false);
 
         assert!(res.is_ok());
 
@@ -191,13 +230,33 @@ mod tests {
     #[sealed_test(files = ["tests/data/contracts/constructor_with_params/Scarb.toml"])]
     fn test_add_created_profile_to_configuration_profile_already_exists() {
         let config = CastConfig {
-            rpc_url: String::from("http://some-url"),
+            rpc_urls: vec![String::from("http://some-url")],
             account: String::from("myprofile"),
-            accounts_file: DEFAULT_ACCOUNTS_FILE.into(),
+            accounts_files: vec![DEFAULT_ACCOUNTS_FILE.into()],
             keystore: Utf8PathBuf::default(),
+            ..Default::default()
         };
-        let res = add_created_profile_to_configuration(&None, &config);
+        let res = add_created_profile_to_configuration(&None, &config, false);
 
         assert!(res.is_err());
     }
+
+    #[sealed_test(files = ["tests/data/contracts/constructor_with_params/Scarb.toml"])]
+    fn test_add_created_profile_to_configuration_overwrite() {
+        let config = CastConfig {
+            rpc_urls: vec![String::from("http://some-url")],
+            account: String::from("myprofile"),
+            accounts_files: vec!["accounts".into()],
+            keystore: Utf8PathBuf::default(),
+            ..Default::default()
+        };
+        add_created_profile_to_configuration(&None, &config, true).unwrap();
+
+        let contents = fs::read_to_string("Scarb.toml").expect("Unable to read Scarb.toml");
+        let contents: toml::Table = toml::from_str(&contents).unwrap();
+        let profile = &contents["tool"]["sncast"]["myprofile"];
+        assert_eq!(profile["url"].as_str(), Some("http://some-url"));
+        assert_eq!(profile["account"].as_str(), Some("myprofile"));
+        assert_eq!(profile["accounts-file"].as_str(), Some("accounts"));
+    }
 }
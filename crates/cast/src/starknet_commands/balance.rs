@@ -0,0 +1,62 @@
+use crate::handle_rpc_error;
+use crate::helpers::constants::ETH_ADDRESS;
+use crate::helpers::response_structs::BalanceResponse;
+use crate::helpers::retry_transport::RetryTransport;
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use primitive_types::U256;
+use starknet::core::types::{BlockId, FieldElement, FunctionCall};
+use starknet::core::utils::get_selector_from_name;
+use starknet::providers::{JsonRpcClient, Provider};
+
+#[derive(Args)]
+#[command(about = "Get the token balance of an account", long_about = None)]
+pub struct Balance {
+    /// Address to check the balance of; if not passed, address of the account passed with `--account` is used
+    #[clap(long)]
+    pub address: Option<FieldElement>,
+
+    /// Address of the ERC20 token contract; ETH fee token is used by default
+    #[clap(short, long, default_value = ETH_ADDRESS)]
+    pub token: FieldElement,
+
+    /// Block identifier on which the balance should be checked.
+    /// Possible values: pending, latest, block hash (0x prefixed string)
+    /// and block number (u64)
+    #[clap(short, long, default_value = "pending")]
+    pub block_id: String,
+}
+
+pub async fn balance(
+    address: FieldElement,
+    token: FieldElement,
+    provider: &JsonRpcClient<RetryTransport>,
+    block_id: &BlockId,
+) -> Result<BalanceResponse> {
+    let function_call = FunctionCall {
+        contract_address: token,
+        entry_point_selector: get_selector_from_name("balanceOf")
+            .context("Failed to convert entry point selector to FieldElement")?,
+        calldata: vec![address],
+    };
+
+    let response = match provider.call(function_call, block_id).await {
+        Ok(response) => response,
+        Err(error) => return handle_rpc_error(error),
+    };
+    let [balance_low, balance_high] = response[..] else {
+        bail!(
+            "Unexpected balanceOf response: expected u256, got {} felts",
+            response.len()
+        );
+    };
+
+    let balance = (U256::from_big_endian(&balance_high.to_bytes_be()) << 128)
+        + U256::from_big_endian(&balance_low.to_bytes_be());
+
+    Ok(BalanceResponse {
+        balance: format!("{balance:#x}"),
+        balance_low,
+        balance_high,
+    })
+}
@@ -1,52 +1,237 @@
+use crate::helpers::abi::decode_function_outputs;
+use crate::helpers::calldata::CalldataArgs;
+use crate::helpers::response_structs::{BatchCallResponse, BatchCallResult, CallResponse};
+use crate::helpers::retry_transport::RetryTransport;
+use crate::{handle_rpc_error, parse_number};
 use anyhow::{Context, Result};
-use cast::handle_rpc_error;
-use cast::helpers::response_structs::CallResponse;
+use camino::Utf8PathBuf;
 use clap::Args;
+use futures::StreamExt;
+use starknet::core::types::contract::AbiEntry;
 use starknet::core::types::{BlockId, FieldElement, FunctionCall};
-use starknet::core::utils::get_selector_from_name;
-use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::{JsonRpcClient, Provider};
 
 #[derive(Args)]
 #[command(about = "Call a contract instance on Starknet", long_about = None)]
 pub struct Call {
     /// Address of the called contract (hex)
-    #[clap(short = 'a', long)]
-    pub contract_address: FieldElement,
+    #[clap(short = 'a', long, required_unless_present = "contract_addresses_file")]
+    pub contract_address: Option<FieldElement>,
+
+    /// Path to a file with addresses of contracts to call, one per line;
+    /// the calls are made concurrently and their results are reported per address
+    #[clap(long, conflicts_with = "contract_address")]
+    pub contract_addresses_file: Option<Utf8PathBuf>,
+
+    /// Maximum number of calls made at the same time with `--contract-addresses-file`
+    #[clap(long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..))]
+    pub concurrency: u16,
 
     /// Name of the contract function to be called
-    #[clap(short, long)]
-    pub function: String,
+    #[clap(short, long, required_unless_present = "selector")]
+    pub function: Option<String>,
 
-    /// Arguments of the called function (list of hex)
-    #[clap(short, long, value_delimiter = ' ', num_args = 1..)]
-    pub calldata: Vec<FieldElement>,
+    /// Selector of the contract function to be called (hex), used as is instead of hashing a function name
+    #[clap(long, conflicts_with = "function")]
+    pub selector: Option<FieldElement>,
 
-    /// Block identifier on which call should be performed.
+    #[clap(flatten)]
+    pub calldata_args: CalldataArgs,
+
+    /// Block identifier on which call should be performed; overrides default-block-id from Scarb.toml [default: pending].
     /// Possible values: pending, latest, block hash (0x prefixed string)
     /// and block number (u64)
-    #[clap(short, long, default_value = "pending")]
-    pub block_id: String,
+    #[clap(short, long)]
+    pub block_id: Option<String>,
+
+    /// Decode the response using the return type of the function from the contract ABI;
+    /// enabled by default when `--abi-path` is passed
+    #[clap(long, requires = "function")]
+    pub decode: bool,
+
+    /// Print the response exactly as returned by the RPC, without decoding it with the ABI or as short strings;
+    /// only `--hex-format` and `--int-format` are applied
+    #[clap(long, conflicts_with = "decode")]
+    pub raw: bool,
+}
+
+impl Call {
+    /// The response is decoded when requested, or when the ABI is passed explicitly and the function is known,
+    /// unless the raw response is requested
+    #[must_use]
+    pub fn decodes_response(&self) -> bool {
+        !self.raw
+            && (self.decode || (self.calldata_args.abi_path.is_some() && self.function.is_some()))
+    }
 }
 
+/// `decode` holds the ABI and the name of the called function, used to decode the response
 #[allow(clippy::ptr_arg)]
 pub async fn call(
     contract_address: FieldElement,
-    func_name: &str,
+    entry_point_selector: FieldElement,
     calldata: Vec<FieldElement>,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<RetryTransport>,
     block_id: &BlockId,
+    decode: Option<(&[AbiEntry], &str)>,
 ) -> Result<CallResponse> {
     let function_call = FunctionCall {
         contract_address,
-        entry_point_selector: get_selector_from_name(func_name)
-            .context("Failed to convert entry point selector to FieldElement")?,
+        entry_point_selector,
         calldata,
     };
     let res = provider.call(function_call, block_id).await;
 
-    match res {
-        Ok(response) => Ok(CallResponse { response }),
-        Err(error) => handle_rpc_error(error),
+    let response = match res {
+        Ok(response) => response,
+        Err(error) => return handle_rpc_error(error),
+    };
+    let decoded = decode
+        .map(|(abi, func_name)| decode_function_outputs(abi, func_name, &response))
+        .transpose()
+        .context("Failed to decode the response")?;
+
+    Ok(CallResponse { response, decoded })
+}
+
+/// Resolves the calldata and the ABI for the given contract and calls it
+pub async fn call_contract(
+    call_args: &Call,
+    contract_address: FieldElement,
+    entry_point_selector: FieldElement,
+    provider: &JsonRpcClient<RetryTransport>,
+    block_id: &BlockId,
+) -> Result<CallResponse> {
+    let calldata = call_args
+        .calldata_args
+        .resolve(
+            provider,
+            contract_address,
+            call_args.function.as_deref(),
+            block_id,
+        )
+        .await?;
+    let abi = if call_args.decodes_response() {
+        Some(
+            call_args
+                .calldata_args
+                .abi(provider, contract_address, block_id)
+                .await?,
+        )
+    } else {
+        None
+    };
+
+    call(
+        contract_address,
+        entry_point_selector,
+        calldata,
+        provider,
+        block_id,
+        abi.as_deref().zip(call_args.function.as_deref()),
+    )
+    .await
+}
+
+/// Calls every contract from the list, at most `concurrency` at a time;
+/// a failed call does not stop the others and is reported in its result
+pub async fn call_many(
+    call_args: &Call,
+    contract_addresses: Vec<FieldElement>,
+    entry_point_selector: FieldElement,
+    provider: &JsonRpcClient<RetryTransport>,
+    block_id: &BlockId,
+) -> Result<BatchCallResponse> {
+    let results = futures::stream::iter(contract_addresses)
+        .map(|contract_address| async move {
+            match call_contract(
+                call_args,
+                contract_address,
+                entry_point_selector,
+                provider,
+                block_id,
+            )
+            .await
+            {
+                Ok(CallResponse { response, decoded }) => BatchCallResult {
+                    contract_address,
+                    response: Some(response),
+                    decoded,
+                    error: None,
+                },
+                Err(error) => BatchCallResult {
+                    contract_address,
+                    response: None,
+                    decoded: None,
+                    error: Some(format!("{error:#}")),
+                },
+            }
+        })
+        .buffered(usize::from(call_args.concurrency))
+        .collect()
+        .await;
+
+    Ok(BatchCallResponse { results })
+}
+
+/// Reads contract addresses, one per line; empty lines and lines starting with `#` are skipped
+pub fn read_contract_addresses(path: &Utf8PathBuf) -> Result<Vec<FieldElement>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read contract addresses file {path}"))?;
+
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| {
+            parse_number(line).with_context(|| {
+                format!(
+                    "Invalid contract address {line} in {path} at line {}",
+                    index + 1
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_contract_addresses;
+    use camino::Utf8PathBuf;
+    use starknet::core::types::FieldElement;
+    use std::fs;
+
+    #[test]
+    fn test_read_contract_addresses() {
+        let addresses = read_contract_addresses(&Utf8PathBuf::from(
+            "tests/data/files/contract_addresses.txt",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            addresses,
+            vec![
+                FieldElement::from_hex_be(
+                    "0x3e4c57b4b63fa9b1d7b8b1a8d0ba2e3ecbee3cf2f9a9e30f4c39a2f19f08a55"
+                )
+                .unwrap(),
+                FieldElement::ONE,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_contract_addresses_invalid_line() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = Utf8PathBuf::from_path_buf(tempdir.path().join("addresses.txt")).unwrap();
+        fs::write(&path, "0x1\n\nnot_an_address\n").unwrap();
+
+        let error = read_contract_addresses(&path).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("Invalid contract address not_an_address"));
+        assert!(error.to_string().ends_with("at line 3"));
     }
 }
@@ -0,0 +1,43 @@
+use crate::helpers::constants::CONTRACT_NOT_FOUND_ERROR_CODE;
+use crate::helpers::response_structs::ClassHashAtResponse;
+use crate::helpers::retry_transport::RetryTransport;
+use crate::{handle_rpc_error, RpcErrorWithCode};
+use anyhow::Result;
+use clap::Args;
+use starknet::core::types::{BlockId, FieldElement};
+use starknet::providers::{JsonRpcClient, Provider};
+
+#[derive(Args)]
+#[command(about = "Get the class hash of a contract deployed at the given address", long_about = None)]
+pub struct ClassHashAt {
+    /// Address of the contract (hex)
+    pub contract_address: FieldElement,
+
+    /// Block identifier on which the class hash should be checked.
+    /// Possible values: pending, latest, block hash (0x prefixed string)
+    /// and block number (u64)
+    #[clap(short, long, default_value = "pending")]
+    pub block_id: String,
+}
+
+pub async fn class_hash_at(
+    contract_address: FieldElement,
+    provider: &JsonRpcClient<RetryTransport>,
+    block_id: &BlockId,
+) -> Result<ClassHashAtResponse> {
+    match provider.get_class_hash_at(block_id, contract_address).await {
+        Ok(class_hash) => Ok(ClassHashAtResponse { class_hash }),
+        Err(error) => handle_rpc_error(error).map_err(|error| {
+            match error.downcast_ref::<RpcErrorWithCode>() {
+                Some(rpc_error) if rpc_error.code == CONTRACT_NOT_FOUND_ERROR_CODE => {
+                    RpcErrorWithCode {
+                        code: CONTRACT_NOT_FOUND_ERROR_CODE,
+                        message: format!("No contract deployed at address {contract_address:#x}"),
+                    }
+                    .into()
+                }
+                _ => error,
+            }
+        }),
+    }
+}
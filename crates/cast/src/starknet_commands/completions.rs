@@ -0,0 +1,26 @@
+use clap::{Args, Command};
+use clap_complete::{generate, Shell};
+
+#[derive(Args, Debug)]
+#[command(
+    about = "Generate a shell completion script for sncast",
+    long_about = "Generate a shell completion script for sncast and print it to stdout.
+
+To install it:
+  bash:       sncast completions bash > ~/.local/share/bash-completion/completions/sncast
+  zsh:        sncast completions zsh > ~/.zfunc/_sncast
+              (with `fpath+=~/.zfunc` and `autoload -U compinit && compinit` in ~/.zshrc)
+  fish:       sncast completions fish > ~/.config/fish/completions/sncast.fish
+  PowerShell: sncast completions powershell >> $PROFILE
+
+Restart the shell afterwards for the completions to be loaded."
+)]
+pub struct Completions {
+    /// Shell to generate the completion script for
+    pub shell: Shell,
+}
+
+/// Completions cover all subcommands (including nested ones) and their flags
+pub fn completions(shell: Shell, command: &mut Command) {
+    generate(shell, command, "sncast", &mut std::io::stdout());
+}
@@ -1,43 +1,632 @@
-use anyhow::{anyhow, Context, Result};
+use crate::helpers::constants::DRY_RUN_MESSAGE;
+use crate::helpers::response_structs::{
+    ContractDeclareResult, DeclareManyResponse, DeclareResponse, DeclareStatus, DryRunResponse,
+    FeeEstimateResponse, SimulateResponse,
+};
+use crate::helpers::retry_transport::RetryTransport;
+use crate::helpers::scarb_utils::{get_package_metadata, get_scarb_manifest};
+use crate::helpers::signer::CastSigner;
+use crate::starknet_commands::invoke::{fee_estimate_response, simulate_response};
+use crate::{
+    ensure_balance_covers_fee, handle_rpc_error, handle_wait_for_tx, max_fee_from_estimate,
+    print_command_result, sign_transaction_hash, write_to_output, ValueFormat, WaitForTx,
+};
+use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
-use cast::helpers::scarb_utils::get_package_metadata;
-use cast::helpers::{response_structs::DeclareResponse, scarb_utils::get_scarb_manifest};
-use cast::{handle_rpc_error, handle_wait_for_tx};
 use clap::Args;
-use scarb_artifacts::get_contracts_map;
-use starknet::accounts::AccountError::Provider;
+use scarb_artifacts::{get_contracts_map, StarknetContractArtifacts};
+use starknet::accounts::AccountError::{Provider, Signing};
 use starknet::accounts::ConnectedAccount;
-use starknet::core::types::FieldElement;
+use starknet::core::types::{BlockId, BlockTag::Pending, DeclareTransactionResult, FieldElement};
+use starknet::providers::Provider;
 use starknet::{
     accounts::{Account, SingleOwnerAccount},
-    core::types::contract::{CompiledClass, SierraClass},
-    providers::jsonrpc::{HttpTransport, JsonRpcClient},
-    signers::LocalWallet,
+    core::types::{
+        contract::{legacy::LegacyContractClass, CompiledClass, SierraClass},
+        FlattenedSierraClass,
+    },
+    providers::jsonrpc::JsonRpcClient,
 };
+use std::collections::HashMap;
+use std::fs;
 use std::process::{Command, Stdio};
 use std::sync::Arc;
 
 #[derive(Args)]
 #[command(about = "Declare a contract to starknet", long_about = None)]
 pub struct Declare {
-    /// Contract name
-    #[clap(short = 'c', long = "contract-name")]
-    pub contract: String,
+    /// Contract name; can be passed multiple times to declare several contracts
+    #[clap(short = 'c', long = "contract-name", alias = "contract", conflicts_with_all = ["sierra_path", "casm_path"])]
+    pub contract: Vec<String>,
+
+    /// If passed, all contracts from the Scarb package are declared
+    #[clap(long, conflicts_with_all = ["contract", "sierra_path", "casm_path"])]
+    pub all: bool,
+
+    /// Path to the compiled Sierra contract class, used together with `--casm-path` instead of `--contract-name`
+    #[clap(long, alias = "sierra-file", requires = "casm_path")]
+    pub sierra_path: Option<Utf8PathBuf>,
+
+    /// Path to the compiled CASM contract class, used together with `--sierra-path` instead of `--contract-name`
+    #[clap(long, alias = "casm-file", requires = "sierra_path")]
+    pub casm_path: Option<Utf8PathBuf>,
+
+    /// Path to a compiled Cairo 0 contract; if passed, the class is declared with a legacy (v1) declare transaction
+    #[clap(long, value_name = "PATH", conflicts_with_all = ["contract", "all", "sierra_path", "casm_path"])]
+    pub legacy: Option<Utf8PathBuf>,
 
     /// Max fee for the transaction. If not provided, max fee will be automatically estimated
     #[clap(short, long)]
     pub max_fee: Option<FieldElement>,
+
+    /// Nonce of the transaction. If not provided, nonce will be set automatically
+    #[clap(short, long)]
+    pub nonce: Option<FieldElement>,
+
+    /// If passed, only the fee of the transaction will be estimated, without sending it
+    #[clap(long)]
+    pub estimate_only: bool,
+
+    /// If passed, the transaction is only simulated: its fee and state diff are printed
+    #[clap(long, conflicts_with = "estimate_only")]
+    pub simulate: bool,
+
+    /// If passed, the declare transaction is sent even if the class is already declared
+    #[clap(long)]
+    pub force_declare: bool,
+}
+
+/// Where the class to declare is loaded from
+pub enum ContractSource {
+    /// Contract from the Scarb project, built before declaring
+    Name(String),
+    /// Precompiled Sierra and CASM artifacts
+    Artifacts {
+        sierra_path: Utf8PathBuf,
+        casm_path: Utf8PathBuf,
+    },
+    /// Compiled Cairo 0 contract, declared with a legacy declare transaction
+    Legacy(Utf8PathBuf),
+}
+
+impl Declare {
+    /// Whether more than one contract is declared, with `--all` or `--contract-name` passed multiple times
+    #[must_use]
+    pub fn declares_many(&self) -> bool {
+        self.all || self.contract.len() > 1
+    }
+
+    pub fn contract_source(&self) -> Result<ContractSource> {
+        if let Some(legacy_path) = &self.legacy {
+            if !legacy_path.is_file() {
+                bail!("Cairo 0 contract file {legacy_path} does not exist");
+            }
+            return Ok(ContractSource::Legacy(legacy_path.clone()));
+        }
+        match (self.contract.as_slice(), &self.sierra_path, &self.casm_path) {
+            ([contract_name], None, None) => Ok(ContractSource::Name(contract_name.clone())),
+            ([], Some(sierra_path), Some(casm_path)) => {
+                // fail before connecting to the network if the artifacts were not built
+                for (kind, path) in [("Sierra", sierra_path), ("CASM", casm_path)] {
+                    if !path.is_file() {
+                        bail!("{kind} file {path} does not exist");
+                    }
+                }
+                Ok(ContractSource::Artifacts {
+                    sierra_path: sierra_path.clone(),
+                    casm_path: casm_path.clone(),
+                })
+            }
+            ([], _, _) => Err(anyhow!(
+                "Either --contract-name, --all, --legacy or both --sierra-path and --casm-path must be passed"
+            )),
+            ([_], _, _) => Err(anyhow!(
+                "--contract-name cannot be used together with --sierra-path and --casm-path"
+            )),
+            (_, _, _) => Err(anyhow!(
+                "Multiple contracts can be declared only by passing --contract-name multiple times or --all"
+            )),
+        }
+    }
 }
 
-#[allow(clippy::too_many_lines)]
 pub async fn declare(
-    contract_name: &str,
+    contract: &ContractSource,
     max_fee: Option<FieldElement>,
-    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
     path_to_scarb_toml: &Option<Utf8PathBuf>,
-    wait: bool,
+    wait_config: WaitForTx,
+    force: bool,
 ) -> Result<DeclareResponse> {
-    let contract_name: String = contract_name.to_string();
+    let result = if let ContractSource::Legacy(legacy_path) = contract {
+        let contract_class = load_legacy_contract_class(legacy_path)?;
+        let class_hash = legacy_class_hash(&contract_class)?;
+        if !force && is_declared(account, class_hash).await {
+            return Ok(skipped_declaration(class_hash));
+        }
+        send_legacy_declaration(contract_class, max_fee, fee_multiplier, nonce, account).await?
+    } else {
+        let (contract_class, casm_class_hash) = load_contract_class(contract, path_to_scarb_toml)?;
+        let class_hash = contract_class.class_hash();
+        if !force && is_declared(account, class_hash).await {
+            return Ok(skipped_declaration(class_hash));
+        }
+        send_declaration(
+            contract_class,
+            casm_class_hash,
+            max_fee,
+            fee_multiplier,
+            nonce,
+            account,
+        )
+        .await?
+    };
+    handle_wait_for_tx(
+        account.provider(),
+        result.transaction_hash,
+        DeclareResponse {
+            class_hash: result.class_hash,
+            transaction_hash: Some(result.transaction_hash),
+            already_declared: false,
+            message: None,
+        },
+        wait_config,
+    )
+    .await
+}
+
+fn skipped_declaration(class_hash: FieldElement) -> DeclareResponse {
+    DeclareResponse {
+        class_hash,
+        transaction_hash: None,
+        already_declared: true,
+        message: Some(String::from(
+            "Class is already declared, declaration skipped (pass --force-declare to send it anyway)",
+        )),
+    }
+}
+
+async fn is_declared(
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    class_hash: FieldElement,
+) -> bool {
+    account
+        .provider()
+        .get_class(BlockId::Tag(Pending), class_hash)
+        .await
+        .is_ok()
+}
+
+async fn send_declaration(
+    contract_class: FlattenedSierraClass,
+    casm_class_hash: FieldElement,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+) -> Result<DeclareTransactionResult> {
+    let declaration = account.declare(Arc::new(contract_class), casm_class_hash);
+    let execution = if let Some(nonce) = nonce {
+        declaration.nonce(nonce)
+    } else {
+        declaration
+    };
+    let max_fee = match max_fee {
+        Some(max_fee) => max_fee,
+        None => max_fee_from_estimate(execution.estimate_fee().await, fee_multiplier)?,
+    };
+
+    ensure_balance_covers_fee(account, max_fee).await?;
+    match execution.max_fee(max_fee).send().await {
+        Ok(result) => Ok(result),
+        Err(Provider(error)) => handle_rpc_error(error),
+        Err(Signing(error)) => Err(anyhow!("Failed to sign transaction: {error}")),
+        _ => Err(anyhow!("Unknown RPC error")),
+    }
+}
+
+async fn send_legacy_declaration(
+    contract_class: LegacyContractClass,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+) -> Result<DeclareTransactionResult> {
+    let declaration = account.declare_legacy(Arc::new(contract_class));
+    let execution = if let Some(nonce) = nonce {
+        declaration.nonce(nonce)
+    } else {
+        declaration
+    };
+    let max_fee = match max_fee {
+        Some(max_fee) => max_fee,
+        None => max_fee_from_estimate(execution.estimate_fee().await, fee_multiplier)?,
+    };
+
+    ensure_balance_covers_fee(account, max_fee).await?;
+    match execution.max_fee(max_fee).send().await {
+        Ok(result) => Ok(result),
+        Err(Provider(error)) => handle_rpc_error(error),
+        Err(Signing(error)) => Err(anyhow!("Failed to sign transaction: {error}")),
+        _ => Err(anyhow!("Unknown RPC error")),
+    }
+}
+
+/// Declares the contracts one after another, reporting the outcome of every one of them instead of stopping at
+/// the first failure. Contracts which are already declared are reported with their class hash.
+/// Nonces are assigned upfront, as the nonce of the pending block may not include the declarations just sent
+#[allow(clippy::too_many_arguments)]
+pub async fn declare_many(
+    contracts: &[String],
+    all: bool,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    path_to_scarb_toml: &Option<Utf8PathBuf>,
+    wait_config: WaitForTx,
+    force: bool,
+) -> Result<DeclareManyResponse> {
+    let artifacts = build_scarb_contracts(path_to_scarb_toml)?;
+    let contracts = if all {
+        let mut contracts: Vec<String> = artifacts.keys().cloned().collect();
+        contracts.sort();
+        contracts
+    } else {
+        contracts.to_vec()
+    };
+    if contracts.is_empty() {
+        bail!("No contracts found in the Scarb package");
+    }
+
+    let mut nonce = match nonce {
+        Some(nonce) => nonce,
+        None => match account.get_nonce().await {
+            Ok(nonce) => nonce,
+            Err(error) => return handle_rpc_error(error),
+        },
+    };
+
+    let mut results = Vec::with_capacity(contracts.len());
+    for contract in contracts {
+        let loaded = artifacts
+            .get(&contract)
+            .ok_or_else(|| anyhow!("Failed to find artifacts of contract {contract}"))
+            .and_then(|artifacts| parse_contract_artifacts(&artifacts.sierra, &artifacts.casm));
+        let (contract_class, casm_class_hash) = match loaded {
+            Ok(loaded) => loaded,
+            Err(error) => {
+                results.push(ContractDeclareResult::failed(contract, None, None, &error));
+                continue;
+            }
+        };
+        let class_hash = contract_class.class_hash();
+
+        if !force && is_declared(account, class_hash).await {
+            results.push(ContractDeclareResult {
+                contract,
+                status: DeclareStatus::AlreadyDeclared,
+                class_hash: Some(class_hash),
+                transaction_hash: None,
+                error: None,
+            });
+            continue;
+        }
+
+        // waiting happens only after the nonce is known to be used
+        let sent = send_declaration(
+            contract_class,
+            casm_class_hash,
+            max_fee,
+            fee_multiplier,
+            Some(nonce),
+            account,
+        )
+        .await;
+        let response = match sent {
+            Ok(response) => response,
+            Err(error) => {
+                results.push(ContractDeclareResult::failed(
+                    contract,
+                    Some(class_hash),
+                    None,
+                    &error,
+                ));
+                continue;
+            }
+        };
+        nonce += FieldElement::ONE;
+
+        let waited = handle_wait_for_tx(
+            account.provider(),
+            response.transaction_hash,
+            (),
+            wait_config,
+        )
+        .await;
+        results.push(match waited {
+            Ok(()) => ContractDeclareResult {
+                contract,
+                status: DeclareStatus::Declared,
+                class_hash: Some(response.class_hash),
+                transaction_hash: Some(response.transaction_hash),
+                error: None,
+            },
+            Err(error) => ContractDeclareResult::failed(
+                contract,
+                Some(response.class_hash),
+                Some(response.transaction_hash),
+                &error,
+            ),
+        });
+    }
+
+    Ok(DeclareManyResponse { results })
+}
+
+impl ContractDeclareResult {
+    fn failed(
+        contract: String,
+        class_hash: Option<FieldElement>,
+        transaction_hash: Option<FieldElement>,
+        error: &anyhow::Error,
+    ) -> Self {
+        ContractDeclareResult {
+            contract,
+            status: DeclareStatus::Failed,
+            class_hash,
+            transaction_hash,
+            error: Some(format!("{error:#}")),
+        }
+    }
+}
+
+/// Contracts are listed in a table, as their results can't be displayed as flat key-value pairs
+pub fn print_declare_many_result(
+    result: &Result<DeclareManyResponse>,
+    value_format: ValueFormat,
+    json: bool,
+) -> Result<()> {
+    let response = match result {
+        Ok(response) if !json => response,
+        _ => return print_command_result("declare", result, value_format, json),
+    };
+
+    let name_width = response
+        .results
+        .iter()
+        .map(|result| result.contract.len())
+        .max()
+        .unwrap_or_default()
+        .max("contract".len())
+        + 2;
+    write_to_output("command: declare", false);
+    write_to_output(
+        format!(
+            "{:<name_width$}{:<68}{:<18}details",
+            "contract", "class_hash", "status"
+        ),
+        false,
+    );
+    for result in &response.results {
+        let class_hash = result.class_hash.map_or_else(
+            || String::from("-"),
+            |class_hash| value_format.format_str(&format!("{class_hash:#x}")),
+        );
+        let status = match result.status {
+            DeclareStatus::Declared => "declared",
+            DeclareStatus::AlreadyDeclared => "already_declared",
+            DeclareStatus::Failed => "failed",
+        };
+        let details = match (&result.error, &result.transaction_hash) {
+            (Some(error), _) => error.replace('\n', " "),
+            (None, Some(transaction_hash)) => {
+                value_format.format_str(&format!("{transaction_hash:#x}"))
+            }
+            (None, None) => String::new(),
+        };
+        write_to_output(
+            format!(
+                "{:<name_width$}{class_hash:<68}{status:<18}{details}",
+                result.contract
+            ),
+            false,
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn estimate_declare(
+    contract: &ContractSource,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    path_to_scarb_toml: &Option<Utf8PathBuf>,
+) -> Result<FeeEstimateResponse> {
+    let estimate = if let ContractSource::Legacy(legacy_path) = contract {
+        let contract_class = load_legacy_contract_class(legacy_path)?;
+        account
+            .declare_legacy(Arc::new(contract_class))
+            .estimate_fee()
+            .await
+    } else {
+        let (contract_class, casm_class_hash) = load_contract_class(contract, path_to_scarb_toml)?;
+        account
+            .declare(Arc::new(contract_class), casm_class_hash)
+            .estimate_fee()
+            .await
+    };
+
+    match estimate {
+        Ok(fee_estimate) => Ok(fee_estimate_response(fee_estimate, max_fee, fee_multiplier)),
+        Err(Provider(error)) => handle_rpc_error(error),
+        Err(Signing(error)) => Err(anyhow!("Failed to sign transaction: {error}")),
+        _ => Err(anyhow!("Unknown RPC error")),
+    }
+}
+
+pub async fn dry_run_declare(
+    contract: &ContractSource,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    signer: &CastSigner,
+    path_to_scarb_toml: &Option<Utf8PathBuf>,
+) -> Result<DryRunResponse> {
+    if matches!(contract, ContractSource::Legacy(_)) {
+        bail!("--dry-run is not supported for Cairo 0 contracts declared with --legacy");
+    }
+    let (contract_class, casm_class_hash) = load_contract_class(contract, path_to_scarb_toml)?;
+    let class_hash = contract_class.class_hash();
+
+    let nonce = match nonce {
+        Some(nonce) => nonce,
+        None => match account.get_nonce().await {
+            Ok(nonce) => nonce,
+            Err(error) => return handle_rpc_error(error),
+        },
+    };
+    let declaration = account
+        .declare(Arc::new(contract_class), casm_class_hash)
+        .nonce(nonce);
+
+    let max_fee = match max_fee {
+        Some(max_fee) => max_fee,
+        None => max_fee_from_estimate(declaration.estimate_fee().await, fee_multiplier)?,
+    };
+
+    let transaction_hash = declaration
+        .max_fee(max_fee)
+        .prepared()
+        .map_err(|_| anyhow!("Failed to prepare transaction"))?
+        .transaction_hash(false);
+
+    Ok(DryRunResponse {
+        message: DRY_RUN_MESSAGE.to_string(),
+        // account.declare builds declare v2 transactions
+        version: FieldElement::TWO,
+        transaction_hash,
+        sender_address: account.address(),
+        nonce,
+        max_fee,
+        signature: sign_transaction_hash(signer, transaction_hash).await?,
+        calldata: None,
+        contract_address: None,
+        class_hash: Some(class_hash),
+        compiled_class_hash: Some(casm_class_hash),
+        salt: None,
+    })
+}
+
+/// Fee is not charged, so that the simulation does not depend on the max fee being high enough
+pub async fn simulate_declare(
+    contract: &ContractSource,
+    max_fee: Option<FieldElement>,
+    nonce: Option<FieldElement>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    path_to_scarb_toml: &Option<Utf8PathBuf>,
+) -> Result<SimulateResponse> {
+    let (class_hash, simulation) = if let ContractSource::Legacy(legacy_path) = contract {
+        let contract_class = load_legacy_contract_class(legacy_path)?;
+        let class_hash = legacy_class_hash(&contract_class)?;
+
+        let declaration = account.declare_legacy(Arc::new(contract_class));
+        let declaration = match nonce {
+            Some(nonce) => declaration.nonce(nonce),
+            None => declaration,
+        };
+        let declaration = match max_fee {
+            Some(max_fee) => declaration.max_fee(max_fee),
+            None => declaration,
+        };
+        (class_hash, declaration.simulate(false, true).await)
+    } else {
+        let (contract_class, casm_class_hash) = load_contract_class(contract, path_to_scarb_toml)?;
+        let class_hash = contract_class.class_hash();
+
+        let declaration = account.declare(Arc::new(contract_class), casm_class_hash);
+        let declaration = match nonce {
+            Some(nonce) => declaration.nonce(nonce),
+            None => declaration,
+        };
+        let declaration = match max_fee {
+            Some(max_fee) => declaration.max_fee(max_fee),
+            None => declaration,
+        };
+        (class_hash, declaration.simulate(false, true).await)
+    };
+
+    let simulation = match simulation {
+        Ok(simulation) => simulation,
+        Err(Provider(error)) => return handle_rpc_error(error),
+        Err(Signing(error)) => return Err(anyhow!("Failed to sign transaction: {error}")),
+        _ => return Err(anyhow!("Unknown RPC error")),
+    };
+    let mut response = simulate_response(simulation)?;
+    response.class_hash = Some(class_hash);
+    Ok(response)
+}
+
+fn load_contract_class(
+    contract: &ContractSource,
+    path_to_scarb_toml: &Option<Utf8PathBuf>,
+) -> Result<(FlattenedSierraClass, FieldElement)> {
+    match contract {
+        ContractSource::Name(contract_name) => {
+            load_contract_class_from_scarb(contract_name, path_to_scarb_toml)
+        }
+        ContractSource::Artifacts {
+            sierra_path,
+            casm_path,
+        } => {
+            let sierra = fs::read_to_string(sierra_path)
+                .with_context(|| format!("Failed to read sierra artifact at {sierra_path}"))?;
+            let casm = fs::read_to_string(casm_path)
+                .with_context(|| format!("Failed to read casm artifact at {casm_path}"))?;
+            parse_contract_artifacts(&sierra, &casm).with_context(|| {
+                format!("Invalid contract artifacts {sierra_path} and {casm_path}")
+            })
+        }
+        ContractSource::Legacy(legacy_path) => {
+            bail!("Cairo 0 contract {legacy_path} has no Sierra contract class")
+        }
+    }
+}
+
+fn load_legacy_contract_class(legacy_path: &Utf8PathBuf) -> Result<LegacyContractClass> {
+    let contents = fs::read_to_string(legacy_path)
+        .with_context(|| format!("Failed to read Cairo 0 contract at {legacy_path}"))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid Cairo 0 contract {legacy_path}"))
+}
+
+fn legacy_class_hash(contract_class: &LegacyContractClass) -> Result<FieldElement> {
+    contract_class
+        .class_hash()
+        .map_err(|error| anyhow!("Failed to compute class hash of the Cairo 0 contract: {error}"))
+}
+
+fn load_contract_class_from_scarb(
+    contract_name: &str,
+    path_to_scarb_toml: &Option<Utf8PathBuf>,
+) -> Result<(FlattenedSierraClass, FieldElement)> {
+    let contracts = build_scarb_contracts(path_to_scarb_toml)?;
+
+    let contract_artifacts = contracts
+        .get(contract_name)
+        .ok_or(anyhow!("Failed to find artifacts in starknet_artifacts.json file. Make sure you have enabled sierra and casm code generation in Scarb.toml"))?;
+
+    parse_contract_artifacts(&contract_artifacts.sierra, &contract_artifacts.casm)
+}
+
+/// Builds the Scarb package and returns the artifacts of its contracts by name
+fn build_scarb_contracts(
+    path_to_scarb_toml: &Option<Utf8PathBuf>,
+) -> Result<HashMap<String, StarknetContractArtifacts>> {
     let manifest_path = match path_to_scarb_toml.clone() {
         Some(path) => path,
         None => get_scarb_manifest().context("Failed to obtain manifest path from scarb")?,
@@ -71,42 +660,20 @@ pub async fn declare(
         .context("Failed to obtain scarb metadata")?;
 
     let package = get_package_metadata(&metadata, &manifest_path)
-        .with_context(|| anyhow!("Failed to find package for contract {}", contract_name))?;
-    let contracts = get_contracts_map(&metadata, &package.id)?;
-
-    let contract_artifacts = contracts
-        .get(&contract_name)
-        .ok_or(anyhow!("Failed to find artifacts in starknet_artifacts.json file. Make sure you have enabled sierra and casm code generation in Scarb.toml"))?;
+        .with_context(|| anyhow!("Failed to find package {manifest_path}"))?;
+    get_contracts_map(&metadata, &package.id)
+}
 
-    let contract_definition: SierraClass = serde_json::from_str(&contract_artifacts.sierra)
-        .with_context(|| "Failed to parse sierra artifact")?;
-    let casm_contract_definition: CompiledClass = serde_json::from_str(&contract_artifacts.casm)
-        .with_context(|| "Failed to parse casm artifact")?;
+fn parse_contract_artifacts(
+    sierra: &str,
+    casm: &str,
+) -> Result<(FlattenedSierraClass, FieldElement)> {
+    let contract_definition: SierraClass =
+        serde_json::from_str(sierra).with_context(|| "Failed to parse sierra artifact")?;
+    let casm_contract_definition: CompiledClass =
+        serde_json::from_str(casm).with_context(|| "Failed to parse casm artifact")?;
 
     let casm_class_hash = casm_contract_definition.class_hash()?;
 
-    let declaration = account.declare(Arc::new(contract_definition.flatten()?), casm_class_hash);
-    let execution = if let Some(max_fee) = max_fee {
-        declaration.max_fee(max_fee)
-    } else {
-        declaration
-    };
-    let declared = execution.send().await;
-
-    match declared {
-        Ok(result) => {
-            handle_wait_for_tx(
-                account.provider(),
-                result.transaction_hash,
-                DeclareResponse {
-                    class_hash: result.class_hash,
-                    transaction_hash: result.transaction_hash,
-                },
-                wait,
-            )
-            .await
-        }
-        Err(Provider(error)) => handle_rpc_error(error),
-        _ => Err(anyhow!("Unknown RPC error")),
-    }
+    Ok((contract_definition.flatten()?, casm_class_hash))
 }
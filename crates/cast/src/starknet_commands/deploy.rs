@@ -1,17 +1,27 @@
+use crate::helpers::constants::UDC_ADDRESS;
+use crate::helpers::retry_transport::RetryTransport;
+use crate::helpers::signer::CastSigner;
+use crate::starknet_commands::invoke::{dry_run_calls, estimate_calls, simulate_calls};
 use anyhow::{anyhow, Result};
+use camino::Utf8PathBuf;
 use clap::Args;
-use starknet::accounts::AccountError::Provider;
-use starknet::accounts::{Account, ConnectedAccount, SingleOwnerAccount};
+use starknet::accounts::AccountError::{Provider, Signing};
+use starknet::accounts::{Account, Call, ConnectedAccount, SingleOwnerAccount};
 use starknet::contract::ContractFactory;
-use starknet::core::types::FieldElement;
-use starknet::core::utils::get_udc_deployed_address;
-use starknet::providers::jsonrpc::HttpTransport;
-use starknet::providers::JsonRpcClient;
-use starknet::signers::LocalWallet;
+use starknet::core::types::{BlockId, BlockTag, FieldElement, StarknetError};
+use starknet::core::utils::{get_selector_from_name, get_udc_deployed_address};
+use starknet::providers::{
+    JsonRpcClient, MaybeUnknownErrorCode, Provider as _, ProviderError, StarknetErrorWithMessage,
+};
 
-use cast::helpers::response_structs::DeployResponse;
-use cast::{extract_or_generate_salt, udc_uniqueness};
-use cast::{handle_rpc_error, handle_wait_for_tx};
+use crate::helpers::response_structs::{
+    DeployResponse, DryRunResponse, FeeEstimateResponse, SimulateResponse,
+};
+use crate::{
+    ensure_balance_covers_fee, extract_or_generate_salt, max_fee_from_estimate, parse_number,
+    parse_salt, udc_uniqueness,
+};
+use crate::{handle_rpc_error, handle_wait_for_tx, WaitForTx};
 
 #[derive(Args)]
 #[command(about = "Deploy a contract on Starknet")]
@@ -20,14 +30,26 @@ pub struct Deploy {
     #[clap(short = 'g', long)]
     pub class_hash: FieldElement,
 
-    /// Calldata for the contract constructor
-    #[clap(short, long, value_delimiter = ' ', num_args = 1..)]
-    pub constructor_calldata: Vec<FieldElement>,
+    /// Calldata for the contract constructor (list of hex or decimal felts); `'text'` is encoded
+    /// as a Cairo short string and `str:"text"` as a ByteArray
+    #[clap(short, long, num_args = 1..)]
+    pub constructor_calldata: Vec<String>,
 
-    /// Salt for the address
-    #[clap(short, long)]
+    /// Path to the file with calldata for the contract constructor (`-` reads it from stdin), separated
+    /// by whitespace or newlines, or as a JSON array
+    #[clap(long, conflicts_with = "constructor_calldata")]
+    pub calldata_file: Option<Utf8PathBuf>,
+
+    /// Salt for the address; pass `random` to generate a random one.
+    /// If not passed, a random salt is used
+    #[clap(short, long, value_parser = parse_salt)]
     pub salt: Option<FieldElement>,
 
+    /// Label from which the salt is derived (using starknet keccak), allowing to get the same
+    /// contract address on different networks
+    #[clap(long, conflicts_with = "salt")]
+    pub salt_from_label: Option<String>,
+
     /// If true, salt will be modified with an account address
     #[clap(short, long)]
     pub unique: bool,
@@ -35,29 +57,66 @@ pub struct Deploy {
     /// Max fee for the transaction. If not provided, max fee will be automatically estimated
     #[clap(short, long)]
     pub max_fee: Option<FieldElement>,
+
+    /// Nonce of the transaction. If not provided, nonce will be set automatically
+    #[clap(short, long)]
+    pub nonce: Option<FieldElement>,
+
+    /// If passed, only the fee of the transaction will be estimated, without sending it
+    #[clap(long)]
+    pub estimate_only: bool,
+
+    /// If passed, the transaction is only simulated: its fee, execution trace with internal calls and events,
+    /// and state diff are printed
+    #[clap(long, conflicts_with = "estimate_only")]
+    pub simulate: bool,
+
+    /// If passed, it will not be checked whether the class is declared before deploying it
+    #[clap(long)]
+    pub skip_validation: bool,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn deploy(
     class_hash: FieldElement,
     constructor_calldata: Vec<FieldElement>,
     salt: Option<FieldElement>,
     unique: bool,
     max_fee: Option<FieldElement>,
-    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
-    wait: bool,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+    skip_validation: bool,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    wait_config: WaitForTx,
 ) -> Result<DeployResponse> {
+    if !skip_validation {
+        ensure_class_declared(account.provider(), class_hash).await?;
+    }
     let salt = extract_or_generate_salt(salt);
+    let contract_address = predicted_contract_address(
+        class_hash,
+        salt,
+        unique,
+        &constructor_calldata,
+        account.address(),
+    );
+    eprintln!("Contract will be deployed at address {contract_address:#x}");
 
     let factory = ContractFactory::new(class_hash, account);
     let deployment = factory.deploy(constructor_calldata.clone(), salt, unique);
 
-    let execution = if let Some(max_fee) = max_fee {
-        deployment.max_fee(max_fee)
+    let execution = if let Some(nonce) = nonce {
+        deployment.nonce(nonce)
     } else {
         deployment
     };
+    let max_fee = match max_fee {
+        Some(max_fee) => max_fee,
+        None => max_fee_from_estimate(execution.estimate_fee().await, fee_multiplier)?,
+    };
 
-    let result = execution.send().await;
+    ensure_balance_covers_fee(account, max_fee).await?;
+    let result = execution.max_fee(max_fee).send().await;
 
     match result {
         Ok(result) => {
@@ -65,19 +124,157 @@ pub async fn deploy(
                 account.provider(),
                 result.transaction_hash,
                 DeployResponse {
-                    contract_address: get_udc_deployed_address(
-                        salt,
-                        class_hash,
-                        &udc_uniqueness(unique, account.address()),
-                        &constructor_calldata,
-                    ),
+                    contract_address,
                     transaction_hash: result.transaction_hash,
+                    salt,
                 },
-                wait,
+                wait_config,
             )
             .await
         }
         Err(Provider(error)) => handle_rpc_error(error),
+        Err(Signing(error)) => Err(anyhow!("Failed to sign transaction: {error}")),
         _ => Err(anyhow!("Unknown RPC error")),
     }
 }
+
+#[allow(clippy::too_many_arguments)]
+pub async fn estimate_deploy(
+    class_hash: FieldElement,
+    constructor_calldata: &[FieldElement],
+    salt: Option<FieldElement>,
+    unique: bool,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    skip_validation: bool,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+) -> Result<FeeEstimateResponse> {
+    if !skip_validation {
+        ensure_class_declared(account.provider(), class_hash).await?;
+    }
+    let salt = extract_or_generate_salt(salt);
+    let call = udc_deploy_call(class_hash, salt, unique, constructor_calldata)?;
+
+    estimate_calls(account, vec![call], max_fee, fee_multiplier).await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn dry_run_deploy(
+    class_hash: FieldElement,
+    constructor_calldata: Vec<FieldElement>,
+    salt: Option<FieldElement>,
+    unique: bool,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+    skip_validation: bool,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    signer: &CastSigner,
+) -> Result<DryRunResponse> {
+    if !skip_validation {
+        ensure_class_declared(account.provider(), class_hash).await?;
+    }
+    let salt = extract_or_generate_salt(salt);
+    let call = udc_deploy_call(class_hash, salt, unique, &constructor_calldata)?;
+
+    let mut response =
+        dry_run_calls(account, signer, vec![call], max_fee, fee_multiplier, nonce).await?;
+    response.contract_address = Some(predicted_contract_address(
+        class_hash,
+        salt,
+        unique,
+        &constructor_calldata,
+        account.address(),
+    ));
+    response.salt = Some(salt);
+    Ok(response)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn simulate_deploy(
+    class_hash: FieldElement,
+    constructor_calldata: &[FieldElement],
+    salt: Option<FieldElement>,
+    unique: bool,
+    max_fee: Option<FieldElement>,
+    nonce: Option<FieldElement>,
+    skip_validation: bool,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+) -> Result<SimulateResponse> {
+    if !skip_validation {
+        ensure_class_declared(account.provider(), class_hash).await?;
+    }
+    let salt = extract_or_generate_salt(salt);
+    let call = udc_deploy_call(class_hash, salt, unique, constructor_calldata)?;
+
+    let mut response = simulate_calls(account, vec![call], max_fee, nonce).await?;
+    response.contract_address = Some(predicted_contract_address(
+        class_hash,
+        salt,
+        unique,
+        constructor_calldata,
+        account.address(),
+    ));
+    Ok(response)
+}
+
+/// Checks that the class is declared on the network, as deploying an undeclared class
+/// fails with an error which is hard to understand
+pub async fn ensure_class_declared(
+    provider: &JsonRpcClient<RetryTransport>,
+    class_hash: FieldElement,
+) -> Result<()> {
+    match provider
+        .get_class(BlockId::Tag(BlockTag::Pending), class_hash)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(ProviderError::StarknetError(StarknetErrorWithMessage {
+            code: MaybeUnknownErrorCode::Known(StarknetError::ClassHashNotFound),
+            ..
+        })) => Err(anyhow!(
+            "Class with hash {class_hash:#x} is not declared, declare it first with `declare` command (or pass --skip-validation to deploy anyway)"
+        )),
+        Err(error) => handle_rpc_error(error),
+    }
+}
+
+/// Computes the address of a contract deployed through the Universal Deployer Contract.
+/// When `unique` is set, UDC mixes the salt with the address of the deploying account
+#[must_use]
+pub fn predicted_contract_address(
+    class_hash: FieldElement,
+    salt: FieldElement,
+    unique: bool,
+    constructor_calldata: &[FieldElement],
+    deployer_address: FieldElement,
+) -> FieldElement {
+    get_udc_deployed_address(
+        salt,
+        class_hash,
+        &udc_uniqueness(unique, deployer_address),
+        constructor_calldata,
+    )
+}
+
+/// Builds a call to the Universal Deployer Contract deploying a contract of given class
+pub fn udc_deploy_call(
+    class_hash: FieldElement,
+    salt: FieldElement,
+    unique: bool,
+    constructor_calldata: &[FieldElement],
+) -> Result<Call> {
+    let mut calldata = vec![
+        class_hash,
+        salt,
+        FieldElement::from(u8::from(unique)),
+        constructor_calldata.len().into(),
+    ];
+    calldata.extend(constructor_calldata);
+
+    Ok(Call {
+        to: parse_number(UDC_ADDRESS)?,
+        selector: get_selector_from_name("deployContract")?,
+        calldata,
+    })
+}
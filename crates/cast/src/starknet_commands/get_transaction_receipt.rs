@@ -0,0 +1,108 @@
+use crate::handle_rpc_error;
+use crate::helpers::response_structs::{EventResponse, TransactionReceiptResponse};
+use crate::helpers::retry_transport::RetryTransport;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Args;
+use starknet::core::types::{
+    ExecutionResult, FieldElement, MaybePendingTransactionReceipt, StarknetError,
+    TransactionFinalityStatus, TransactionReceipt,
+};
+use starknet::providers::{
+    JsonRpcClient, MaybeUnknownErrorCode, Provider, ProviderError, StarknetErrorWithMessage,
+};
+
+#[derive(Args)]
+#[command(about = "Get the receipt of a transaction", long_about = None)]
+pub struct GetTransactionReceipt {
+    /// Hash of the transaction
+    pub transaction_hash: FieldElement,
+}
+
+pub async fn get_transaction_receipt(
+    provider: &JsonRpcClient<RetryTransport>,
+    transaction_hash: FieldElement,
+) -> Result<TransactionReceiptResponse> {
+    let receipt = match provider.get_transaction_receipt(transaction_hash).await {
+        Ok(MaybePendingTransactionReceipt::Receipt(receipt)) => receipt,
+        Ok(MaybePendingTransactionReceipt::PendingReceipt(_))
+        | Err(ProviderError::StarknetError(StarknetErrorWithMessage {
+            code: MaybeUnknownErrorCode::Known(StarknetError::TransactionHashNotFound),
+            message: _,
+        })) => bail!(
+            "Receipt of transaction {transaction_hash:#x} is not yet available, the transaction is pending or has not been received"
+        ),
+        Err(error) => return handle_rpc_error(error),
+    };
+
+    let (transaction_type, actual_fee, finality_status, block_number, events, execution_result) =
+        match receipt {
+            TransactionReceipt::Invoke(receipt) => (
+                "invoke",
+                receipt.actual_fee,
+                receipt.finality_status,
+                receipt.block_number,
+                receipt.events,
+                receipt.execution_result,
+            ),
+            TransactionReceipt::L1Handler(receipt) => (
+                "l1_handler",
+                receipt.actual_fee,
+                receipt.finality_status,
+                receipt.block_number,
+                receipt.events,
+                receipt.execution_result,
+            ),
+            TransactionReceipt::Declare(receipt) => (
+                "declare",
+                receipt.actual_fee,
+                receipt.finality_status,
+                receipt.block_number,
+                receipt.events,
+                receipt.execution_result,
+            ),
+            TransactionReceipt::Deploy(receipt) => (
+                "deploy",
+                receipt.actual_fee,
+                receipt.finality_status,
+                receipt.block_number,
+                receipt.events,
+                receipt.execution_result,
+            ),
+            TransactionReceipt::DeployAccount(receipt) => (
+                "deploy_account",
+                receipt.actual_fee,
+                receipt.finality_status,
+                receipt.block_number,
+                receipt.events,
+                receipt.execution_result,
+            ),
+        };
+
+    let (execution_status, revert_reason) = match execution_result {
+        ExecutionResult::Succeeded => ("succeeded", None),
+        ExecutionResult::Reverted { reason } => ("reverted", Some(reason)),
+    };
+
+    Ok(TransactionReceiptResponse {
+        transaction_type: transaction_type.to_string(),
+        finality_status: match finality_status {
+            TransactionFinalityStatus::AcceptedOnL2 => "accepted_on_l2",
+            TransactionFinalityStatus::AcceptedOnL1 => "accepted_on_l1",
+        }
+        .to_string(),
+        execution_status: execution_status.to_string(),
+        revert_reason,
+        actual_fee: u64::try_from(actual_fee)
+            .map_err(|_| anyhow!("Actual fee {actual_fee:#x} does not fit in u64"))
+            .context("Failed to parse transaction receipt")?,
+        block_number,
+        events: events
+            .into_iter()
+            .map(|event| EventResponse {
+                from_address: event.from_address,
+                keys: event.keys,
+                data: event.data,
+            })
+            .collect(),
+    })
+}
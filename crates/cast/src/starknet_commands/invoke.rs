@@ -1,68 +1,196 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use clap::Args;
 
-use cast::helpers::response_structs::InvokeResponse;
-use cast::{handle_rpc_error, handle_wait_for_tx};
-use starknet::accounts::AccountError::Provider;
-use starknet::accounts::{Account, Call, ConnectedAccount, SingleOwnerAccount};
-use starknet::core::types::FieldElement;
-use starknet::core::utils::get_selector_from_name;
-use starknet::providers::jsonrpc::HttpTransport;
+use crate::helpers::calldata::{parse_calldata_values, CalldataArgs};
+use crate::helpers::constants::DRY_RUN_MESSAGE;
+use crate::helpers::response_structs::{
+    DryRunResponse, EventResponse, FeeEstimateResponse, InvocationResponse, InvokeResponse,
+    SimulateResponse,
+};
+use crate::helpers::retry_transport::RetryTransport;
+use crate::helpers::signer::CastSigner;
+use crate::{
+    apply_fee_multiplier, ensure_balance_covers_fee, get_entry_point_selector, handle_rpc_error,
+    handle_wait_for_tx, max_fee_from_estimate, parse_number, sign_transaction_hash, WaitForTx,
+};
+use starknet::accounts::AccountError::{Provider, Signing};
+use starknet::accounts::{Account, Call, ConnectedAccount, ExecutionEncoder, SingleOwnerAccount};
+use starknet::core::types::{
+    BlockId, ExecuteInvocation, FeeEstimate, FieldElement, FunctionInvocation,
+    SimulatedTransaction, TransactionTrace,
+};
 use starknet::providers::JsonRpcClient;
-use starknet::signers::LocalWallet;
 
 #[derive(Args)]
 #[command(about = "Invoke a contract on Starknet")]
 pub struct Invoke {
     /// Address of contract to invoke
-    #[clap(short = 'a', long)]
-    pub contract_address: FieldElement,
+    #[clap(short = 'a', long, required_unless_present = "calls")]
+    pub contract_address: Option<FieldElement>,
 
     /// Name of the function to invoke
-    #[clap(short, long)]
-    pub function: String,
+    #[clap(short, long, required_unless_present_any = ["selector", "calls"])]
+    pub function: Option<String>,
+
+    /// Call in `<CONTRACT_ADDRESS>:<FUNCTION>[:<CALLDATA>]` format, with calldata felts separated by commas;
+    /// can be passed multiple times to execute all the calls atomically in a single transaction
+    #[clap(
+        long = "call",
+        value_parser = parse_call,
+        conflicts_with_all = ["contract_address", "function", "selector", "calldata", "calldata_file", "arguments", "arguments_json", "interactive"]
+    )]
+    pub calls: Vec<Call>,
+
+    /// Selector of the function to invoke (hex), used as is instead of hashing a function name
+    #[clap(long, conflicts_with = "function")]
+    pub selector: Option<FieldElement>,
 
-    /// Calldata for the invoked function
-    #[clap(short, long, value_delimiter = ' ', num_args = 1..)]
-    pub calldata: Vec<FieldElement>,
+    #[clap(flatten)]
+    pub calldata_args: CalldataArgs,
 
     /// Max fee for the transaction. If not provided, max fee will be automatically estimated
     #[clap(short, long)]
     pub max_fee: Option<FieldElement>,
+
+    /// Nonce of the transaction. If not provided, nonce will be set automatically
+    #[clap(short, long)]
+    pub nonce: Option<FieldElement>,
+
+    /// If passed, only the fee of the transaction will be estimated, without sending it
+    #[clap(long)]
+    pub estimate_only: bool,
+
+    /// If passed, the transaction is only simulated: its fee, execution trace with internal calls and events,
+    /// and state diff are printed
+    #[clap(long, conflicts_with = "estimate_only")]
+    pub simulate: bool,
+
+    /// Block identifier on which fee estimation (`--estimate-only` or when `--max-fee` is not passed) should be performed;
+    /// transaction itself is always sent against the pending block.
+    /// Possible values: pending, latest, block hash (0x prefixed string)
+    /// and block number (u64)
+    #[clap(short, long, default_value = "pending")]
+    pub block_id: String,
+}
+
+fn parse_call(value: &str) -> Result<Call> {
+    let mut parts = value.splitn(3, ':');
+    let (Some(contract_address), Some(function)) = (parts.next(), parts.next()) else {
+        bail!("Invalid call {value}, expected <CONTRACT_ADDRESS>:<FUNCTION>[:<CALLDATA>]");
+    };
+    if function.is_empty() {
+        bail!("Invalid call {value}, function name is missing");
+    }
+    let calldata = parts.next().unwrap_or_default().replace(',', " ");
+
+    Ok(Call {
+        to: parse_number(contract_address)
+            .with_context(|| format!("Invalid contract address {contract_address}"))?,
+        selector: get_entry_point_selector(Some(function), None)?,
+        calldata: parse_calldata_values(&[calldata])?,
+    })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn invoke(
     contract_address: FieldElement,
-    entry_point_name: &str,
+    selector: FieldElement,
     calldata: Vec<FieldElement>,
     max_fee: Option<FieldElement>,
-    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
-    wait: bool,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+    block_id: BlockId,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    wait_config: WaitForTx,
 ) -> Result<InvokeResponse> {
     let call = Call {
         to: contract_address,
-        selector: get_selector_from_name(entry_point_name)?,
+        selector,
+        calldata,
+    };
+
+    execute_calls(
+        account,
+        vec![call],
+        max_fee,
+        fee_multiplier,
+        nonce,
+        block_id,
+        wait_config,
+    )
+    .await
+}
+
+pub async fn estimate_invoke(
+    contract_address: FieldElement,
+    selector: FieldElement,
+    calldata: Vec<FieldElement>,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+) -> Result<FeeEstimateResponse> {
+    let call = Call {
+        to: contract_address,
+        selector,
+        calldata,
+    };
+
+    estimate_calls(account, vec![call], max_fee, fee_multiplier).await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn dry_run_invoke(
+    contract_address: FieldElement,
+    selector: FieldElement,
+    calldata: Vec<FieldElement>,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    signer: &CastSigner,
+) -> Result<DryRunResponse> {
+    let call = Call {
+        to: contract_address,
+        selector,
         calldata,
     };
 
-    execute_calls(account, vec![call], max_fee, wait).await
+    dry_run_calls(account, signer, vec![call], max_fee, fee_multiplier, nonce).await
 }
 
 pub async fn execute_calls(
-    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
     calls: Vec<Call>,
     max_fee: Option<FieldElement>,
-    wait: bool,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+    block_id: BlockId,
+    wait_config: WaitForTx,
 ) -> Result<InvokeResponse> {
-    let execution = account.execute(calls);
+    let max_fee = match max_fee {
+        Some(max_fee) => max_fee,
+        None => {
+            let mut estimation_account = account.clone();
+            estimation_account.set_block_id(block_id);
+            let estimation = estimation_account.execute(calls.clone());
+            let estimation = if let Some(nonce) = nonce {
+                estimation.nonce(nonce)
+            } else {
+                estimation
+            };
+            max_fee_from_estimate(estimation.estimate_fee().await, fee_multiplier)?
+        }
+    };
 
-    let execution = if let Some(max_fee) = max_fee {
-        execution.max_fee(max_fee)
+    let execution = account.execute(calls);
+    let execution = if let Some(nonce) = nonce {
+        execution.nonce(nonce)
     } else {
         execution
     };
 
-    match execution.send().await {
+    ensure_balance_covers_fee(account, max_fee).await?;
+    match execution.max_fee(max_fee).send().await {
         Ok(result) => {
             handle_wait_for_tx(
                 account.provider(),
@@ -70,11 +198,169 @@ pub async fn execute_calls(
                 InvokeResponse {
                     transaction_hash: result.transaction_hash,
                 },
-                wait,
+                wait_config,
             )
             .await
         }
         Err(Provider(error)) => handle_rpc_error(error),
+        Err(Signing(error)) => Err(anyhow!("Failed to sign transaction: {error}")),
         _ => Err(anyhow!("Unknown RPC error")),
     }
 }
+
+pub async fn estimate_calls(
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    calls: Vec<Call>,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+) -> Result<FeeEstimateResponse> {
+    match account.execute(calls).estimate_fee().await {
+        Ok(fee_estimate) => Ok(fee_estimate_response(fee_estimate, max_fee, fee_multiplier)),
+        Err(Provider(error)) => handle_rpc_error(error),
+        Err(Signing(error)) => Err(anyhow!("Failed to sign transaction: {error}")),
+        _ => Err(anyhow!("Unknown RPC error")),
+    }
+}
+
+/// Includes the max fee which would be used if `--max-fee` is not passed
+#[must_use]
+pub fn fee_estimate_response(
+    fee_estimate: FeeEstimate,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+) -> FeeEstimateResponse {
+    let auto_max_fee = apply_fee_multiplier(fee_estimate.overall_fee, fee_multiplier);
+    let mut response = FeeEstimateResponse::from(fee_estimate);
+    if max_fee.is_none() {
+        response.max_fee = Some(auto_max_fee);
+    }
+    response
+}
+
+pub async fn dry_run_calls(
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    signer: &CastSigner,
+    calls: Vec<Call>,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+    nonce: Option<FieldElement>,
+) -> Result<DryRunResponse> {
+    let nonce = match nonce {
+        Some(nonce) => nonce,
+        None => match account.get_nonce().await {
+            Ok(nonce) => nonce,
+            Err(error) => return handle_rpc_error(error),
+        },
+    };
+    let calldata = account.encode_calls(&calls);
+    let execution = account.execute(calls).nonce(nonce);
+
+    let max_fee = match max_fee {
+        Some(max_fee) => max_fee,
+        None => max_fee_from_estimate(execution.estimate_fee().await, fee_multiplier)?,
+    };
+
+    let transaction_hash = execution
+        .max_fee(max_fee)
+        .prepared()
+        .map_err(|_| anyhow!("Failed to prepare transaction"))?
+        .transaction_hash(false);
+
+    Ok(DryRunResponse {
+        message: DRY_RUN_MESSAGE.to_string(),
+        // account.execute builds invoke v1 transactions
+        version: FieldElement::ONE,
+        transaction_hash,
+        sender_address: account.address(),
+        nonce,
+        max_fee,
+        signature: sign_transaction_hash(signer, transaction_hash).await?,
+        calldata: Some(calldata),
+        contract_address: None,
+        class_hash: None,
+        compiled_class_hash: None,
+        salt: None,
+    })
+}
+
+/// Simulates the transaction without sending it; fee is not charged, so that the simulation
+/// does not depend on the max fee being high enough
+pub async fn simulate_execution(
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    calls: Vec<Call>,
+    max_fee: Option<FieldElement>,
+    nonce: Option<FieldElement>,
+) -> Result<SimulatedTransaction> {
+    let execution = account.execute(calls);
+    let execution = match nonce {
+        Some(nonce) => execution.nonce(nonce),
+        None => execution,
+    };
+    let execution = match max_fee {
+        Some(max_fee) => execution.max_fee(max_fee),
+        None => execution,
+    };
+
+    match execution.simulate(false, true).await {
+        Ok(simulation) => Ok(simulation),
+        Err(Provider(error)) => handle_rpc_error(error),
+        Err(Signing(error)) => Err(anyhow!("Failed to sign transaction: {error}")),
+        _ => Err(anyhow!("Unknown RPC error")),
+    }
+}
+
+pub async fn simulate_calls(
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    calls: Vec<Call>,
+    max_fee: Option<FieldElement>,
+    nonce: Option<FieldElement>,
+) -> Result<SimulateResponse> {
+    simulate_response(simulate_execution(account, calls, max_fee, nonce).await?)
+}
+
+pub fn simulate_response(simulation: SimulatedTransaction) -> Result<SimulateResponse> {
+    let (trace, revert_reason, state_diff) = match simulation.transaction_trace {
+        TransactionTrace::Invoke(trace) => match trace.execute_invocation {
+            ExecuteInvocation::Success(invocation) => (
+                Some(invocation_response(&invocation)),
+                None,
+                trace.state_diff,
+            ),
+            ExecuteInvocation::Reverted(reverted) => {
+                (None, Some(reverted.revert_reason), trace.state_diff)
+            }
+        },
+        TransactionTrace::Declare(trace) => (None, None, trace.state_diff),
+        _ => bail!("Unexpected trace type returned by the simulation"),
+    };
+
+    Ok(SimulateResponse {
+        gas_consumed: simulation.fee_estimation.gas_consumed,
+        gas_price: simulation.fee_estimation.gas_price,
+        overall_fee: simulation.fee_estimation.overall_fee,
+        contract_address: None,
+        class_hash: None,
+        revert_reason,
+        trace,
+        state_diff,
+    })
+}
+
+fn invocation_response(invocation: &FunctionInvocation) -> InvocationResponse {
+    InvocationResponse {
+        contract_address: invocation.contract_address,
+        entry_point_selector: invocation.entry_point_selector,
+        calldata: invocation.calldata.clone(),
+        result: invocation.result.clone(),
+        events: invocation
+            .events
+            .iter()
+            .map(|event| EventResponse {
+                from_address: invocation.contract_address,
+                keys: event.keys.clone(),
+                data: event.data.clone(),
+            })
+            .collect(),
+        calls: invocation.calls.iter().map(invocation_response).collect(),
+    }
+}
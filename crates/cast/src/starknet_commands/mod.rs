@@ -1,8 +1,17 @@
 pub mod account;
+pub mod balance;
 pub mod call;
+pub mod class_hash_at;
+pub mod completions;
 pub mod declare;
 pub mod deploy;
+pub mod get_transaction_receipt;
 pub mod invoke;
 pub mod multicall;
+pub mod networks;
+pub mod nonce;
 pub mod script;
 pub mod show_config;
+pub mod tx_status;
+pub mod verify;
+pub mod verify_signature;
@@ -1,7 +1,7 @@
+use crate::helpers::constants::DEFAULT_MULTICALL_CONTENTS;
+use crate::helpers::response_structs::MulticallNewResponse;
 use anyhow::{bail, Result};
 use camino::Utf8PathBuf;
-use cast::helpers::constants::DEFAULT_MULTICALL_CONTENTS;
-use cast::helpers::response_structs::MulticallNewResponse;
 use clap::Args;
 
 #[derive(Args, Debug)]
@@ -30,7 +30,7 @@ pub fn new(output_path: &Utf8PathBuf, overwrite: bool) -> Result<MulticallNewRes
     std::fs::write(output_path.clone(), DEFAULT_MULTICALL_CONTENTS)?;
 
     Ok(MulticallNewResponse {
-        path: output_path.clone(),
+        path: Some(output_path.clone()),
         content: DEFAULT_MULTICALL_CONTENTS.to_string(),
     })
 }
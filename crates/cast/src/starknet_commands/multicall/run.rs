@@ -1,18 +1,28 @@
-use crate::starknet_commands::invoke::execute_calls;
-use anyhow::{anyhow, Context, Result};
+use crate::helpers::response_structs::{
+    DryRunResponse, EventResponse, MulticallRunResponse, MulticallSimulateResponse,
+    MulticallStepResponse, SimulatedCallResponse, StepStatus,
+};
+use crate::helpers::retry_transport::RetryTransport;
+use crate::helpers::signer::CastSigner;
+use crate::starknet_commands::deploy::{predicted_contract_address, udc_deploy_call};
+use crate::starknet_commands::invoke::{dry_run_calls, execute_calls, simulate_execution};
+use crate::{
+    extract_or_generate_salt, handle_rpc_error, parse_number, print_command_result,
+    print_formatted, write_to_output, ValueFormat, WaitForTx,
+};
+use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
-use cast::helpers::constants::UDC_ADDRESS;
-use cast::helpers::response_structs::InvokeResponse;
-use cast::{extract_or_generate_salt, parse_number, udc_uniqueness};
 use clap::Args;
 use serde::Deserialize;
-use starknet::accounts::{Account, Call, SingleOwnerAccount};
-use starknet::core::types::FieldElement;
-use starknet::core::utils::{get_selector_from_name, get_udc_deployed_address};
-use starknet::providers::jsonrpc::HttpTransport;
+use serde_json::Value;
+use starknet::accounts::{Account, Call, ConnectedAccount, SingleOwnerAccount};
+use starknet::core::types::{
+    BlockId, BlockTag::Pending, ExecuteInvocation, FieldElement, FunctionInvocation,
+    InvokeTransactionTrace, TransactionTrace,
+};
+use starknet::core::utils::get_selector_from_name;
 use starknet::providers::JsonRpcClient;
-use starknet::signers::LocalWallet;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Args, Debug)]
 #[command(about = "Execute a multicall from a .toml file", long_about = None)]
@@ -24,6 +34,15 @@ pub struct Run {
     /// Max fee for the transaction. If not provided, max fee will be automatically estimated
     #[clap(short, long)]
     pub max_fee: Option<FieldElement>,
+
+    /// If passed, the transaction is only simulated: its fee, state diff and events emitted by every call are printed
+    #[clap(long)]
+    pub simulate: bool,
+
+    /// If passed, every call is sent in a separate transaction and calls following a failed one are still sent,
+    /// except for those depending on failed calls; the status of every call is reported
+    #[clap(long, conflicts_with = "simulate")]
+    pub continue_on_error: bool,
 }
 
 #[allow(dead_code)]
@@ -46,20 +65,526 @@ struct InvokeCall {
     inputs: Vec<String>,
 }
 
+/// Calls are sent in a single transaction, unless some of them declare `depends_on` - then they are split
+/// into groups sent in separate transactions, each group after the groups it depends on.
+/// With `continue_on_error`, every call is sent in a separate transaction and a failed transaction does not stop
+/// the run: calls depending on calls which failed are skipped, and the status of every call is returned
 pub async fn run(
     path: &Utf8PathBuf,
-    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
     max_fee: Option<FieldElement>,
-    wait: bool,
-) -> Result<InvokeResponse> {
+    fee_multiplier: f64,
+    wait_config: WaitForTx,
+    continue_on_error: bool,
+) -> Result<MulticallRunResponse> {
+    let groups = parse_call_groups(path, account.address(), continue_on_error)?;
+    let groups_count = groups.len();
+
+    // without waiting, the next transaction may be sent before the previous one is in the pending block,
+    // so nonces are assigned upfront
+    let mut nonce = if groups_count > 1 && !wait_config.wait {
+        match account.get_nonce().await {
+            Ok(nonce) => Some(nonce),
+            Err(error) => return handle_rpc_error(error),
+        }
+    } else {
+        None
+    };
+
+    let mut transaction_hashes = vec![];
+    let mut steps = vec![];
+    // calls which failed or were skipped
+    let mut unsuccessful_steps = HashSet::new();
+    for (index, group) in groups.into_iter().enumerate() {
+        if let Some(dependency) = group
+            .dependencies
+            .iter()
+            .find(|dependency| unsuccessful_steps.contains(*dependency))
+        {
+            eprintln!(
+                "Skipping transaction {} of {groups_count}, as it depends on call {dependency} which was not executed successfully",
+                index + 1
+            );
+            let error = format!("Depends on call {dependency} which was not executed successfully");
+            unsuccessful_steps.extend(group.steps.iter().map(|(step, _)| *step));
+            steps.extend(step_responses(
+                group.steps,
+                StepStatus::Skipped,
+                None,
+                Some(error),
+            ));
+            continue;
+        }
+
+        if groups_count > 1 {
+            eprintln!(
+                "Sending transaction {} of {groups_count} with {} call(s)",
+                index + 1,
+                group.calls.len()
+            );
+        }
+        let result = execute_calls(
+            account,
+            group.calls,
+            max_fee,
+            fee_multiplier,
+            nonce,
+            BlockId::Tag(Pending),
+            wait_config,
+        )
+        .await;
+
+        match result {
+            Ok(response) => {
+                transaction_hashes.push(response.transaction_hash);
+                nonce = nonce.map(|nonce| nonce + FieldElement::ONE);
+                steps.extend(step_responses(
+                    group.steps,
+                    StepStatus::Succeeded,
+                    Some(response.transaction_hash),
+                    None,
+                ));
+            }
+            // without `--wait`, a failed transaction has not been sent, so its nonce is used by the next one
+            Err(error) if continue_on_error => {
+                unsuccessful_steps.extend(group.steps.iter().map(|(step, _)| *step));
+                steps.extend(step_responses(
+                    group.steps,
+                    StepStatus::Failed,
+                    None,
+                    Some(format!("{error:#}")),
+                ));
+            }
+            Err(error) => {
+                return Err(error).with_context(|| {
+                    if transaction_hashes.is_empty() {
+                        String::from("Failed to send the multicall transaction")
+                    } else {
+                        format!(
+                            "Failed to send transaction {} of {groups_count}, already sent transactions: {}",
+                            index + 1,
+                            format_hashes(&transaction_hashes)
+                        )
+                    }
+                });
+            }
+        }
+    }
+    steps.sort_by_key(|step| step.step);
+
+    Ok(MulticallRunResponse {
+        transaction_hash: transaction_hashes.last().copied(),
+        transaction_hashes: (groups_count > 1).then_some(transaction_hashes),
+        steps: continue_on_error.then_some(steps),
+    })
+}
+
+fn step_responses(
+    steps: Vec<(usize, Option<String>)>,
+    status: StepStatus,
+    transaction_hash: Option<FieldElement>,
+    error: Option<String>,
+) -> impl Iterator<Item = MulticallStepResponse> {
+    steps
+        .into_iter()
+        .map(move |(step, id)| MulticallStepResponse {
+            step,
+            id,
+            status,
+            transaction_hash,
+            error: error.clone(),
+        })
+}
+
+/// With `--continue-on-error`, the status of every call is printed as a table, as it can't be displayed
+/// as flat key-value pairs like results of other commands
+pub fn print_run_result(
+    result: &Result<MulticallRunResponse>,
+    value_format: ValueFormat,
+    json: bool,
+) -> Result<()> {
+    let (response, steps) = match result {
+        Ok(
+            response @ MulticallRunResponse {
+                steps: Some(steps), ..
+            },
+        ) => (response, steps),
+        _ => return print_command_result("multicall run", result, value_format, json),
+    };
+
+    let mut output = vec![("command", Value::String(String::from("multicall run")))];
+    if json {
+        let response = value_format.format_json_structure(&serde_json::to_value(response)?);
+        output.extend(
+            response
+                .as_object()
+                .expect("Invalid JSON value")
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.clone())),
+        );
+        return print_formatted(output, true, false);
+    }
+
+    if let Some(transaction_hash) = response.transaction_hash {
+        output.push((
+            "transaction_hash",
+            Value::String(value_format.format_str(&format!("{transaction_hash:#x}"))),
+        ));
+    }
+    print_formatted(output, false, false)?;
+    write_to_output(
+        format!("{:<6}{:<24}{:<11}result", "step", "id", "status"),
+        false,
+    );
+    for step in steps {
+        let status = match step.status {
+            StepStatus::Succeeded => "succeeded",
+            StepStatus::Failed => "failed",
+            StepStatus::Skipped => "skipped",
+        };
+        let outcome = match (&step.transaction_hash, &step.error) {
+            (Some(transaction_hash), _) => {
+                value_format.format_str(&format!("{transaction_hash:#x}"))
+            }
+            (None, Some(error)) => error.replace('\n', " "),
+            (None, None) => String::new(),
+        };
+        write_to_output(
+            format!(
+                "{:<6}{:<24}{:<11}{outcome}",
+                step.step,
+                step.id.as_deref().unwrap_or("-"),
+                status
+            ),
+            false,
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn dry_run(
+    path: &Utf8PathBuf,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    signer: &CastSigner,
+    max_fee: Option<FieldElement>,
+    fee_multiplier: f64,
+) -> Result<DryRunResponse> {
+    let mut groups = parse_call_groups(path, account.address(), false)?;
+    if groups.len() > 1 {
+        bail!("--dry-run is not supported for calls split into multiple transactions with `depends_on`");
+    }
+    dry_run_calls(
+        account,
+        signer,
+        groups.remove(0).calls,
+        max_fee,
+        fee_multiplier,
+        None,
+    )
+    .await
+}
+
+/// Simulates the transaction with all calls without sending it. If the transaction is reverted,
+/// calls are simulated again one by one to find the first call which fails
+pub async fn simulate(
+    path: &Utf8PathBuf,
+    account: &SingleOwnerAccount<&JsonRpcClient<RetryTransport>, CastSigner>,
+    max_fee: Option<FieldElement>,
+) -> Result<MulticallSimulateResponse> {
+    let mut groups = parse_call_groups(path, account.address(), false)?;
+    if groups.len() > 1 {
+        bail!("--simulate is not supported for calls split into multiple transactions with `depends_on`");
+    }
+    let calls = groups.remove(0).calls;
+
+    let nonce = match account.get_nonce().await {
+        Ok(nonce) => nonce,
+        Err(error) => return handle_rpc_error(error),
+    };
+    let simulation = simulate_execution(account, calls.clone(), max_fee, Some(nonce)).await?;
+    let TransactionTrace::Invoke(trace) = simulation.transaction_trace else {
+        bail!("Unexpected trace type returned by the simulation of an invoke transaction");
+    };
+
+    let (call_results, reverted_call, revert_reason) = match trace.execute_invocation {
+        ExecuteInvocation::Success(invocation) => (
+            calls
+                .iter()
+                .enumerate()
+                .map(|(index, call)| simulated_call_response(call, invocation.calls.get(index)))
+                .collect(),
+            None,
+            None,
+        ),
+        ExecuteInvocation::Reverted(reverted) => {
+            let mut reverted_call = None;
+            for index in 0..calls.len() {
+                let simulation =
+                    simulate_execution(account, calls[..=index].to_vec(), max_fee, Some(nonce))
+                        .await?;
+                if let TransactionTrace::Invoke(InvokeTransactionTrace {
+                    execute_invocation: ExecuteInvocation::Reverted(_),
+                    ..
+                }) = simulation.transaction_trace
+                {
+                    reverted_call = Some(index);
+                    break;
+                }
+            }
+            (
+                calls
+                    .iter()
+                    .map(|call| simulated_call_response(call, None))
+                    .collect(),
+                reverted_call,
+                Some(reverted.revert_reason),
+            )
+        }
+    };
+
+    Ok(MulticallSimulateResponse {
+        gas_consumed: simulation.fee_estimation.gas_consumed,
+        gas_price: simulation.fee_estimation.gas_price,
+        overall_fee: simulation.fee_estimation.overall_fee,
+        calls: call_results,
+        reverted_call,
+        revert_reason,
+        state_diff: trace.state_diff,
+    })
+}
+
+/// Events are taken from the invocation of the call made by the account's `__execute__`
+fn simulated_call_response(
+    call: &Call,
+    invocation: Option<&FunctionInvocation>,
+) -> SimulatedCallResponse {
+    let mut events = vec![];
+    if let Some(invocation) = invocation {
+        collect_events(invocation, &mut events);
+    }
+    SimulatedCallResponse {
+        contract_address: call.to,
+        selector: call.selector,
+        events,
+    }
+}
+
+fn collect_events(invocation: &FunctionInvocation, events: &mut Vec<EventResponse>) {
+    events.extend(invocation.events.iter().map(|event| EventResponse {
+        from_address: invocation.contract_address,
+        keys: event.keys.clone(),
+        data: event.data.clone(),
+    }));
+    for inner_invocation in &invocation.calls {
+        collect_events(inner_invocation, events);
+    }
+}
+
+fn format_hashes(hashes: &[FieldElement]) -> String {
+    hashes
+        .iter()
+        .map(|hash| format!("{hash:#x}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Fields of the calls that can be referenced by later calls with `$step<index>.<field>`.
+/// Values are resolved before sending any transaction, so only values known upfront are available
+type StepOutputs = Vec<(&'static str, FieldElement)>;
+
+/// Calls sent in a single transaction
+#[derive(Default)]
+struct CallGroup {
+    calls: Vec<Call>,
+    /// Positions of the calls in the file with their ids
+    steps: Vec<(usize, Option<String>)>,
+    /// Positions of the calls from earlier groups which the calls depend on or reference
+    dependencies: Vec<usize>,
+}
+
+/// With `one_call_per_group`, every call is in a separate group, so that independent calls are sent
+/// in separate transactions; groups are ordered so that every call follows the calls it depends on
+
+/// Parses the calls and splits them into groups sent in separate transactions, in dependency order
+fn parse_call_groups(
+    path: &Utf8PathBuf,
+    account_address: FieldElement,
+    one_call_per_group: bool,
+) -> Result<Vec<CallGroup>> {
     let contents = std::fs::read_to_string(path)?;
     let items_map: HashMap<String, Vec<toml::Value>> =
         toml::from_str(&contents).map_err(|_| anyhow!("Failed to parse {path}"))?;
+    let calls = items_map.get("call").cloned().unwrap_or_default();
+
+    let parsed_calls = parse_calls(&calls, account_address)?;
+    if parsed_calls.is_empty() {
+        bail!("No calls found in {path}");
+    }
+    let dependencies = call_dependencies(&calls)?;
+    let mut levels = dependency_levels(&dependencies, &calls)?;
+    if one_call_per_group {
+        levels = separate_levels(&levels);
+    }
+
+    let groups_count = levels.iter().max().map_or(0, |max| max + 1);
+    let mut groups: Vec<CallGroup> = (0..groups_count).map(|_| CallGroup::default()).collect();
+    for (index, call) in parsed_calls.into_iter().enumerate() {
+        let group = &mut groups[levels[index]];
+        let id = calls[index]
+            .get("id")
+            .and_then(toml::Value::as_str)
+            .map(str::to_string);
+        group.calls.push(call);
+        group.steps.push((index, id));
+
+        let mut references = vec![];
+        collect_step_references(&calls[index], &mut references);
+        // calls from the same group are sent in one transaction, so they succeed or fail together
+        for dependency in dependencies[index].iter().chain(&references) {
+            if levels[*dependency] < levels[index] {
+                group.dependencies.push(*dependency);
+            }
+        }
+    }
+    Ok(groups)
+}
+
+/// Collects positions of the calls referenced with `$step<index>.<field>` or `${prev.<index>.output}`
+fn collect_step_references(value: &toml::Value, references: &mut Vec<usize>) {
+    match value {
+        toml::Value::String(string) => {
+            for prefix in ["$step", "${prev."] {
+                for (position, _) in string.match_indices(prefix) {
+                    let index: String = string[position + prefix.len()..]
+                        .chars()
+                        .take_while(char::is_ascii_digit)
+                        .collect();
+                    if let Ok(index) = index.parse() {
+                        references.push(index);
+                    }
+                }
+            }
+        }
+        toml::Value::Array(array) => {
+            for item in array {
+                collect_step_references(item, references);
+            }
+        }
+        toml::Value::Table(table) => {
+            for item in table.values() {
+                collect_step_references(item, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the positions of the calls listed in `depends_on` of every call
+fn call_dependencies(calls: &[toml::Value]) -> Result<Vec<Vec<usize>>> {
+    let mut ids = HashMap::new();
+    for (index, call) in calls.iter().enumerate() {
+        if let Some(id) = call.get("id").and_then(toml::Value::as_str) {
+            if ids.insert(id, index).is_some() {
+                bail!("Duplicate call id {id}");
+            }
+        }
+    }
+
+    let mut dependencies = vec![];
+    for (index, call) in calls.iter().enumerate() {
+        let depends_on = match call.get("depends_on") {
+            None => vec![],
+            Some(toml::Value::Array(depends_on)) => depends_on
+                .iter()
+                .map(|id| {
+                    let id = id.as_str().ok_or_else(|| {
+                        anyhow!("`depends_on` of call {index} must be a list of call ids")
+                    })?;
+                    ids.get(id)
+                        .copied()
+                        .ok_or_else(|| anyhow!("Call {index} depends on unknown call id {id}"))
+                })
+                .collect::<Result<Vec<usize>>>()?,
+            Some(_) => bail!("`depends_on` of call {index} must be a list of call ids"),
+        };
+        dependencies.push(depends_on);
+    }
+    Ok(dependencies)
+}
 
+/// Returns the index of the transaction group of every call: calls without dependencies are in group 0,
+/// and other calls are in the group following the last group of their dependencies
+fn dependency_levels(dependencies: &[Vec<usize>], calls: &[toml::Value]) -> Result<Vec<usize>> {
+    let mut levels = vec![None; calls.len()];
+    for index in 0..calls.len() {
+        resolve_level(index, dependencies, &mut levels, &mut vec![], calls)?;
+    }
+    Ok(levels
+        .into_iter()
+        .map(|level| level.expect("all levels are resolved"))
+        .collect())
+}
+
+/// Assigns a separate level to every call, keeping calls ordered by their levels and then by their positions
+fn separate_levels(levels: &[usize]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    order.sort_by_key(|index| levels[*index]);
+
+    let mut separated = vec![0; levels.len()];
+    for (level, index) in order.into_iter().enumerate() {
+        separated[index] = level;
+    }
+    separated
+}
+
+fn resolve_level(
+    index: usize,
+    dependencies: &[Vec<usize>],
+    levels: &mut [Option<usize>],
+    path: &mut Vec<usize>,
+    calls: &[toml::Value],
+) -> Result<usize> {
+    if let Some(level) = levels[index] {
+        return Ok(level);
+    }
+    if let Some(position) = path.iter().position(|visited| *visited == index) {
+        let cycle: Vec<String> = path[position..]
+            .iter()
+            .chain([&index])
+            .map(|index| call_name(*index, calls))
+            .collect();
+        bail!("Dependency cycle between calls: {}", cycle.join(" -> "));
+    }
+
+    path.push(index);
+    let mut level = 0;
+    for dependency in &dependencies[index] {
+        level = level.max(resolve_level(*dependency, dependencies, levels, path, calls)? + 1);
+    }
+    path.pop();
+
+    levels[index] = Some(level);
+    Ok(level)
+}
+
+fn call_name(index: usize, calls: &[toml::Value]) -> String {
+    calls[index]
+        .get("id")
+        .and_then(toml::Value::as_str)
+        .map_or_else(|| format!("call {index}"), str::to_string)
+}
+
+fn parse_calls(calls: &[toml::Value], account_address: FieldElement) -> Result<Vec<Call>> {
     let mut contracts = HashMap::new();
+    let mut steps: Vec<StepOutputs> = vec![];
+    // addresses of contracts deployed by the calls, referenced with `${prev.<index>.output}`
+    let mut outputs: Vec<Option<FieldElement>> = vec![];
     let mut parsed_calls: Vec<Call> = vec![];
 
-    for call in items_map.get("call").unwrap_or(&vec![]) {
+    for (index, call) in calls.iter().enumerate() {
+        let call = &substitute_variables(call, &outputs)
+            .with_context(|| format!("Failed to substitute variables in call {index}"))?;
         let call_type = call.get("call_type");
         if call_type.is_none() {
             anyhow::bail!("`call_type` field is missing in a call specification");
@@ -71,46 +596,53 @@ pub async fn run(
                     .map_err(|_| anyhow!("Failed to parse toml `deploy` call"))?;
 
                 let salt = extract_or_generate_salt(deploy_call.salt);
-                let mut calldata = vec![
+                let parsed_inputs = parse_inputs(&deploy_call.inputs, &contracts, &steps)?;
+
+                parsed_calls.push(udc_deploy_call(
                     deploy_call.class_hash,
                     salt,
-                    FieldElement::from(u8::from(deploy_call.unique)),
-                    deploy_call.inputs.len().into(),
-                ];
-
-                let parsed_inputs = parse_inputs(&deploy_call.inputs, &contracts)?;
-                calldata.extend(&parsed_inputs);
-
-                parsed_calls.push(Call {
-                    to: parse_number(UDC_ADDRESS)?,
-                    selector: get_selector_from_name("deployContract")?,
-                    calldata,
-                });
+                    deploy_call.unique,
+                    &parsed_inputs,
+                )?);
 
-                let contract_address = get_udc_deployed_address(
-                    salt,
+                let contract_address = predicted_contract_address(
                     deploy_call.class_hash,
-                    &udc_uniqueness(deploy_call.unique, account.address()),
+                    salt,
+                    deploy_call.unique,
                     &parsed_inputs,
+                    account_address,
                 );
                 contracts.insert(deploy_call.id, contract_address.to_string());
+                outputs.push(Some(contract_address));
+                steps.push(vec![
+                    ("contract_address", contract_address),
+                    ("class_hash", deploy_call.class_hash),
+                    ("salt", salt),
+                ]);
             }
             Some("invoke") => {
                 let invoke_call: InvokeCall = toml::from_str(toml::to_string(&call)?.as_str())
                     .context("failed to parse toml `invoke` call")?;
-                let mut contract_address = &invoke_call.contract_address;
-                if let Some(addr) = contracts.get(&invoke_call.contract_address) {
-                    contract_address = addr;
-                }
+                let contract_address =
+                    match resolve_step_reference(&invoke_call.contract_address, &steps)? {
+                        Some(contract_address) => contract_address,
+                        None => parse_number(
+                            contracts
+                                .get(&invoke_call.contract_address)
+                                .unwrap_or(&invoke_call.contract_address),
+                        )
+                        .context("Unable to parse contract address to FieldElement")?,
+                    };
 
-                let calldata = parse_inputs(&invoke_call.inputs, &contracts)?;
+                let calldata = parse_inputs(&invoke_call.inputs, &contracts, &steps)?;
 
                 parsed_calls.push(Call {
-                    to: parse_number(contract_address)
-                        .context("Unable to parse contract address to FieldElement")?,
+                    to: contract_address,
                     selector: get_selector_from_name(&invoke_call.function)?,
                     calldata,
                 });
+                steps.push(vec![("contract_address", contract_address)]);
+                outputs.push(None);
             }
             Some(unsupported) => {
                 anyhow::bail!("unsupported call type found: {}", unsupported);
@@ -119,15 +651,20 @@ pub async fn run(
         }
     }
 
-    execute_calls(account, parsed_calls, max_fee, wait).await
+    Ok(parsed_calls)
 }
 
 fn parse_inputs(
     inputs: &Vec<String>,
     contracts: &HashMap<String, String>,
+    steps: &[StepOutputs],
 ) -> Result<Vec<FieldElement>> {
     let mut parsed_inputs = Vec::new();
     for input in inputs {
+        if let Some(value) = resolve_step_reference(input, steps)? {
+            parsed_inputs.push(value);
+            continue;
+        }
         let current_input = contracts.get(input).unwrap_or(input);
         parsed_inputs
             .push(parse_number(current_input).context("Unable to parse input to FieldElement")?);
@@ -135,3 +672,288 @@ fn parse_inputs(
 
     Ok(parsed_inputs)
 }
+
+/// Replaces `${NAME}` in all string values of the call with the value of the environment variable `NAME`,
+/// and `${prev.<index>.output}` with the address of the contract deployed by an earlier `deploy` call
+fn substitute_variables(
+    value: &toml::Value,
+    outputs: &[Option<FieldElement>],
+) -> Result<toml::Value> {
+    Ok(match value {
+        toml::Value::String(string) => {
+            toml::Value::String(substitute_variables_in_str(string, outputs)?)
+        }
+        toml::Value::Array(array) => toml::Value::Array(
+            array
+                .iter()
+                .map(|item| substitute_variables(item, outputs))
+                .collect::<Result<_>>()?,
+        ),
+        toml::Value::Table(table) => toml::Value::Table(
+            table
+                .iter()
+                .map(|(key, item)| Ok((key.clone(), substitute_variables(item, outputs)?)))
+                .collect::<Result<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+fn substitute_variables_in_str(value: &str, outputs: &[Option<FieldElement>]) -> Result<String> {
+    let mut result = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("Unclosed variable in {value}, expected `${{NAME}}`"))?;
+        let name = &rest[start + 2..start + end];
+
+        if let Some(reference) = name.strip_prefix("prev.") {
+            result.push_str(&format!(
+                "{:#x}",
+                resolve_prev_output(name, reference, outputs)?
+            ));
+        } else {
+            let variable = std::env::var(name)
+                .map_err(|_| anyhow!("Environment variable {name} is not set"))?;
+            result.push_str(&variable);
+        }
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+fn resolve_prev_output(
+    name: &str,
+    reference: &str,
+    outputs: &[Option<FieldElement>],
+) -> Result<FieldElement> {
+    let index: usize = reference
+        .strip_suffix(".output")
+        .and_then(|index| index.parse().ok())
+        .ok_or_else(|| {
+            anyhow!("Invalid reference ${{{name}}}, expected `${{prev.<index>.output}}`")
+        })?;
+
+    match outputs.get(index) {
+        Some(Some(output)) => Ok(*output),
+        Some(None) => bail!("Invalid reference ${{{name}}}, call {index} is not a `deploy` call"),
+        None => bail!(
+            "Invalid reference ${{{name}}}, only calls preceding the current one can be referenced"
+        ),
+    }
+}
+
+/// Resolves `$step<index>.<field>` references to the outputs of earlier calls (indexed from 0).
+/// Returns `None` if the value is not a reference
+fn resolve_step_reference(value: &str, steps: &[StepOutputs]) -> Result<Option<FieldElement>> {
+    let Some(reference) = value.strip_prefix("$step") else {
+        return Ok(None);
+    };
+    let (index, field) = reference
+        .split_once('.')
+        .ok_or_else(|| anyhow!("Invalid reference {value}, expected `$step<index>.<field>`"))?;
+    let index: usize = index
+        .parse()
+        .map_err(|_| anyhow!("Invalid reference {value}, expected `$step<index>.<field>`"))?;
+
+    let outputs = steps.get(index).ok_or_else(|| {
+        anyhow!("Invalid reference {value}, only calls preceding the current one can be referenced")
+    })?;
+    outputs
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, output)| Some(*output))
+        .ok_or_else(|| {
+            let available: Vec<&str> = outputs.iter().map(|(name, _)| *name).collect();
+            anyhow!(
+                "Invalid reference {value}, available fields of call {index} are: {}",
+                available.join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        call_dependencies, collect_step_references, dependency_levels, resolve_step_reference,
+        separate_levels, substitute_variables_in_str, StepOutputs,
+    };
+    use starknet::core::types::FieldElement;
+
+    fn calls(contents: &str) -> Vec<toml::Value> {
+        let mut items: std::collections::HashMap<String, Vec<toml::Value>> =
+            toml::from_str(contents).unwrap();
+        items.remove("call").unwrap()
+    }
+
+    fn levels(calls: &[toml::Value]) -> anyhow::Result<Vec<usize>> {
+        dependency_levels(&call_dependencies(calls)?, calls)
+    }
+
+    fn steps() -> Vec<StepOutputs> {
+        vec![
+            vec![
+                ("contract_address", FieldElement::ONE),
+                ("class_hash", FieldElement::TWO),
+                ("salt", FieldElement::THREE),
+            ],
+            vec![("contract_address", FieldElement::TWO)],
+        ]
+    }
+
+    #[test]
+    fn test_resolve_step_reference() {
+        let steps = steps();
+
+        assert_eq!(
+            resolve_step_reference("$step0.class_hash", &steps).unwrap(),
+            Some(FieldElement::TWO)
+        );
+        assert_eq!(
+            resolve_step_reference("$step1.contract_address", &steps).unwrap(),
+            Some(FieldElement::TWO)
+        );
+        assert_eq!(resolve_step_reference("0x123", &steps).unwrap(), None);
+    }
+
+    #[test]
+    fn test_resolve_step_reference_invalid() {
+        let steps = steps();
+
+        let error = resolve_step_reference("$step2.contract_address", &steps).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("only calls preceding the current one can be referenced"));
+
+        let error = resolve_step_reference("$step1.salt", &steps).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("available fields of call 1 are: contract_address"));
+
+        let error = resolve_step_reference("$stepx", &steps).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("expected `$step<index>.<field>`"));
+    }
+
+    #[test]
+    fn test_dependency_levels() {
+        let calls = calls(
+            r#"
+            [[call]]
+            id = "a"
+            [[call]]
+            id = "b"
+            depends_on = ["a"]
+            [[call]]
+            id = "c"
+            [[call]]
+            depends_on = ["b", "c"]
+            "#,
+        );
+
+        assert_eq!(levels(&calls).unwrap(), vec![0, 1, 0, 2]);
+        assert_eq!(separate_levels(&[0, 1, 0, 2]), vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn test_dependency_levels_cycle() {
+        let calls = calls(
+            r#"
+            [[call]]
+            id = "a"
+            depends_on = ["b"]
+            [[call]]
+            id = "b"
+            depends_on = ["a"]
+            "#,
+        );
+
+        let error = levels(&calls).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Dependency cycle between calls: a -> b -> a"
+        );
+    }
+
+    #[test]
+    fn test_dependency_levels_unknown_id() {
+        let calls = calls(
+            r#"
+            [[call]]
+            depends_on = ["a"]
+            "#,
+        );
+
+        let error = levels(&calls).unwrap_err();
+        assert_eq!(error.to_string(), "Call 0 depends on unknown call id a");
+    }
+
+    #[test]
+    fn test_collect_step_references() {
+        let calls = calls(
+            r#"
+            [[call]]
+            contract_address = "${prev.0.output}"
+            inputs = ["0x1", "$step12.contract_address", "${MULTICALL_TEST_ADDRESS}"]
+            "#,
+        );
+        let mut references = vec![];
+        collect_step_references(&calls[0], &mut references);
+        references.sort_unstable();
+
+        assert_eq!(references, vec![0, 12]);
+    }
+
+    #[test]
+    fn test_substitute_variables() {
+        std::env::set_var("MULTICALL_TEST_ADDRESS", "0x123");
+        let outputs = vec![Some(FieldElement::TWO), None];
+
+        assert_eq!(
+            substitute_variables_in_str("${MULTICALL_TEST_ADDRESS}", &outputs).unwrap(),
+            "0x123"
+        );
+        assert_eq!(
+            substitute_variables_in_str("a${prev.0.output}b", &outputs).unwrap(),
+            "a0x2b"
+        );
+        assert_eq!(
+            substitute_variables_in_str("$step0.contract_address", &outputs).unwrap(),
+            "$step0.contract_address"
+        );
+    }
+
+    #[test]
+    fn test_substitute_variables_invalid() {
+        let outputs = vec![Some(FieldElement::TWO), None];
+
+        let error =
+            substitute_variables_in_str("${MULTICALL_TEST_UNSET_VARIABLE}", &outputs).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Environment variable MULTICALL_TEST_UNSET_VARIABLE is not set"
+        );
+
+        let error = substitute_variables_in_str("${prev.1.output}", &outputs).unwrap_err();
+        assert!(error.to_string().contains("call 1 is not a `deploy` call"));
+
+        let error = substitute_variables_in_str("${prev.2.output}", &outputs).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("only calls preceding the current one can be referenced"));
+
+        let error = substitute_variables_in_str("${prev.0}", &outputs).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("expected `${prev.<index>.output}`"));
+
+        let error = substitute_variables_in_str("${UNCLOSED", &outputs).unwrap_err();
+        assert!(error.to_string().contains("Unclosed variable"));
+    }
+}
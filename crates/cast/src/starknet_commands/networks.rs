@@ -0,0 +1,87 @@
+use crate::helpers::constants::{FAUCET_URLS, KNOWN_NETWORKS};
+use crate::helpers::response_structs::{NetworkItem, NetworksResponse};
+use crate::{parse_chain_id, print_command_result, print_formatted, write_to_output, ValueFormat};
+use anyhow::Result;
+use clap::Args;
+use serde_json::Value;
+
+#[derive(Args, Debug)]
+#[command(about = "List known networks and their chain ids")]
+pub struct Networks {}
+
+pub fn networks() -> Result<NetworksResponse> {
+    let networks = KNOWN_NETWORKS
+        .iter()
+        .map(|(name, chain_id)| {
+            let chain_id_felt = parse_chain_id(chain_id)?;
+            Ok(NetworkItem {
+                name: (*name).to_string(),
+                chain_id: (*chain_id).to_string(),
+                chain_id_hex: format!("{chain_id_felt:#x}"),
+                chain_id_int: chain_id_felt.to_string(),
+                faucet_url: FAUCET_URLS
+                    .iter()
+                    .find(|(network, _)| network == name)
+                    .map(|(_, url)| (*url).to_string()),
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(NetworksResponse { networks })
+}
+
+/// Networks are listed with their details, so they can't be displayed as flat key-value pairs like
+/// results of other commands
+pub fn print_networks(
+    result: &Result<NetworksResponse>,
+    value_format: ValueFormat,
+    json: bool,
+) -> Result<()> {
+    let response = match result {
+        Ok(response) => response,
+        Err(_) => return print_command_result("networks", result, value_format, json),
+    };
+
+    if json {
+        return print_formatted(
+            vec![
+                ("command", Value::String(String::from("networks"))),
+                ("networks", serde_json::to_value(&response.networks)?),
+            ],
+            true,
+            false,
+        );
+    }
+
+    write_to_output("command: networks", false);
+    for network in &response.networks {
+        write_to_output(format!("{}:", network.name), false);
+        write_to_output(format!("  chain_id: {}", network.chain_id), false);
+        write_to_output(format!("  chain_id_hex: {}", network.chain_id_hex), false);
+        write_to_output(format!("  chain_id_int: {}", network.chain_id_int), false);
+        if let Some(faucet_url) = &network.faucet_url {
+            write_to_output(format!("  faucet_url: {faucet_url}"), false);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chain_id_to_network_name;
+    use crate::starknet_commands::networks::networks;
+    use starknet::core::types::FieldElement;
+
+    #[test]
+    fn test_networks_match_chain_id_names() {
+        let response = networks().unwrap();
+
+        for network in &response.networks {
+            let chain_id = FieldElement::from_hex_be(&network.chain_id_hex).unwrap();
+            assert_eq!(chain_id_to_network_name(chain_id), network.name);
+            assert_eq!(chain_id.to_string(), network.chain_id_int);
+        }
+        assert_eq!(response.networks[0].chain_id_hex, "0x534e5f474f45524c49");
+    }
+}
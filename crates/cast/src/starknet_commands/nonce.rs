@@ -0,0 +1,32 @@
+use crate::handle_rpc_error;
+use crate::helpers::response_structs::NonceResponse;
+use crate::helpers::retry_transport::RetryTransport;
+use anyhow::Result;
+use clap::Args;
+use starknet::core::types::{BlockId, FieldElement};
+use starknet::providers::{JsonRpcClient, Provider};
+
+#[derive(Args)]
+#[command(about = "Get the nonce of an account", long_about = None)]
+pub struct Nonce {
+    /// Address to get the nonce of; if not passed, address of the account passed with `--account` is used
+    #[clap(long)]
+    pub address: Option<FieldElement>,
+
+    /// Block identifier on which the nonce should be checked.
+    /// Possible values: pending, latest, block hash (0x prefixed string)
+    /// and block number (u64)
+    #[clap(short, long, default_value = "pending")]
+    pub block_id: String,
+}
+
+pub async fn nonce(
+    address: FieldElement,
+    provider: &JsonRpcClient<RetryTransport>,
+    block_id: &BlockId,
+) -> Result<NonceResponse> {
+    match provider.get_nonce(block_id, address).await {
+        Ok(nonce) => Ok(NonceResponse { nonce }),
+        Err(error) => handle_rpc_error(error),
+    }
+}
@@ -3,7 +3,13 @@ use std::collections::HashMap;
 use std::fs;
 
 use crate::get_account;
+use crate::helpers::response_structs::ScriptResponse;
+use crate::helpers::retry_transport::RetryTransport;
+use crate::helpers::scarb_utils::{
+    get_package_metadata, get_scarb_manifest, get_scarb_metadata_with_deps, CastConfig,
+};
 use crate::starknet_commands::{call, declare, deploy, invoke};
+use crate::{get_entry_point_selector, WaitForTx};
 use anyhow::{anyhow, ensure, Context, Result};
 use cairo_felt::Felt252;
 use cairo_lang_casm::hints::{Hint, StarknetHint};
@@ -26,10 +32,6 @@ use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
 use cairo_vm::vm::runners::cairo_runner::{ResourceTracker, RunResources};
 use cairo_vm::vm::vm_core::VirtualMachine;
 use camino::Utf8PathBuf;
-use cast::helpers::response_structs::ScriptResponse;
-use cast::helpers::scarb_utils::{
-    get_package_metadata, get_scarb_manifest, get_scarb_metadata_with_deps, CastConfig,
-};
 use cheatnet::cheatcodes::EnhancedHintError;
 use clap::command;
 use clap::Args;
@@ -38,7 +40,6 @@ use itertools::chain;
 use num_traits::ToPrimitive;
 use scarb_metadata::ScarbCommand;
 use starknet::core::types::{BlockId, BlockTag::Pending, FieldElement};
-use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 use tokio::runtime::Runtime;
 
@@ -51,7 +52,7 @@ pub struct Script {
 
 pub struct CairoHintProcessor<'a> {
     pub hints: &'a HashMap<String, Hint>,
-    pub provider: &'a JsonRpcClient<HttpTransport>,
+    pub provider: &'a JsonRpcClient<RetryTransport>,
     pub runtime: Runtime,
     pub run_resources: RunResources,
     pub config: &'a CastConfig,
@@ -187,12 +188,14 @@ impl CairoHintProcessor<'_> {
                     .map(|el| FieldElement::from_(el.clone()))
                     .collect();
 
+                let selector = get_entry_point_selector(Some(&function_name), None)?;
                 let call_response = self.runtime.block_on(call::call(
                     contract_address,
-                    &function_name,
+                    selector,
                     calldata_felts,
                     self.provider,
                     &BlockId::Tag(Pending),
+                    None,
                 ))?;
 
                 buffer
@@ -215,16 +218,23 @@ impl CairoHintProcessor<'_> {
                 };
                 let account = self.runtime.block_on(get_account(
                     &self.config.account,
-                    &self.config.accounts_file,
+                    &self.config.accounts_files,
                     self.provider,
                     &self.config.keystore,
+                    &self.config.signer_command,
                 ))?;
 
                 let declare_response = self.runtime.block_on(declare::declare(
-                    &contract_name,
+                    &declare::ContractSource::Name(contract_name),
                     max_fee,
+                    self.config.fee_multiplier,
+                    None,
                     &account,
                     &None,
+                    WaitForTx {
+                        wait: true,
+                        wait_params: self.config.wait_params,
+                    },
                     true,
                 ))?;
 
@@ -233,7 +243,11 @@ impl CairoHintProcessor<'_> {
                     .expect("Failed to insert class hash");
 
                 buffer
-                    .write(Felt252::from_(declare_response.transaction_hash))
+                    .write(Felt252::from_(
+                        declare_response
+                            .transaction_hash
+                            .expect("Forced declaration is always sent"),
+                    ))
                     .expect("Failed to insert transaction hash");
 
                 Ok(())
@@ -269,9 +283,10 @@ impl CairoHintProcessor<'_> {
 
                 let account = self.runtime.block_on(get_account(
                     &self.config.account,
-                    &self.config.accounts_file,
+                    &self.config.accounts_files,
                     self.provider,
                     &self.config.keystore,
+                    &self.config.signer_command,
                 ))?;
 
                 let deploy_response = self.runtime.block_on(deploy::deploy(
@@ -280,8 +295,14 @@ impl CairoHintProcessor<'_> {
                     salt,
                     unique,
                     max_fee,
+                    self.config.fee_multiplier,
+                    None,
+                    false,
                     &account,
-                    true,
+                    WaitForTx {
+                        wait: true,
+                        wait_params: self.config.wait_params,
+                    },
                 ))?;
 
                 buffer
@@ -317,18 +338,26 @@ impl CairoHintProcessor<'_> {
 
                 let account = self.runtime.block_on(get_account(
                     &self.config.account,
-                    &self.config.accounts_file,
+                    &self.config.accounts_files,
                     self.provider,
                     &self.config.keystore,
+                    &self.config.signer_command,
                 ))?;
 
+                let selector = get_entry_point_selector(Some(&entry_point_name), None)?;
                 let invoke_response = self.runtime.block_on(invoke::invoke(
                     contract_address,
-                    &entry_point_name,
+                    selector,
                     calldata,
                     max_fee,
+                    self.config.fee_multiplier,
+                    None,
+                    BlockId::Tag(Pending),
                     &account,
-                    true,
+                    WaitForTx {
+                        wait: true,
+                        wait_params: self.config.wait_params,
+                    },
                 ))?;
 
                 buffer
@@ -351,7 +380,7 @@ impl CairoHintProcessor<'_> {
 pub fn run(
     module_name: &str,
     path_to_scarb_toml: &Option<Utf8PathBuf>,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<RetryTransport>,
     runtime: Runtime,
     config: &CastConfig,
 ) -> Result<ScriptResponse> {
@@ -1,42 +1,178 @@
+use crate::helpers::response_structs::ShowConfigResponse;
+use crate::helpers::retry_transport::RetryTransport;
+use crate::helpers::scarb_utils::CastConfig;
+use crate::{chain_id_to_network_name, decode_chain_id, find_account, get_chain_id};
 use anyhow::Result;
 use camino::Utf8PathBuf;
-use cast::helpers::response_structs::ShowConfigResponse;
-use cast::helpers::scarb_utils::CastConfig;
-use cast::{chain_id_to_network_name, get_chain_id};
-use clap::Args;
-use starknet::providers::jsonrpc::HttpTransport;
+use clap::{Args, ValueEnum};
 use starknet::providers::JsonRpcClient;
+use toml::{Table, Value};
 
 #[derive(Args)]
 #[command(about = "Show current configuration being used", long_about = None)]
-pub struct ShowConfig {}
+pub struct ShowConfig {
+    /// Output format; `toml` prints the configuration as a `[tool.sncast]` section which can be pasted into Scarb.toml
+    #[clap(long, value_enum, default_value_t = ShowConfigFormat::Human)]
+    pub format: ShowConfigFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShowConfigFormat {
+    Human,
+    Json,
+    Toml,
+}
 
 #[allow(clippy::ptr_arg)]
 pub async fn show_config(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<RetryTransport>,
     cast_config: CastConfig,
     profile: Option<String>,
     scarb_path: Option<Utf8PathBuf>,
 ) -> Result<ShowConfigResponse> {
     let chain_id_field = get_chain_id(provider).await?;
     let chain_id = chain_id_to_network_name(chain_id_field);
-    let rpc_url = Some(cast_config.rpc_url).filter(|p| !p.is_empty());
+    let resolved_chain_id = decode_chain_id(chain_id_field);
+    let rpc_url = Some(cast_config.rpc_urls.join(", ")).filter(|p| !p.is_empty());
+    let warnings = signer_warnings(&cast_config, &chain_id);
     let account = Some(cast_config.account).filter(|p| !p.is_empty());
-    let mut accounts_file_path =
-        Some(cast_config.accounts_file).filter(|p| p != &Utf8PathBuf::default());
+    let mut accounts_file_path = Some(
+        cast_config
+            .accounts_files
+            .iter()
+            .map(Utf8PathBuf::as_str)
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+    .filter(|p| !p.is_empty());
     let keystore = Some(cast_config.keystore).filter(|p| p != &Utf8PathBuf::default());
+    let signer_command = Some(cast_config.signer_command).filter(|p| !p.is_empty());
 
-    if keystore.is_some() {
+    if keystore.is_some() || signer_command.is_some() {
         accounts_file_path = None;
     }
 
     Ok(ShowConfigResponse {
         profile,
         chain_id,
+        resolved_chain_id,
         rpc_url,
         account,
         scarb_path,
         accounts_file_path,
         keystore,
+        signer_command,
+        warnings,
     })
 }
+
+/// With `--keystore` or `--signer-command`, the account is not read from the accounts file,
+/// which is easy to miss if an account with the same name is defined there
+fn signer_warnings(cast_config: &CastConfig, network_name: &str) -> Vec<String> {
+    if cast_config.account.is_empty() {
+        return vec![];
+    }
+    let signer = if !cast_config.signer_command.is_empty() {
+        "--signer-command"
+    } else if cast_config.keystore != Utf8PathBuf::default() {
+        "--keystore"
+    } else {
+        return vec![];
+    };
+    // errors in the accounts file are reported when the account is actually used
+    let Ok(Some((_, accounts_file))) = find_account(
+        &cast_config.account,
+        network_name,
+        &cast_config.accounts_files,
+    ) else {
+        return vec![];
+    };
+
+    vec![format!(
+        "Account {} is defined in the accounts file {accounts_file}, but {signer} is set, so it takes effect instead: the account from the accounts file is ignored and --account is treated as a path to the starkli JSON account file",
+        cast_config.account
+    )]
+}
+
+/// Serializes the configuration as a `[tool.sncast]` section (`[tool.sncast.<profile>]` if a profile is used).
+/// `signer-command` is omitted, as the command may contain secrets in its arguments
+pub fn show_config_toml(cast_config: &CastConfig, profile: &Option<String>) -> Result<String> {
+    let mut config = Table::new();
+
+    if !cast_config.rpc_urls.is_empty() {
+        config.insert("url".into(), string_or_list(&cast_config.rpc_urls));
+    }
+    if !cast_config.account.is_empty() {
+        config.insert("account".into(), cast_config.account.clone().into());
+    }
+    if !cast_config.accounts_files.is_empty() {
+        let accounts_files: Vec<String> = cast_config
+            .accounts_files
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        config.insert("accounts-file".into(), string_or_list(&accounts_files));
+    }
+    if cast_config.keystore != Utf8PathBuf::default() {
+        config.insert("keystore".into(), cast_config.keystore.to_string().into());
+    }
+    config.insert(
+        "wait-timeout".into(),
+        i64::from(cast_config.wait_params.get_timeout()).into(),
+    );
+    config.insert(
+        "wait-retry-interval".into(),
+        i64::from(cast_config.wait_params.get_retry_interval()).into(),
+    );
+    config.insert(
+        "rpc-retries".into(),
+        i64::from(cast_config.rpc_retries).into(),
+    );
+    config.insert(
+        "rpc-retry-base-delay".into(),
+        i64::from(cast_config.rpc_retry_base_delay).into(),
+    );
+    config.insert("fee-multiplier".into(), cast_config.fee_multiplier.into());
+    config.insert(
+        "default-block-id".into(),
+        cast_config.default_block_id.clone().into(),
+    );
+    config.insert("decode-strings".into(), cast_config.decode_strings.into());
+    if !cast_config.networks.is_empty() {
+        let networks: Table = cast_config
+            .networks
+            .iter()
+            .map(|(name, chain_id)| (format!("{chain_id:#x}"), Value::from(name.clone())))
+            .collect();
+        config.insert("networks".into(), networks.into());
+    }
+    if !cast_config.faucets.is_empty() {
+        let faucets: Table = cast_config
+            .faucets
+            .iter()
+            .map(|(network, url)| (network.clone(), Value::from(url.clone())))
+            .collect();
+        config.insert("faucets".into(), faucets.into());
+    }
+
+    let mut sncast = Table::new();
+    match profile {
+        Some(profile) => {
+            sncast.insert(profile.clone(), config.into());
+        }
+        None => sncast = config,
+    }
+    let mut tool = Table::new();
+    tool.insert("sncast".into(), sncast.into());
+    let mut manifest = Table::new();
+    manifest.insert("tool".into(), tool.into());
+
+    Ok(toml::to_string(&manifest)?)
+}
+
+fn string_or_list(values: &[String]) -> Value {
+    match values {
+        [value] => value.clone().into(),
+        values => values.to_vec().into(),
+    }
+}
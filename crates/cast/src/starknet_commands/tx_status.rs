@@ -0,0 +1,66 @@
+use crate::helpers::response_structs::TransactionStatusResponse;
+use crate::helpers::retry_transport::RetryTransport;
+use crate::{decode_revert_reason, handle_rpc_error};
+use anyhow::Result;
+use clap::Args;
+use starknet::core::types::{
+    ExecutionResult, FieldElement, TransactionExecutionStatus, TransactionStatus,
+};
+use starknet::providers::jsonrpc::JsonRpcClient;
+use starknet::providers::Provider;
+
+#[derive(Args)]
+#[command(about = "Get the status of a transaction", long_about = None)]
+pub struct TxStatus {
+    /// Hash of the transaction
+    pub transaction_hash: FieldElement,
+}
+
+pub async fn tx_status(
+    provider: &JsonRpcClient<RetryTransport>,
+    transaction_hash: FieldElement,
+) -> Result<TransactionStatusResponse> {
+    let status = match provider.get_transaction_status(transaction_hash).await {
+        Ok(status) => status,
+        Err(error) => return handle_rpc_error(error),
+    };
+
+    let (finality_status, execution_status) = match status {
+        TransactionStatus::Received => ("received", None),
+        TransactionStatus::Rejected => ("rejected", None),
+        TransactionStatus::AcceptedOnL2(execution_status) => {
+            ("accepted_on_l2", Some(execution_status))
+        }
+        TransactionStatus::AcceptedOnL1(execution_status) => {
+            ("accepted_on_l1", Some(execution_status))
+        }
+    };
+
+    let revert_reason = if execution_status == Some(TransactionExecutionStatus::Reverted) {
+        match provider.get_transaction_receipt(transaction_hash).await {
+            Ok(receipt) => match receipt.execution_result() {
+                ExecutionResult::Reverted { reason } => Some(decode_revert_reason(reason)),
+                ExecutionResult::Succeeded => None,
+            },
+            Err(error) => return handle_rpc_error(error),
+        }
+    } else {
+        None
+    };
+    // printed to stderr, so that it is noticed when the output is processed by scripts
+    if let Some(reason) = &revert_reason {
+        eprintln!("Transaction has been reverted: {reason}");
+    }
+
+    Ok(TransactionStatusResponse {
+        finality_status: finality_status.to_string(),
+        execution_status: execution_status.map(|status| {
+            match status {
+                TransactionExecutionStatus::Succeeded => "succeeded",
+                TransactionExecutionStatus::Reverted => "reverted",
+            }
+            .to_string()
+        }),
+        revert_reason,
+    })
+}
@@ -0,0 +1,328 @@
+use crate::helpers::constants::VERIFIER_API_URLS;
+use crate::helpers::response_structs::VerifyResponse;
+use crate::helpers::scarb_utils::get_scarb_manifest;
+use crate::ValidatedWaitParams;
+use anyhow::{anyhow, bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+use starknet::core::types::FieldElement;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[derive(Args)]
+#[command(about = "Verify the source code of a contract on a block explorer", long_about = None)]
+pub struct Verify {
+    /// Address of the contract to verify
+    #[clap(short = 'a', long, conflicts_with = "class_hash")]
+    pub contract_address: Option<FieldElement>,
+
+    /// Class hash of the contract to verify
+    #[clap(short = 'g', long)]
+    pub class_hash: Option<FieldElement>,
+
+    /// Name of the contract in the Scarb project (the part after the mod keyword)
+    #[clap(short = 'c', long = "contract-name")]
+    pub contract_name: String,
+
+    /// Block explorer used to verify the contract
+    #[clap(long, value_enum)]
+    pub verifier: Verifier,
+
+    /// URL of the verification API, used instead of the API of the verifier for the current network
+    #[clap(long)]
+    pub verifier_url: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verifier {
+    Voyager,
+    Starkscan,
+}
+
+impl Display for Verifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verifier::Voyager => write!(f, "voyager"),
+            Verifier::Starkscan => write!(f, "starkscan"),
+        }
+    }
+}
+
+// Both verifiers are called through the same job-based API:
+// `POST {api_url}/verify` with a `VerificationRequest` body returns a `VerificationJob`,
+// then `GET {api_url}/verify/job/{job_id}` is polled for the `VerificationStatus` until it is final
+
+#[derive(Serialize)]
+struct VerificationRequest {
+    contract_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contract_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class_hash: Option<String>,
+    files: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct VerificationJob {
+    job_id: String,
+}
+
+#[derive(Deserialize)]
+struct VerificationStatus {
+    status: String,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+pub async fn verify(
+    contract_address: Option<FieldElement>,
+    class_hash: Option<FieldElement>,
+    contract_name: String,
+    verifier: Verifier,
+    api_url: &str,
+    path_to_scarb_toml: &Option<Utf8PathBuf>,
+    wait_params: ValidatedWaitParams,
+) -> Result<VerifyResponse> {
+    if contract_address.is_none() && class_hash.is_none() {
+        bail!("Either --contract-address or --class-hash must be passed");
+    }
+
+    let manifest_path = match path_to_scarb_toml {
+        Some(path) => path.clone(),
+        None => get_scarb_manifest().context("Failed to obtain manifest path from scarb")?,
+    };
+    let files = collect_project_files(&manifest_path)?;
+
+    let request = VerificationRequest {
+        contract_name,
+        contract_address: contract_address.map(|address| format!("{address:#x}")),
+        class_hash: class_hash.map(|class_hash| format!("{class_hash:#x}")),
+        files,
+    };
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{api_url}/verify"))
+        .header("Content-Type", "application/json")
+        .body(serde_json::to_string(&request)?)
+        .send()
+        .await
+        .with_context(|| format!("Failed to send verification request to {verifier}"))?;
+    let body = response.text().await?;
+    let job: VerificationJob = serde_json::from_str(&body)
+        .with_context(|| format!("Unexpected response from {verifier}: {body}"))?;
+
+    for i in (1..=wait_params.get_retries()).rev() {
+        let body = client
+            .get(format!("{api_url}/verify/job/{}", job.job_id))
+            .send()
+            .await
+            .with_context(|| format!("Failed to get verification status from {verifier}"))?
+            .text()
+            .await?;
+        let status: VerificationStatus = serde_json::from_str(&body)
+            .with_context(|| format!("Unexpected response from {verifier}: {body}"))?;
+
+        match status.status.as_str() {
+            "success" | "verified" => {
+                return Ok(VerifyResponse {
+                    verifier: verifier.to_string(),
+                    job_id: job.job_id,
+                    status: "verified".to_string(),
+                    message: status.message,
+                })
+            }
+            "failed" | "fail" | "error" => {
+                bail!(
+                    "Verification of the contract failed: {}",
+                    status.message.unwrap_or(status.status)
+                )
+            }
+            _ => {}
+        }
+
+        eprintln!(
+            "Waiting for the verification to finish, current status: {} ({}s left until timeout)",
+            status.status,
+            wait_params.remaining_time(i)
+        );
+        sleep(Duration::from_secs(wait_params.get_retry_interval().into())).await;
+    }
+
+    bail!(
+        "Verification (job id {}) did not finish within {}s, check its status on {verifier}",
+        job.job_id,
+        wait_params.get_timeout()
+    )
+}
+
+pub fn verifier_api_url(verifier: Verifier, network_name: &str) -> Result<&'static str> {
+    VERIFIER_API_URLS
+        .iter()
+        .find(|(name, network, _)| *name == verifier.to_string() && *network == network_name)
+        .map(|(_, _, url)| *url)
+        .ok_or_else(|| {
+            anyhow!("Verification with {verifier} is not supported on network {network_name}")
+        })
+}
+
+/// Returns the manifest and all Cairo files of the project, keyed by their paths relative to the project root
+fn collect_project_files(manifest_path: &Utf8Path) -> Result<BTreeMap<String, String>> {
+    let project_root = manifest_path
+        .parent()
+        .ok_or_else(|| anyhow!("Invalid manifest path {manifest_path}"))?;
+
+    let mut files = BTreeMap::new();
+    files.insert(
+        "Scarb.toml".to_string(),
+        fs::read_to_string(manifest_path)
+            .with_context(|| format!("Failed to read {manifest_path}"))?,
+    );
+    let lock_path = project_root.join("Scarb.lock");
+    if lock_path.exists() {
+        files.insert("Scarb.lock".to_string(), fs::read_to_string(&lock_path)?);
+    }
+    collect_cairo_files(project_root, project_root, &mut files)?;
+
+    Ok(files)
+}
+
+fn collect_cairo_files(
+    project_root: &Utf8Path,
+    directory: &Utf8Path,
+    files: &mut BTreeMap<String, String>,
+) -> Result<()> {
+    for entry in directory
+        .read_dir_utf8()
+        .with_context(|| format!("Failed to read directory {directory}"))?
+    {
+        let path = entry?.into_path();
+        if path.is_dir() {
+            // build artifacts are not part of the sources
+            if path.file_name() != Some("target") {
+                collect_cairo_files(project_root, &path, files)?;
+            }
+        } else if path.extension() == Some("cairo") {
+            let relative_path = path.strip_prefix(project_root)?.as_str().replace('\\', "/");
+            files.insert(
+                relative_path,
+                fs::read_to_string(&path).with_context(|| format!("Failed to read {path}"))?,
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect_project_files, verifier_api_url, verify, Verifier};
+    use crate::helpers::mock_server::MockServer;
+    use crate::ValidatedWaitParams;
+    use camino::Utf8PathBuf;
+    use starknet::core::types::FieldElement;
+
+    #[test]
+    fn test_collect_project_files() {
+        let files =
+            collect_project_files(&Utf8PathBuf::from("tests/data/contracts/map/Scarb.toml"))
+                .unwrap();
+
+        assert!(files["Scarb.toml"].contains("name = \"map\""));
+        assert!(files.contains_key("src/lib.cairo"));
+        assert!(files.keys().all(|path| !path.starts_with("target")));
+    }
+
+    #[test]
+    fn test_verifier_api_url_unsupported_network() {
+        let error = verifier_api_url(Verifier::Voyager, "SN_DEVNET").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Verification with voyager is not supported on network SN_DEVNET"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_polls_job_status() {
+        let server = MockServer::start(vec![
+            ("/verify", vec![r#"{"job_id": "123"}"#]),
+            (
+                "/verify/job/123",
+                vec![
+                    r#"{"status": "pending"}"#,
+                    r#"{"status": "verified", "message": "Contract verified"}"#,
+                ],
+            ),
+        ]);
+
+        let response = verify(
+            Some(FieldElement::from_hex_be("0x123").unwrap()),
+            None,
+            "Map".to_string(),
+            Verifier::Voyager,
+            server.url(),
+            &Some(Utf8PathBuf::from("tests/data/contracts/map/Scarb.toml")),
+            ValidatedWaitParams::new(1, 5).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.job_id, "123");
+        assert_eq!(response.status, "verified");
+        assert_eq!(response.message.as_deref(), Some("Contract verified"));
+
+        let requests = server.requests();
+        let paths: Vec<_> = requests
+            .iter()
+            .map(|request| (request.method.as_str(), request.path.as_str()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("POST", "/verify"),
+                ("GET", "/verify/job/123"),
+                ("GET", "/verify/job/123")
+            ]
+        );
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["contract_name"], "Map");
+        assert_eq!(body["contract_address"], "0x123");
+        assert!(body.get("class_hash").is_none());
+        assert!(body["files"]["Scarb.toml"]
+            .as_str()
+            .unwrap()
+            .contains("name = \"map\""));
+    }
+
+    #[tokio::test]
+    async fn test_verify_failed() {
+        let server = MockServer::start(vec![
+            ("/verify", vec![r#"{"job_id": "123"}"#]),
+            (
+                "/verify/job/123",
+                vec![r#"{"status": "failed", "message": "Compilation failed"}"#],
+            ),
+        ]);
+
+        let error = verify(
+            None,
+            Some(FieldElement::from_hex_be("0x456").unwrap()),
+            "Map".to_string(),
+            Verifier::Starkscan,
+            server.url(),
+            &Some(Utf8PathBuf::from("tests/data/contracts/map/Scarb.toml")),
+            ValidatedWaitParams::new(1, 5).unwrap(),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Verification of the contract failed: Compilation failed"
+        );
+    }
+}
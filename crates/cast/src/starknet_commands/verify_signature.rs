@@ -0,0 +1,74 @@
+use crate::handle_rpc_error;
+use crate::helpers::response_structs::VerifySignatureResponse;
+use crate::helpers::retry_transport::RetryTransport;
+use anyhow::{Context, Result};
+use clap::Args;
+use starknet::core::types::{BlockId, FieldElement, FunctionCall, StarknetError};
+use starknet::core::utils::{cairo_short_string_to_felt, get_selector_from_name};
+use starknet::providers::{
+    JsonRpcClient, MaybeUnknownErrorCode, Provider, ProviderError, StarknetErrorWithMessage,
+};
+
+#[derive(Args)]
+#[command(about = "Check whether a signature is valid for an account contract", long_about = None)]
+pub struct VerifySignature {
+    /// Hash which was signed, e.g. a transaction hash
+    #[clap(long)]
+    pub hash: FieldElement,
+
+    /// Signature to verify, usually its r and s values
+    #[clap(long, num_args = 1.., required = true)]
+    pub signature: Vec<FieldElement>,
+
+    /// Address of the account contract; if not passed, address of the account passed with `--account` is used
+    #[clap(long)]
+    pub address: Option<FieldElement>,
+
+    /// Block identifier on which the signature should be checked.
+    /// Possible values: pending, latest, block hash (0x prefixed string)
+    /// and block number (u64)
+    #[clap(short, long, default_value = "pending")]
+    pub block_id: String,
+}
+
+/// Calls `is_valid_signature` of the account contract. Accounts return `'VALID'` (SNIP-6) or `1` for valid signatures,
+/// while invalid ones either return `0` or make the call fail, in which case the error is reported as the reason
+pub async fn verify_signature(
+    address: FieldElement,
+    hash: FieldElement,
+    signature: &[FieldElement],
+    provider: &JsonRpcClient<RetryTransport>,
+    block_id: &BlockId,
+) -> Result<VerifySignatureResponse> {
+    let mut calldata = vec![hash, FieldElement::from(signature.len())];
+    calldata.extend_from_slice(signature);
+    let function_call = FunctionCall {
+        contract_address: address,
+        entry_point_selector: get_selector_from_name("is_valid_signature")
+            .context("Failed to convert entry point selector to FieldElement")?,
+        calldata,
+    };
+
+    match provider.call(function_call, block_id).await {
+        Ok(response) => {
+            let valid_value = cairo_short_string_to_felt("VALID")
+                .context("Failed to convert 'VALID' to FieldElement")?;
+            let valid = match response[..] {
+                [value] => value == valid_value || value == FieldElement::ONE,
+                _ => false,
+            };
+            Ok(VerifySignatureResponse {
+                valid,
+                reason: None,
+            })
+        }
+        Err(ProviderError::StarknetError(StarknetErrorWithMessage {
+            code: MaybeUnknownErrorCode::Known(StarknetError::ContractError),
+            message,
+        })) => Ok(VerifySignatureResponse {
+            valid: false,
+            reason: Some(message),
+        }),
+        Err(error) => handle_rpc_error(error),
+    }
+}
@@ -47,6 +47,7 @@ pub async fn test_happy_case() {
     assert!(contents.contains("address"));
     assert!(contents.contains("salt"));
     assert!(contents.contains("class_hash"));
+    assert!(contents.contains("\"type\": \"oz\""));
 
     fs::remove_dir_all(Utf8PathBuf::from(accounts_file).parent().unwrap()).unwrap();
 }
@@ -89,6 +90,37 @@ pub async fn test_happy_case_generate_salt() {
     fs::remove_dir_all(Utf8PathBuf::from(accounts_file).parent().unwrap()).unwrap();
 }
 
+#[tokio::test]
+pub async fn test_happy_case_random_salt() {
+    let accounts_file = "./tmp-c3/accounts.json";
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "--json",
+        "account",
+        "create",
+        "--name",
+        "my_account",
+        "--salt",
+        "random",
+        "--class-hash",
+        DEVNET_OZ_CLASS_HASH,
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let salt = output["salt"].as_str().unwrap();
+
+    let contents = fs::read_to_string(accounts_file).expect("Unable to read created file");
+    let contents: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(contents["alpha-goerli"]["my_account"]["salt"], salt);
+
+    fs::remove_dir_all(Utf8PathBuf::from(accounts_file).parent().unwrap()).unwrap();
+}
+
 #[tokio::test]
 pub async fn test_happy_case_add_profile() {
     let current_dir =
@@ -123,6 +155,8 @@ pub async fn test_happy_case_add_profile() {
         .expect("Unable to read Scarb.toml");
     assert!(contents.contains("[tool.sncast.my_account]"));
     assert!(contents.contains("account = \"my_account\""));
+    assert!(contents.contains(&format!("url = \"{URL}\"")));
+    assert!(contents.contains("accounts-file = \"./accounts.json\""));
 }
 
 #[tokio::test]
@@ -206,6 +240,48 @@ pub async fn test_profile_already_exists() {
     ));
 }
 
+#[tokio::test]
+pub async fn test_profile_already_exists_overwrite() {
+    let current_dir = duplicate_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/constructor_with_params",
+        "put",
+        "21",
+    );
+    let accounts_file = "./accounts.json";
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "account",
+        "create",
+        "--name",
+        "myprofile",
+        "--add-profile",
+        "--overwrite",
+        "--class-hash",
+        DEVNET_OZ_CLASS_HASH,
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(current_dir.path())
+        .args(args);
+    let bdg = snapbox.assert();
+    let out = bdg.get_output();
+
+    let stdout_str =
+        std::str::from_utf8(&out.stdout).expect("failed to convert command output to string");
+    assert!(stdout_str.contains("add_profile: Profile successfully added to Scarb.toml"));
+
+    let contents = fs::read_to_string(current_dir.path().join("Scarb.toml"))
+        .expect("Unable to read Scarb.toml");
+    let contents: toml::Table = toml::from_str(&contents).unwrap();
+    let profile = &contents["tool"]["sncast"]["myprofile"];
+    assert_eq!(profile["url"].as_str(), Some(URL));
+    assert_eq!(profile["accounts-file"].as_str(), Some(accounts_file));
+}
+
 #[tokio::test]
 pub async fn test_account_already_exists() {
     let mut args = default_cli_args();
@@ -228,6 +304,27 @@ pub async fn test_account_already_exists() {
     "});
 }
 
+#[test]
+pub fn test_unknown_account_type() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "account",
+        "create",
+        "--name",
+        "my_account",
+        "--type",
+        "argent_multisig",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().failure().stderr_matches(indoc! {r"
+        error: invalid value 'argent_multisig' for '--type <ACCOUNT_TYPE>'
+          [possible values: oz, argent, braavos]
+        ...
+    "});
+}
+
 #[tokio::test]
 pub async fn test_happy_case_keystore() {
     let keystore_path = "my_key.json";
@@ -257,6 +354,7 @@ pub async fn test_happy_case_keystore() {
         address: 0x[..]
         max_fee: [..]
         message: Account successfully created[..]
+        salt: [..]
     "});
 
     let contents = fs::read_to_string(account_path).expect("Unable to read created file");
@@ -407,6 +505,7 @@ pub async fn test_happy_case_keystore_int_format() {
         address: [..]
         max_fee: [..]
         message: Account successfully created[..]
+        salt: [..]
     "});
 
     let contents = fs::read_to_string(account_path).expect("Unable to read created file");
@@ -448,6 +547,7 @@ pub async fn test_happy_case_keystore_hex_format() {
         address: 0x[..]
         max_fee: 0x[..]
         message: Account successfully created[..]
+        salt: [..]
     "});
 
     let contents = fs::read_to_string(account_path).expect("Unable to read created file");
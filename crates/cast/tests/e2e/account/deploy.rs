@@ -97,6 +97,42 @@ pub async fn test_happy_case_add_profile() {
     fs::remove_dir_all(created_dir).unwrap();
 }
 
+#[tokio::test]
+pub async fn test_happy_case_estimated_max_fee() {
+    let (created_dir, accounts_file) = create_account("16", false).await;
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "--json",
+        "account",
+        "deploy",
+        "--name",
+        "my_account",
+        "--class-hash",
+        DEVNET_OZ_CLASS_HASH,
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(&created_dir)
+        .args(&args);
+    let bdg = snapbox.assert().success();
+    let out = bdg.get_output();
+
+    let hash = get_transaction_hash(&out.stdout);
+    let receipt = get_transaction_receipt(hash).await;
+
+    assert!(matches!(receipt, DeployAccount(_)));
+
+    let stderr_str =
+        std::str::from_utf8(&out.stderr).expect("failed to convert command output to string");
+    assert!(stderr_str.contains("Estimated fee: "));
+
+    fs::remove_dir_all(created_dir).unwrap();
+}
+
 #[test_case("{}", "error: No accounts defined for network alpha-goerli" ; "when empty file")]
 #[test_case("{\"alpha-goerli\": {}}", "error: Account with name my_account does not exist" ; "when account name not present")]
 #[test_case("{\"alpha-goerli\": {\"my_account\" : {}}}", "error: Couldn't get private key from accounts file" ; "when private key not present")]
@@ -493,3 +529,145 @@ pub async fn test_deploy_keystore_other_args() {
 
     _ = fs::remove_file(account_path);
 }
+
+#[tokio::test]
+pub async fn test_happy_case_constructor_calldata() {
+    let (created_dir, accounts_file) = create_account("51", false).await;
+
+    let contents = fs::read_to_string(created_dir.join(accounts_file)).unwrap();
+    let items: Value = serde_json::from_str(&contents).unwrap();
+    let public_key = items["alpha-goerli"]["my_account"]["public_key"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "account",
+        "deploy",
+        "--name",
+        "my_account",
+        "--max-fee",
+        "99999999999999999",
+        "--constructor-calldata",
+        &public_key,
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(&created_dir)
+        .args(args);
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: account deploy
+        transaction_hash: [..]
+    "});
+
+    fs::remove_dir_all(created_dir).unwrap();
+}
+
+#[tokio::test]
+pub async fn test_constructor_calldata_address_mismatch() {
+    let (created_dir, accounts_file) = create_account("52", false).await;
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "account",
+        "deploy",
+        "--name",
+        "my_account",
+        "--max-fee",
+        "99999999999999999",
+        "--constructor-calldata",
+        "0x1",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(&created_dir)
+        .args(args);
+
+    snapbox.assert().success().stderr_matches(indoc! {r"
+        command: account deploy
+        error: Account my_account would be deployed at address [..] with the constructor calldata, which differs from its address [..] in the accounts file
+    "});
+
+    fs::remove_dir_all(created_dir).unwrap();
+}
+
+#[tokio::test]
+pub async fn test_saved_constructor_calldata() {
+    let (created_dir, accounts_file) = create_account("53", false).await;
+
+    let accounts_file_path = created_dir.join(accounts_file);
+    let contents = fs::read_to_string(&accounts_file_path).unwrap();
+    let mut items: Value = serde_json::from_str(&contents).unwrap();
+    items["alpha-goerli"]["my_account"]["constructor_calldata"] = serde_json::json!(["0x1"]);
+    fs::write(
+        &accounts_file_path,
+        serde_json::to_string_pretty(&items).unwrap(),
+    )
+    .unwrap();
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "account",
+        "deploy",
+        "--name",
+        "my_account",
+        "--max-fee",
+        "99999999999999999",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(&created_dir)
+        .args(args);
+
+    snapbox.assert().success().stderr_matches(indoc! {r"
+        command: account deploy
+        error: Account my_account would be deployed at address [..] with the constructor calldata, which differs from its address [..] in the accounts file
+    "});
+
+    fs::remove_dir_all(created_dir).unwrap();
+}
+
+#[tokio::test]
+pub async fn test_keystore_address_mismatch() {
+    let keystore_path = "tests/data/keystore/my_key.json";
+    let account_path = "tests/data/keystore/my_account_address_mismatch_copy.json";
+
+    let contents =
+        fs::read_to_string("tests/data/keystore/my_account_undeployed_happy_case.json").unwrap();
+    let mut items: Value = serde_json::from_str(&contents).unwrap();
+    items["deployment"]["address"] = Value::from("0x1");
+    fs::write(account_path, serde_json::to_string_pretty(&items).unwrap()).unwrap();
+    env::set_var(KEYSTORE_PASSWORD_ENV_VAR, "123");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--keystore",
+        keystore_path,
+        "--account",
+        account_path,
+        "account",
+        "deploy",
+        "--max-fee",
+        "10000000000000000",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast")).args(args);
+    snapbox.assert().stderr_matches(indoc! {r"
+        command: account deploy
+        error: Account would be deployed at address [..], which differs from its address 0x1 in the account file
+    "});
+
+    _ = fs::remove_file(account_path);
+}
@@ -0,0 +1,133 @@
+use crate::helpers::constants::{ACCOUNT_FILE_PATH, URL};
+use crate::helpers::fixtures::default_cli_args;
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use snapbox::cmd::{cargo_bin, Command};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+pub fn test_happy_case() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "account",
+        "export",
+        "--name",
+        "user1",
+        "--network",
+        "alpha-goerli",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: account export
+        address: 0xf6ecd22832b7c3713cfa7826ee309ce96a2769833f093795fafa1b8f20c48b
+        deployed: true
+        public_key: 0x17b62d16ee2b9b5ccd3320e2c0b234dfbdd1d01d09d0aa29ce164827cddf46a
+        salt: 0x14b6b215424909f34f417ddd7cbaca48de2d505d03c92467367d275e847d252
+    "});
+}
+
+#[test]
+pub fn test_private_key_redacted_in_json() {
+    let mut args = vec!["--json"];
+    args.append(&mut default_cli_args());
+    args.append(&mut vec![
+        "account",
+        "export",
+        "--name",
+        "user1",
+        "--network",
+        "alpha-goerli",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert!(output.get("private_key").is_none());
+}
+
+#[test]
+pub fn test_include_private_key() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "account",
+        "export",
+        "--name",
+        "user1",
+        "--network",
+        "alpha-goerli",
+        "--include-private-key",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast")).args(&args).stdin("Y");
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("private_key: 0xffd33878eed7767e7c546ce3fc026295"));
+
+    let snapbox = Command::new(cargo_bin!("sncast")).args(&args).stdin("n");
+    snapbox.assert().stderr_matches(indoc! {r"
+        command: account export
+        error: Export aborted
+    "});
+}
+
+#[test]
+pub fn test_output_file() {
+    let tempdir = TempDir::new().unwrap();
+    let output_file = tempdir.path().join("backup.json");
+    let output_file = output_file.to_str().unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "account",
+        "export",
+        "--name",
+        "user1",
+        "--network",
+        "alpha-goerli",
+        "--output-file",
+        output_file,
+    ]);
+
+    runner(&args).assert().success();
+
+    let contents = fs::read_to_string(output_file).unwrap();
+    let contents: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    let account = &contents["alpha-goerli"]["user1"];
+    assert_eq!(
+        account["address"],
+        "0xf6ecd22832b7c3713cfa7826ee309ce96a2769833f093795fafa1b8f20c48b"
+    );
+    assert!(account.get("private_key").is_none());
+
+    runner(&args).assert().stderr_matches(indoc! {r"
+        command: account export
+        error: File [..] already exists
+    "});
+}
+
+#[test]
+pub fn test_account_does_not_exist() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "account",
+        "export",
+        "--name",
+        "user99",
+        "--network",
+        "alpha-goerli",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().stderr_matches(indoc! {r"
+        command: account export
+        error: Account user99 not found under network alpha-goerli
+    "});
+}
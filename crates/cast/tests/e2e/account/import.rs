@@ -0,0 +1,133 @@
+use crate::helpers::constants::URL;
+use crate::helpers::fixtures::get_keystores_path;
+use cast::helpers::constants::KEYSTORE_PASSWORD_ENV_VAR;
+use indoc::indoc;
+use snapbox::cmd::{cargo_bin, Command};
+use std::fs;
+use tempfile::TempDir;
+
+fn import_args<'a>(accounts_file: &'a str, keystore: &'a str, account: &'a str) -> Vec<&'a str> {
+    vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "account",
+        "import",
+        "--name",
+        "my_imported_account",
+        "--keystore-path",
+        keystore,
+        "--account-path",
+        account,
+    ]
+}
+
+#[tokio::test]
+pub async fn test_happy_case() {
+    let tempdir = TempDir::new().unwrap();
+    let accounts_file = tempdir.path().join("accounts.json");
+    let accounts_file = accounts_file.to_str().unwrap();
+    let my_key_path = get_keystores_path("tests/data/keystore/my_key.json");
+    let my_account_path = get_keystores_path("tests/data/keystore/my_account.json");
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .env(KEYSTORE_PASSWORD_ENV_VAR, "123")
+        .args(import_args(accounts_file, &my_key_path, &my_account_path));
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: account import
+        address: 0xcce3217e4aea0ab738b55446b1b378750edfca617db549fda1ede28435206c
+        network: alpha-goerli
+    "});
+
+    let contents = fs::read_to_string(accounts_file).unwrap();
+    let contents: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    let account = &contents["alpha-goerli"]["my_imported_account"];
+    assert_eq!(
+        account["address"],
+        "0xcce3217e4aea0ab738b55446b1b378750edfca617db549fda1ede28435206c"
+    );
+    assert_eq!(
+        account["class_hash"],
+        "0x4d07e40e93398ed3c76981e72dd1fd22557a78ce36c0515f679e27f0bb5bc5f"
+    );
+    assert_eq!(account["deployed"], true);
+}
+
+#[tokio::test]
+pub async fn test_happy_case_with_aliases() {
+    let tempdir = TempDir::new().unwrap();
+    let accounts_file = tempdir.path().join("accounts.json");
+    let accounts_file = accounts_file.to_str().unwrap();
+    let my_key_path = get_keystores_path("tests/data/keystore/my_key.json");
+    let my_account_path = get_keystores_path("tests/data/keystore/my_account.json");
+
+    let args = import_args(accounts_file, &my_key_path, &my_account_path)
+        .into_iter()
+        .map(|arg| match arg {
+            "--keystore-path" => "--keystore",
+            "--account-path" => "--account-json",
+            arg => arg,
+        })
+        .collect::<Vec<_>>();
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .env(KEYSTORE_PASSWORD_ENV_VAR, "123")
+        .args(args);
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: account import
+        address: 0xcce3217e4aea0ab738b55446b1b378750edfca617db549fda1ede28435206c
+        network: alpha-goerli
+    "});
+}
+
+#[tokio::test]
+pub async fn test_account_already_exists() {
+    let tempdir = TempDir::new().unwrap();
+    let accounts_file = tempdir.path().join("accounts.json");
+    let accounts_file = accounts_file.to_str().unwrap();
+    let my_key_path = get_keystores_path("tests/data/keystore/my_key.json");
+    let my_account_path = get_keystores_path("tests/data/keystore/my_account.json");
+
+    Command::new(cargo_bin!("sncast"))
+        .env(KEYSTORE_PASSWORD_ENV_VAR, "123")
+        .args(import_args(accounts_file, &my_key_path, &my_account_path))
+        .assert()
+        .success();
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .env(KEYSTORE_PASSWORD_ENV_VAR, "123")
+        .args(import_args(accounts_file, &my_key_path, &my_account_path));
+
+    snapbox.assert().stderr_matches(indoc! {r"
+        command: account import
+        error: Account with name my_imported_account already exists in network alpha-goerli, pass --overwrite to replace it
+    "});
+
+    let mut args = import_args(accounts_file, &my_key_path, &my_account_path);
+    args.push("--overwrite");
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .env(KEYSTORE_PASSWORD_ENV_VAR, "123")
+        .args(args);
+
+    snapbox.assert().success();
+}
+
+#[tokio::test]
+pub async fn test_wrong_password() {
+    let tempdir = TempDir::new().unwrap();
+    let accounts_file = tempdir.path().join("accounts.json");
+    let accounts_file = accounts_file.to_str().unwrap();
+    let my_key_path = get_keystores_path("tests/data/keystore/my_key.json");
+    let my_account_path = get_keystores_path("tests/data/keystore/my_account.json");
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .env(KEYSTORE_PASSWORD_ENV_VAR, "456")
+        .args(import_args(accounts_file, &my_key_path, &my_account_path));
+
+    snapbox.assert().stderr_matches(indoc! {r"
+        command: account import
+        error: Failed to decrypt keystore [..]
+    "});
+}
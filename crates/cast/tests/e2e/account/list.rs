@@ -0,0 +1,162 @@
+use crate::helpers::constants::URL;
+use crate::helpers::fixtures::default_cli_args;
+use crate::helpers::runner::runner;
+use tempfile::TempDir;
+
+#[test]
+pub fn test_happy_case() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["account", "list"]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("command: account list"));
+    assert!(output.contains("alpha-goerli:"));
+    assert!(output.contains("  user1:"));
+    assert!(output.contains("    deployed: true"));
+    assert!(!output.contains("private_key"));
+}
+
+#[test]
+pub fn test_show_private_keys_json() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json",
+        "account",
+        "list",
+        "--show-private-keys",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(output["command"], "account list");
+    assert!(output["accounts"]["alpha-goerli"]["user1"]["private_key"].is_string());
+}
+
+#[test]
+pub fn test_accounts_file_does_not_exist() {
+    let args = vec![
+        "--accounts-file",
+        "nonexistent/accounts.json",
+        "--json",
+        "account",
+        "list",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(output["accounts"], serde_json::json!({}));
+}
+
+#[test]
+pub fn test_filter_by_network() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json",
+        "account",
+        "list",
+        "--network",
+        "alpha-goerli2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let networks = output["accounts"].as_object().unwrap();
+    assert_eq!(networks.len(), 1);
+    assert!(networks.contains_key("alpha-goerli2"));
+}
+
+#[test]
+pub fn test_with_nonce() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--json", "account", "list", "--with-nonce"]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let nonce = output["accounts"]["alpha-goerli"]["user1"]["nonce"]
+        .as_str()
+        .unwrap();
+    assert!(nonce.starts_with("0x"));
+    // accounts from other networks than the one of the RPC provider are not queried
+    assert!(output["accounts"]["alpha-goerli2"]
+        .as_object()
+        .unwrap()
+        .values()
+        .all(|account| account.get("nonce").is_none()));
+}
+
+#[test]
+pub fn test_with_nonce_not_deployed() {
+    let temp_dir = TempDir::new().expect("Unable to create a temporary directory");
+    let accounts_file = temp_dir.path().join("accounts.json");
+    std::fs::write(
+        &accounts_file,
+        serde_json::json!({
+            "alpha-goerli": {
+                "created": {
+                    "private_key": "0x1",
+                    "address": "0x123",
+                    "deployed": false
+                },
+                "unknown": {
+                    "private_key": "0x2",
+                    "address": "0x456"
+                }
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file.to_str().unwrap(),
+        "account",
+        "list",
+        "--with-nonce",
+    ];
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output
+        .contains("  created:\n    address: 0x123\n    deployed: false\n    nonce: not deployed"));
+    assert!(output.contains("  unknown:\n    address: 0x456\n    nonce: not deployed"));
+}
+
+#[test]
+pub fn test_output_file_quiet() {
+    let tempdir = TempDir::new().unwrap();
+    let output_file = tempdir.path().join("output.log");
+    let output_file = output_file.to_str().unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--output-file",
+        output_file,
+        "--quiet",
+        "account",
+        "list",
+        "--show-private-keys",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().clone();
+
+    assert!(output.stdout.is_empty());
+    let contents = std::fs::read_to_string(output_file).unwrap();
+    assert!(contents.starts_with("command: account list\n"));
+    assert!(contents.contains("  user1:\n"));
+    assert!(contents.contains("    private_key: 0x"));
+}
@@ -2,3 +2,6 @@ mod add;
 mod create;
 mod delete;
 mod deploy;
+mod export;
+mod import;
+mod list;
@@ -0,0 +1,52 @@
+use crate::helpers::fixtures::default_cli_args;
+use crate::helpers::runner::runner;
+
+#[test]
+fn test_happy_case_account() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user1", "balance"]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("command: balance"));
+    assert!(output.contains("balance: 0x"));
+    assert!(output.contains("balance_low: 0x"));
+    assert!(output.contains("balance_high: 0x0"));
+}
+
+#[test]
+fn test_empty_balance() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--int-format",
+        "balance",
+        "--address",
+        "0x123456789",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("balance: 0\n"));
+    assert!(output.contains("balance_low: 0\n"));
+    assert!(output.contains("balance_high: 0\n"));
+}
+
+#[test]
+fn test_token_not_deployed() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "balance",
+        "--address",
+        "0x123456789",
+        "--token",
+        "0x1",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("command: balance"));
+    assert!(output.contains("error:"));
+}
@@ -1,6 +1,12 @@
+use crate::helpers::constants::URL;
 use crate::helpers::fixtures::{default_cli_args, from_env, invoke_map_contract};
 use crate::helpers::runner::runner;
+use cast::get_provider;
+use cast::helpers::constants::{DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY};
 use indoc::indoc;
+use starknet::core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+use starknet::core::utils::get_selector_from_name;
+use starknet::providers::Provider;
 
 #[test]
 fn test_happy_case() {
@@ -26,6 +32,253 @@ fn test_happy_case() {
     "});
 }
 
+#[test]
+fn test_happy_case_calldata_file() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--calldata-file",
+        "tests/data/files/calldata.txt",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_happy_case_calldata_file_json() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--calldata-file",
+        "tests/data/files/calldata.json",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_happy_case_calldata_from_stdin() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--calldata-file",
+        "-",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args).stdin("0x0\n");
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[tokio::test]
+async fn test_happy_case_named_arguments() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    invoke_map_contract("0x4", "0x5", "user2", &contract_address).await;
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--arg",
+        "key:0x4",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x5]
+    "});
+}
+
+#[test]
+fn test_happy_case_decode() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json",
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+        "--decode",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(output["response"], "[0x0]");
+    assert_eq!(output["decoded"], "0x0");
+}
+
+#[tokio::test]
+async fn test_happy_case_decode_strings() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    invoke_map_contract("0x6", "0x455448", "user2", &contract_address).await;
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--decode-strings",
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--calldata",
+        "0x6",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x455448 ('ETH')]
+    "});
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json",
+        "--decode-strings",
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--calldata",
+        "0x6",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(output["response"]["raw"], "[0x455448]");
+    assert_eq!(output["response"]["decoded"], serde_json::json!(["ETH"]));
+}
+
+#[tokio::test]
+async fn test_raw_response_matches_provider() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    invoke_map_contract("0x7", "0x455448", "user2", &contract_address).await;
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json",
+        "--decode-strings",
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--calldata",
+        "0x7",
+        "--raw",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    let provider = get_provider(URL, DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY).unwrap();
+    let expected = provider
+        .call(
+            FunctionCall {
+                contract_address: FieldElement::from_hex_be(&contract_address).unwrap(),
+                entry_point_selector: get_selector_from_name("get").unwrap(),
+                calldata: vec![FieldElement::from_hex_be("0x7").unwrap()],
+            },
+            BlockId::Tag(BlockTag::Pending),
+        )
+        .await
+        .unwrap();
+    let expected: Vec<String> = expected.iter().map(|felt| format!("{felt:#x}")).collect();
+
+    assert_eq!(output["response"], format!("[{}]", expected.join(", ")));
+}
+
+#[test]
+fn test_raw_conflicts_with_decode() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--raw",
+        "--decode",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("cannot be used with"));
+}
+
+#[test]
+fn test_named_arguments_unknown_argument() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--arguments-json",
+        r#"{"key": "0x1", "value": "0x2"}"#,
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("Unknown argument value for get"));
+}
+
 #[tokio::test]
 async fn test_call_after_storage_changed() {
     let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
@@ -69,6 +322,60 @@ async fn test_contract_does_not_exist() {
     "});
 }
 
+#[test]
+fn test_contract_addresses_file() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let tempdir = tempfile::tempdir().unwrap();
+    let addresses_file = tempdir.path().join("addresses.txt");
+    std::fs::write(&addresses_file, format!("# map\n{contract_address}\n0x1\n")).unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json",
+        "call",
+        "--contract-addresses-file",
+        addresses_file.to_str().unwrap(),
+        "--concurrency",
+        "2",
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let results = output["results"].as_array().unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["response"], serde_json::json!(["0x0"]));
+    assert_eq!(results[1]["contract_address"], "0x1");
+    assert_eq!(results[1]["error"], "Contract not found");
+}
+
+#[test]
+fn test_contract_address_and_addresses_file_conflict() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        "0x1",
+        "--contract-addresses-file",
+        "tests/data/files/contract_addresses.txt",
+        "--function",
+        "get",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("cannot be used with"));
+    assert!(output.contains("--contract-addresses-file <CONTRACT_ADDRESSES_FILE>"));
+}
+
 #[test]
 fn test_wrong_function_name() {
     let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
@@ -89,6 +396,222 @@ fn test_wrong_function_name() {
     "});
 }
 
+#[test]
+fn test_happy_case_selector() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--selector",
+        "0x17c00f03de8b5bd58d2016b59d251c13056b989171c5852949903bc043bc27",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_selector_is_not_hashed() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    // 'get' encoded as a felt, not its selector
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--selector",
+        "0x676574",
+        "--calldata",
+        "0x0",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().stderr_matches(indoc! {r"
+        command: call
+        error: Entry point [..] not found in contract.
+    "});
+}
+
+#[test]
+fn test_function_and_selector_conflict() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--selector",
+        "0x17c00f03de8b5bd58d2016b59d251c13056b989171c5852949903bc043bc27",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains(
+        "the argument '--function <FUNCTION>' cannot be used with '--selector <SELECTOR>'"
+    ));
+}
+
+#[test]
+fn test_happy_case_abi_file() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--abi-file",
+        "tests/data/files/map_abi.json",
+        "--calldata",
+        "'key'",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        decoded: 0x0
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_abi_file_decodes_response_json() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json",
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--abi-file",
+        "tests/data/files/map_abi.json",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(output["decoded"], "0x0");
+    assert_eq!(output["response"], "[0x0]");
+}
+
+#[test]
+fn test_abi_file_wrong_number_of_arguments() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--abi-file",
+        "tests/data/files/map_abi.json",
+        "--calldata",
+        "0x0 0x1",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains(
+        "Function get expects 1 arguments, got 2; expected signature: get(key: core::felt252)"
+    ));
+}
+
+#[test]
+fn test_abi_file_unknown_function() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "gett",
+        "--abi-path",
+        "tests/data/files/map_abi.json",
+        "--calldata-file",
+        "tests/data/files/calldata.txt",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output
+        .contains("Function gett not found in the contract ABI. Available functions: put, get"));
+}
+
+#[test]
+fn test_interactive_arguments() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--abi-file",
+        "tests/data/files/map_abi.json",
+        "--interactive",
+        "--block-id",
+        "latest",
+    ]);
+
+    // the first value is not a valid felt, so it is prompted for again
+    let snapbox = runner(&args).stdin("[1, 2]\n0x0\n");
+    let output = snapbox.assert().success().get_output().clone();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stdout.contains("response: [0x0]"));
+    assert!(stderr.contains("Invalid value for key"));
+}
+
+#[test]
+fn test_interactive_requires_abi_file() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "get",
+        "--interactive",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("--abi-path <ABI_PATH>"));
+}
+
 #[test]
 fn test_wrong_calldata() {
     let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
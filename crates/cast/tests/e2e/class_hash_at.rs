@@ -0,0 +1,35 @@
+use crate::helpers::fixtures::{default_cli_args, from_env};
+use crate::helpers::runner::runner;
+use starknet::core::types::FieldElement;
+
+#[test]
+fn test_happy_case() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let class_hash = from_env("CAST_MAP_CLASS_HASH").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "class-hash-at",
+        &contract_address,
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    let class_hash = FieldElement::from_hex_be(&class_hash).unwrap();
+    assert!(output.contains("command: class-hash-at"));
+    assert!(output.contains(&format!("class_hash: {class_hash:#x}")));
+}
+
+#[test]
+fn test_contract_not_deployed() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["class-hash-at", "0x123456789"]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("command: class-hash-at"));
+    assert!(output.contains("error: No contract deployed at address 0x123456789"));
+}
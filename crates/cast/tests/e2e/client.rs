@@ -0,0 +1,31 @@
+use crate::helpers::constants::URL;
+use crate::helpers::fixtures::from_env;
+use cast::client::SncastClient;
+use cast::get_provider;
+use cast::helpers::constants::{DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY};
+use cast::helpers::scarb_utils::CastConfig;
+use starknet::core::types::FieldElement;
+use starknet::core::utils::get_selector_from_name;
+
+#[tokio::test]
+async fn test_call() {
+    let contract_address =
+        FieldElement::from_hex_be(&from_env("CAST_MAP_ADDRESS").unwrap()).unwrap();
+    let provider = get_provider(URL, DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY).unwrap();
+    let config = CastConfig {
+        default_block_id: "latest".to_string(),
+        ..Default::default()
+    };
+    let client = SncastClient::new(&provider, config);
+
+    let response = client
+        .call(
+            contract_address,
+            get_selector_from_name("get").unwrap(),
+            vec![FieldElement::ZERO],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.response, vec![FieldElement::ZERO]);
+}
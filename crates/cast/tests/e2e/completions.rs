@@ -0,0 +1,39 @@
+use crate::helpers::runner::runner;
+
+#[test]
+fn test_bash() {
+    let snapbox = runner(&["completions", "bash"]);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("_sncast()"));
+    assert!(output.contains("multicall"));
+    assert!(output.contains("--account"));
+}
+
+#[test]
+fn test_all_shells() {
+    for shell in ["bash", "zsh", "fish", "powershell", "elvish"] {
+        let snapbox = runner(&["completions", shell]);
+        let output = snapbox.assert().success().get_output().stdout.clone();
+        assert!(!output.is_empty(), "No completions generated for {shell}");
+    }
+}
+
+#[test]
+fn test_unknown_shell() {
+    let snapbox = runner(&["completions", "tcsh"]);
+    snapbox
+        .assert()
+        .failure()
+        .stderr_matches("error: invalid value 'tcsh' for '<SHELL>'\n...");
+}
+
+#[test]
+fn test_hidden_from_help() {
+    let snapbox = runner(&["--help"]);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(!output.contains("completions"));
+}
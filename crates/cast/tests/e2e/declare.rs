@@ -4,6 +4,7 @@ use crate::helpers::fixtures::{
 };
 use indoc::indoc;
 use snapbox::cmd::{cargo_bin, Command};
+use starknet::core::types::FieldElement;
 use starknet::core::types::TransactionReceipt::Declare;
 use std::fs;
 use test_case::test_case;
@@ -56,13 +57,66 @@ async fn contract_already_declared() {
         "Map",
     ];
 
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(CONTRACTS_DIR.to_string() + "/map")
+        .args(args);
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: declare
+        already_declared: true
+        class_hash: 0x[..]
+        message: Class is already declared, declaration skipped (pass --force-declare to send it anyway)
+    "});
+}
+
+#[tokio::test]
+async fn contract_already_declared_json() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "../../accounts/accounts.json",
+        "--account",
+        "user1",
+        "--json",
+        "declare",
+        "--contract-name",
+        "Map",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(CONTRACTS_DIR.to_string() + "/map")
+        .args(args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(output["already_declared"], true);
+    assert!(output["class_hash"].as_str().unwrap().starts_with("0x"));
+    assert!(output.get("transaction_hash").is_none());
+}
+
+#[tokio::test]
+async fn contract_already_declared_force() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "../../accounts/accounts.json",
+        "--account",
+        "user1",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--force-declare",
+    ];
+
     let snapbox = Command::new(cargo_bin!("sncast"))
         .current_dir(CONTRACTS_DIR.to_string() + "/map")
         .args(args);
 
     snapbox.assert().success().stderr_matches(indoc! {r"
         command: declare
-        error: Class with hash [..] is already declared.
+        error: Failed to estimate transaction fee, pass --max-fee explicitly: Class with hash [..] is already declared.
     "});
 }
 
@@ -173,3 +227,288 @@ fn scarb_no_artifacts(contract_path: &str, accounts_file_path: &str) {
         [..]Make sure you have enabled sierra and casm code generation in Scarb.toml[..]
     "});
 }
+
+#[tokio::test]
+async fn test_happy_case_from_artifact_paths() {
+    let contract_path =
+        duplicate_directory_with_salt(CONTRACTS_DIR.to_string() + "/map", "put", "3");
+    std::process::Command::new("scarb")
+        .arg("build")
+        .current_dir(contract_path.path())
+        .output()
+        .expect("Failed to build contract");
+    let sierra_path = contract_path
+        .path()
+        .join("target/dev/map_Map.contract_class.json");
+    let casm_path = contract_path
+        .path()
+        .join("target/dev/map_Map.compiled_contract_class.json");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user8",
+        "--int-format",
+        "--json",
+        "declare",
+        "--sierra-path",
+        sierra_path.to_str().unwrap(),
+        "--casm-path",
+        casm_path.to_str().unwrap(),
+        "--max-fee",
+        "99999999999999999",
+    ];
+
+    // no Scarb.toml in the current directory, artifacts are loaded from the passed paths
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(contract_path.path().join("target"))
+        .args(args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let hash = get_transaction_hash(&output);
+    let receipt = get_transaction_receipt(hash).await;
+
+    assert!(matches!(receipt, Declare(_)));
+
+    fs::remove_dir_all(contract_path).unwrap();
+}
+
+#[test]
+fn test_no_contract_passed() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "../../accounts/accounts.json",
+        "--account",
+        "user1",
+        "declare",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(CONTRACTS_DIR.to_string() + "/map")
+        .args(args);
+
+    snapbox.assert().failure().stderr_matches(indoc! {r"
+        Error: Either --contract-name, --all, --legacy or both --sierra-path and --casm-path must be passed
+    "});
+}
+
+#[test]
+fn test_missing_casm_file() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "../../accounts/accounts.json",
+        "--account",
+        "user1",
+        "declare",
+        "--sierra-file",
+        "Scarb.toml",
+        "--casm-file",
+        "target/dev/missing.compiled_contract_class.json",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(CONTRACTS_DIR.to_string() + "/map")
+        .args(args);
+
+    snapbox.assert().failure().stderr_matches(indoc! {r"
+        Error: CASM file target/dev/missing.compiled_contract_class.json does not exist
+    "});
+}
+
+#[test]
+fn test_invalid_sierra_file() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "../../accounts/accounts.json",
+        "--account",
+        "user1",
+        "declare",
+        "--sierra-path",
+        "Scarb.toml",
+        "--casm-path",
+        "Scarb.toml",
+        "--max-fee",
+        "99999999999999999",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(CONTRACTS_DIR.to_string() + "/map")
+        .args(args);
+
+    snapbox.assert().stderr_matches(indoc! {r"
+        command: declare
+        error: Invalid contract artifacts Scarb.toml and Scarb.toml: Failed to parse sierra artifact[..]
+    "});
+}
+
+#[test]
+fn test_missing_legacy_file() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "../../accounts/accounts.json",
+        "--account",
+        "user1",
+        "declare",
+        "--legacy",
+        "target/dev/missing_cairo0.json",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(CONTRACTS_DIR.to_string() + "/map")
+        .args(args);
+
+    snapbox.assert().failure().stderr_matches(indoc! {r"
+        Error: Cairo 0 contract file target/dev/missing_cairo0.json does not exist
+    "});
+}
+
+#[test]
+fn test_invalid_legacy_file() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "../../accounts/accounts.json",
+        "--account",
+        "user1",
+        "declare",
+        "--legacy",
+        "Scarb.toml",
+        "--max-fee",
+        "99999999999999999",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(CONTRACTS_DIR.to_string() + "/map")
+        .args(args);
+
+    snapbox.assert().stderr_matches(indoc! {r"
+        command: declare
+        error: Invalid Cairo 0 contract Scarb.toml: [..]
+    "});
+}
+
+#[test]
+fn test_legacy_and_contract_name_conflict() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "../../accounts/accounts.json",
+        "--account",
+        "user1",
+        "declare",
+        "--legacy",
+        "Scarb.toml",
+        "--contract-name",
+        "Map",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(CONTRACTS_DIR.to_string() + "/map")
+        .args(args);
+
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+    assert!(output.contains("'--legacy <PATH>' cannot be used with '--contract-name <CONTRACT>'"));
+}
+
+#[test]
+fn test_contract_name_and_artifact_paths_conflict() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "../../accounts/accounts.json",
+        "--account",
+        "user1",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--sierra-path",
+        "map.contract_class.json",
+        "--casm-path",
+        "map.compiled_contract_class.json",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(CONTRACTS_DIR.to_string() + "/map")
+        .args(args);
+
+    snapbox.assert().failure().stderr_matches(indoc! {r"
+        error: the argument '--contract-name <CONTRACT>' cannot be used with[..]
+        ...
+    "});
+}
+
+#[tokio::test]
+async fn test_declare_many() {
+    let contract_path =
+        duplicate_directory_with_salt(CONTRACTS_DIR.to_string() + "/map", "put", "41");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user9",
+        "--json",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--contract-name",
+        "Missing",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(contract_path.path())
+        .args(&args);
+    let output = snapbox.assert().failure().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    let results = output["results"].as_array().unwrap();
+    assert_eq!(results[0]["contract"], "Map");
+    assert_eq!(results[0]["status"], "declared");
+    assert_eq!(results[1]["contract"], "Missing");
+    assert_eq!(results[1]["status"], "failed");
+
+    let hash = FieldElement::from_hex_be(results[0]["transaction_hash"].as_str().unwrap()).unwrap();
+    let receipt = get_transaction_receipt(hash).await;
+    assert!(matches!(receipt, Declare(_)));
+
+    // classes declared before are reported with their class hash
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user9",
+        "declare",
+        "--all",
+    ];
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .current_dir(contract_path.path())
+        .args(args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("command: declare"));
+    let class_hash = results[0]["class_hash"].as_str().unwrap();
+    assert!(output.lines().any(|line| line.starts_with("Map")
+        && line.contains(class_hash)
+        && line.contains("already_declared")));
+
+    fs::remove_dir_all(contract_path).unwrap();
+}
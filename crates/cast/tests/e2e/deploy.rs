@@ -1,10 +1,13 @@
 use crate::helpers::constants::ACCOUNT;
 use crate::helpers::fixtures::{
-    default_cli_args, from_env, get_transaction_hash, get_transaction_receipt,
+    create_test_provider, default_cli_args, from_env, get_transaction_hash, get_transaction_receipt,
 };
 use crate::helpers::runner::runner;
 use indoc::indoc;
 use starknet::core::types::TransactionReceipt::Deploy;
+use starknet::core::types::{BlockId, BlockTag, FieldElement};
+use starknet::core::utils::starknet_keccak;
+use starknet::providers::Provider;
 
 #[tokio::test]
 async fn test_happy_case() {
@@ -96,6 +99,25 @@ async fn test_contract_not_declared() {
     let snapbox = runner(&args);
     let output = String::from_utf8(snapbox.assert().get_output().stderr.clone()).unwrap();
 
+    assert!(output
+        .contains("Class with hash 0x1 is not declared, declare it first with `declare` command"));
+}
+
+#[tokio::test]
+async fn test_contract_not_declared_skip_validation() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "deploy",
+        "--class-hash",
+        "0x1",
+        "--skip-validation",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().get_output().stderr.clone()).unwrap();
+
     assert!(output.contains("Transaction execution has failed."));
 }
 
@@ -145,3 +167,203 @@ fn test_too_low_max_fee() {
         error: Max fee is smaller than the minimal transaction cost (validation plus fee transfer)
     "});
 }
+
+#[test]
+fn test_dry_run() {
+    let class_hash = from_env("CAST_MAP_CLASS_HASH").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--dry-run",
+        "deploy",
+        "--class-hash",
+        &class_hash,
+        "--salt",
+        "0x3",
+        "--max-fee",
+        "99999999999999999",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("command: deploy"));
+    assert!(output.contains("message: Dry run - transaction has not been sent"));
+    assert!(output.contains("contract_address: 0x"));
+    assert!(output.contains("signature: [0x"));
+}
+
+#[tokio::test]
+async fn test_predicted_address() {
+    let class_hash = from_env("CAST_MAP_CLASS_HASH").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "--wait",
+        "deploy",
+        "--class-hash",
+        &class_hash,
+        "--salt",
+        "0x3",
+        "--unique",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().clone();
+    let stdout: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    let contract_address = stdout["contract_address"].as_str().unwrap();
+    assert!(stderr.contains(&format!(
+        "Contract will be deployed at address {contract_address}"
+    )));
+
+    let deployed_class_hash = create_test_provider()
+        .get_class_hash_at(
+            BlockId::Tag(BlockTag::Latest),
+            FieldElement::from_hex_be(contract_address).unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        deployed_class_hash,
+        FieldElement::from_hex_be(&class_hash).unwrap()
+    );
+}
+
+#[test]
+fn test_random_salt() {
+    let class_hash = from_env("CAST_MAP_CLASS_HASH").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "deploy",
+        "--class-hash",
+        &class_hash,
+        "--salt",
+        "random",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert!(output["salt"].as_str().unwrap().starts_with("0x"));
+    assert!(output["contract_address"].is_string());
+}
+
+#[test]
+fn test_invalid_salt() {
+    let class_hash = from_env("CAST_MAP_CLASS_HASH").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "deploy",
+        "--class-hash",
+        &class_hash,
+        "--salt",
+        "abc",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("Invalid salt, expected a felt or `random`"));
+}
+
+#[test]
+fn test_salt_from_label() {
+    let class_hash = from_env("CAST_MAP_CLASS_HASH").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "--dry-run",
+        "deploy",
+        "--class-hash",
+        &class_hash,
+        "--salt-from-label",
+        "treasury-v2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    let expected_salt = starknet_keccak("treasury-v2".as_bytes());
+    assert_eq!(output["salt"], format!("{expected_salt:#x}"));
+}
+
+#[test]
+fn test_salt_from_label_conflicts_with_salt() {
+    let class_hash = from_env("CAST_MAP_CLASS_HASH").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "deploy",
+        "--class-hash",
+        &class_hash,
+        "--salt",
+        "0x1",
+        "--salt-from-label",
+        "treasury-v2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("cannot be used with"));
+}
+
+#[test]
+fn test_simulate() {
+    let class_hash = from_env("CAST_MAP_CLASS_HASH").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "deploy",
+        "--class-hash",
+        &class_hash,
+        "--simulate",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert!(output["contract_address"].is_string());
+    assert!(output["trace"]["calls"].is_array());
+    assert!(output["state_diff"]["deployed_contracts"].is_array());
+    assert!(output.get("transaction_hash").is_none());
+}
+
+#[test]
+fn test_calldata_file_malformed_felt() {
+    let class_hash = from_env("CAST_MAP_CLASS_HASH").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "deploy",
+        "--class-hash",
+        &class_hash,
+        "--calldata-file",
+        "-",
+    ]);
+
+    let snapbox = runner(&args).stdin("0x1\n0x2 kapusta\n");
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("Failed to parse calldata from stdin"));
+    assert!(output.contains("Invalid felt kapusta in line 2"));
+}
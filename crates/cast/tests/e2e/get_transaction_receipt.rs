@@ -0,0 +1,51 @@
+use crate::helpers::fixtures::{default_cli_args, from_env, get_transaction_hash};
+use crate::helpers::runner::runner;
+
+#[test]
+fn test_happy_case() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "--wait",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x8 0x9",
+        "--max-fee",
+        "99999999999999999",
+    ]);
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let hash = format!("{:#x}", get_transaction_hash(&output));
+
+    let mut args = default_cli_args();
+    args.append(&mut vec!["get-transaction-receipt", &hash]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("command: get-transaction-receipt"));
+    assert!(output.contains("transaction_type: invoke"));
+    assert!(output.contains("finality_status: accepted_on_l2"));
+    assert!(output.contains("execution_status: succeeded"));
+    assert!(output.contains("actual_fee: "));
+    assert!(output.contains("events: [{"));
+}
+
+#[test]
+fn test_nonexistent_transaction() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["get-transaction-receipt", "0x1"]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("command: get-transaction-receipt"));
+    assert!(output.contains("error: Receipt of transaction 0x1 is not yet available"));
+}
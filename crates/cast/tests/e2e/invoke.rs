@@ -35,6 +35,54 @@ async fn test_happy_case() {
     assert!(matches!(receipt, Invoke(_)));
 }
 
+#[tokio::test]
+async fn test_happy_case_selector() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user9",
+        "--int-format",
+        "--json",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--selector",
+        "0x1d7377b4b2053672e38039a02d909f73c4e538c9fddbb7e97aadf700cb9a01a",
+        "--calldata",
+        "0x1 0x2",
+        "--max-fee",
+        "99999999999999999",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let hash = get_transaction_hash(&output);
+    let receipt = get_transaction_receipt(hash).await;
+
+    assert!(matches!(receipt, Invoke(_)));
+}
+
+#[test]
+fn test_function_or_selector_required() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("the following required arguments were not provided"));
+    assert!(output.contains("--function <FUNCTION>"));
+}
+
 #[tokio::test]
 async fn test_contract_does_not_exist() {
     let mut args = default_cli_args();
@@ -127,3 +175,431 @@ fn test_too_low_max_fee() {
         error: Max fee is smaller than the minimal transaction cost (validation plus fee transfer)
     "});
 }
+
+#[test]
+fn test_dry_run() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "--dry-run",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--max-fee",
+        "99999999999999999",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(output["message"], "Dry run - transaction has not been sent");
+    assert_eq!(output["version"], "0x1");
+    assert_eq!(output["max_fee"], "0x16345785d89ffff");
+    assert!(output["transaction_hash"].is_string());
+    assert!(output["signature"].is_string());
+}
+
+#[test]
+fn test_estimate_only() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--int-format",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--estimate-only",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("command: invoke"));
+    assert!(output.contains("gas_consumed: "));
+    assert!(output.contains("gas_price: "));
+    assert!(output.contains("overall_fee: "));
+    assert!(output.contains("max_fee: "));
+    assert!(!output.contains("transaction_hash"));
+}
+
+#[test]
+fn test_estimate_only_on_block() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--max-fee",
+        "99999999999999999",
+        "--estimate-only",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("overall_fee: "));
+    assert!(!output.contains("max_fee: "));
+}
+
+#[test]
+fn test_estimate_only_with_wait() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--wait",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--estimate-only",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("--estimate-only cannot be used with --wait"));
+}
+
+#[test]
+fn test_nonce_too_low() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--max-fee",
+        "99999999999999999",
+        "--nonce",
+        "0x0",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("command: invoke"));
+    assert!(output.contains("error:"));
+}
+
+#[test]
+fn test_max_fee_estimated_with_multiplier() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--fee-multiplier",
+        "2",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().clone();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stdout.contains("transaction_hash: 0x"));
+    assert!(stderr.contains("Estimated fee: "));
+    assert!(stderr.contains("(fee multiplier: 2)"));
+}
+
+#[test]
+fn test_max_fee_estimation_failed() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "invoke",
+        "--contract-address",
+        "0x1",
+        "--function",
+        "put",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("Failed to estimate transaction fee, pass --max-fee explicitly"));
+}
+
+#[test]
+fn test_max_fee_estimated_on_block_id() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x3",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().clone();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stdout.contains("transaction_hash: 0x"));
+    assert!(stderr.contains("Estimated fee: "));
+}
+
+#[test]
+fn test_invalid_block_id() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x3",
+        "--block-id",
+        "mined",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("No such block id mined!"));
+}
+
+#[test]
+fn test_json_output_with_wait_is_single_value() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "--wait",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x4",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().clone();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    let stdout: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(stdout["transaction_hash"].is_string());
+    assert!(stderr.contains("Estimated fee: "));
+}
+
+#[test]
+fn test_dry_run_with_wait() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--dry-run",
+        "--wait",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("'--dry-run' cannot be used with '--wait'"));
+}
+
+#[tokio::test]
+async fn test_happy_case_multiple_calls() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let first_call = format!("{contract_address}:put:0x21,0x3");
+    let second_call = format!("{contract_address}:put:0x22,0x4");
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "invoke",
+        "--call",
+        &first_call,
+        "--call",
+        &second_call,
+        "--max-fee",
+        "99999999999999999",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let hash = get_transaction_hash(&output);
+    let receipt = get_transaction_receipt(hash).await;
+
+    assert!(matches!(receipt, Invoke(_)));
+}
+
+#[test]
+fn test_call_conflicts_with_contract_address() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let call = format!("{contract_address}:put:0x1,0x2");
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "invoke",
+        "--call",
+        &call,
+        "--contract-address",
+        &contract_address,
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output
+        .contains("'--call <CALLS>' cannot be used with '--contract-address <CONTRACT_ADDRESS>'"));
+}
+
+#[test]
+fn test_invalid_call_format() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user2", "invoke", "--call", "0x1"]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("<CONTRACT_ADDRESS>:<FUNCTION>[:<CALLDATA>]"));
+}
+
+#[test]
+fn test_simulate() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--simulate",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert!(output["overall_fee"].is_string());
+    assert!(output["trace"]["calls"][0]["contract_address"].is_string());
+    assert!(output["state_diff"]["storage_diffs"].is_array());
+    assert!(output.get("transaction_hash").is_none());
+    assert!(output.get("revert_reason").is_none());
+}
+
+#[test]
+fn test_simulate_with_wait() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--wait",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--simulate",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("--simulate cannot be used with --wait"));
+}
+
+#[test]
+fn test_check_balance_insufficient() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--check-balance",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--max-fee",
+        "0x1000000000000000000000000",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("command: invoke"));
+    assert!(output.contains("has insufficient ETH balance to cover the max fee"));
+}
@@ -1,7 +1,9 @@
 use crate::helpers::constants::{ACCOUNT, ACCOUNT_FILE_PATH, CONTRACTS_DIR, URL};
-use crate::helpers::fixtures::{duplicate_directory_with_salt, from_env, get_keystores_path};
+use crate::helpers::fixtures::{
+    default_cli_args, duplicate_directory_with_salt, from_env, get_keystores_path,
+};
 use crate::helpers::runner::runner;
-use cast::helpers::constants::KEYSTORE_PASSWORD_ENV_VAR;
+use cast::helpers::constants::{KEYSTORE_PASSWORD_ENV_VAR, SNCAST_KEYSTORE_PASSWORD_ENV_VAR};
 use indoc::indoc;
 use snapbox::cmd::{cargo_bin, Command};
 use std::env;
@@ -151,10 +153,121 @@ async fn test_missing_url() {
     let snapbox = runner(&args);
 
     snapbox.assert().stderr_matches(indoc! {r"
-        Error: RPC url not passed nor found in Scarb.toml
+        Error: RPC url not passed nor found in Scarb.toml. Pass it with --url or set SNCAST_RPC_URL or STARKNET_RPC environment variable
     "});
 }
 
+#[tokio::test]
+async fn test_check_connection_unreachable_url() {
+    let args = vec![
+        "--url",
+        "http://127.0.0.1:1/rpc",
+        "--check-connection",
+        "call",
+        "--contract-address",
+        "0x1",
+        "--function",
+        "get",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure().get_output().stderr.clone();
+
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("Cannot reach RPC at http://127.0.0.1:1/rpc"));
+}
+
+#[tokio::test]
+async fn test_fallback_url() {
+    let args = vec![
+        "--url",
+        "http://127.0.0.1:1/rpc",
+        "--url",
+        URL,
+        "call",
+        "--contract-address",
+        "0x0",
+        "--function",
+        "doesnotmatter",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().clone();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.contains(&format!("Using RPC url {URL}")));
+    assert!(stderr.contains("command: call"));
+}
+
+#[tokio::test]
+async fn test_all_urls_unreachable() {
+    let args = vec![
+        "--url",
+        "http://127.0.0.1:1/rpc,http://127.0.0.1:2/rpc",
+        "call",
+        "--contract-address",
+        "0x0",
+        "--function",
+        "doesnotmatter",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure().get_output().clone();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.contains("All RPC urls failed"));
+    assert!(stderr.contains("http://127.0.0.1:1/rpc: Couldn't fetch chain_id"));
+    assert!(stderr.contains("http://127.0.0.1:2/rpc: Couldn't fetch chain_id"));
+}
+
+#[tokio::test]
+async fn test_json_error_from_command() {
+    let args = vec![
+        "--url",
+        URL,
+        "--json",
+        "call",
+        "--contract-address",
+        "0x1",
+        "--function",
+        "get",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(output["command"], "call");
+    assert_eq!(output["code"], "20");
+    assert!(output["error"].is_string());
+}
+
+#[tokio::test]
+async fn test_json_error_before_command() {
+    let args = vec![
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "--account",
+        ACCOUNT,
+        "--json",
+        "account",
+        "delete",
+        "--name",
+        "whatever",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(output["command"], "account delete");
+    assert_eq!(
+        output["error"],
+        "RPC url not passed nor found in Scarb.toml. Pass it with --url or set SNCAST_RPC_URL or STARKNET_RPC environment variable"
+    );
+}
+
 #[tokio::test]
 async fn test_inexistent_keystore() {
     let args = vec![
@@ -273,3 +386,403 @@ async fn test_keystore_declare() {
 
     fs::remove_dir_all(contract_path).unwrap();
 }
+
+#[tokio::test]
+async fn test_keystore_password_not_prompted_without_terminal() {
+    let address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let my_key_path = get_keystores_path("tests/data/keystore/my_key.json");
+    let my_account_path = get_keystores_path("tests/data/keystore/my_account.json");
+    let args = vec![
+        "--url",
+        URL,
+        "--keystore",
+        my_key_path.as_str(),
+        "--account",
+        my_account_path.as_str(),
+        "invoke",
+        "--contract-address",
+        &address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .env_remove(KEYSTORE_PASSWORD_ENV_VAR)
+        .env_remove(SNCAST_KEYSTORE_PASSWORD_ENV_VAR)
+        .args(args);
+    let output = String::from_utf8(snapbox.assert().get_output().stderr.clone()).unwrap();
+
+    assert!(
+        output.contains("Keystore password is required, but there is no terminal to prompt for it")
+    );
+    assert!(output.contains(SNCAST_KEYSTORE_PASSWORD_ENV_VAR));
+}
+
+#[tokio::test]
+async fn test_keystore_password_from_stdin_and_file() {
+    let address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let my_key_path = get_keystores_path("tests/data/keystore/my_key.json");
+    let my_account_path = get_keystores_path("tests/data/keystore/my_account.json");
+    let password_dir = tempfile::tempdir().unwrap();
+    let password_file = password_dir.path().join("password");
+    fs::write(&password_file, "wrong\n").unwrap();
+
+    for password_args in [
+        vec!["--password-stdin"],
+        vec!["--password-file", password_file.to_str().unwrap()],
+    ] {
+        let mut args = vec![
+            "--url",
+            URL,
+            "--keystore",
+            my_key_path.as_str(),
+            "--account",
+            my_account_path.as_str(),
+        ];
+        args.extend(password_args);
+        args.extend([
+            "invoke",
+            "--contract-address",
+            &address,
+            "--function",
+            "put",
+            "--calldata",
+            "0x1 0x2",
+        ]);
+
+        let snapbox = Command::new(cargo_bin!("sncast"))
+            .env_remove(KEYSTORE_PASSWORD_ENV_VAR)
+            .env_remove(SNCAST_KEYSTORE_PASSWORD_ENV_VAR)
+            .stdin("wrong\n")
+            .args(args);
+        let output = String::from_utf8(snapbox.assert().get_output().stderr.clone()).unwrap();
+
+        // the password is read without prompting, and it is checked against the keystore
+        assert!(output.contains("Failed to decrypt keystore"));
+    }
+}
+
+#[tokio::test]
+async fn test_password_stdin_with_calldata_file_from_stdin() {
+    let address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let my_key_path = get_keystores_path("tests/data/keystore/my_key.json");
+    let my_account_path = get_keystores_path("tests/data/keystore/my_account.json");
+    let args = vec![
+        "--url",
+        URL,
+        "--keystore",
+        my_key_path.as_str(),
+        "--account",
+        my_account_path.as_str(),
+        "--password-stdin",
+        "invoke",
+        "--contract-address",
+        &address,
+        "--function",
+        "put",
+        "--calldata-file",
+        "-",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .stdin("123\n0x1 0x2\n")
+        .args(args);
+
+    snapbox.assert().failure().stderr_matches(indoc! {r"
+        Error: --calldata-file - cannot be used with --password-stdin, as both are read from stdin
+    "});
+}
+
+#[tokio::test]
+async fn test_keystore_read_only_command_does_not_need_password() {
+    let my_key_path = get_keystores_path("tests/data/keystore/my_key.json");
+    let my_account_path = get_keystores_path("tests/data/keystore/my_account.json");
+    let args = vec![
+        "--url",
+        URL,
+        "--keystore",
+        my_key_path.as_str(),
+        "--account",
+        my_account_path.as_str(),
+        "nonce",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .env_remove(KEYSTORE_PASSWORD_ENV_VAR)
+        .env_remove(SNCAST_KEYSTORE_PASSWORD_ENV_VAR)
+        .args(args);
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: nonce
+        nonce: 0x[..]
+    "});
+}
+
+#[tokio::test]
+async fn test_output_file() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let output_file = tempdir.path().join("output.log");
+    let output_file = output_file.to_str().unwrap();
+
+    let args = vec![
+        "--url",
+        URL,
+        "--json",
+        "--output-file",
+        output_file,
+        "show-config",
+    ];
+    let snapbox = runner(&args);
+    let stdout = snapbox.assert().success().get_output().stdout.clone();
+
+    assert_eq!(fs::read(output_file).unwrap(), stdout);
+
+    let args = vec![
+        "--url",
+        URL,
+        "--output-file",
+        output_file,
+        "--output-file-append",
+        "show-config",
+    ];
+    let snapbox = runner(&args);
+    snapbox.assert().success();
+
+    let contents = fs::read_to_string(output_file).unwrap();
+    assert!(contents.starts_with('{'));
+    assert!(contents.contains("command: show-config"));
+}
+
+#[tokio::test]
+async fn test_output_file_quiet() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let output_file = tempdir.path().join("output.log");
+    let output_file = output_file.to_str().unwrap();
+
+    let args = vec![
+        "--url",
+        URL,
+        "--output-file",
+        output_file,
+        "--quiet",
+        "show-config",
+    ];
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().clone();
+
+    assert!(output.stdout.is_empty());
+    let contents = fs::read_to_string(output_file).unwrap();
+    assert!(contents.contains("command: show-config"));
+}
+
+#[tokio::test]
+async fn test_url_from_env() {
+    let address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let args = vec![
+        "call",
+        "--contract-address",
+        &address,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .env("SNCAST_RPC_URL", URL)
+        .args(args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[tokio::test]
+async fn test_url_from_cli_overrides_env() {
+    let address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let args = vec![
+        "--url",
+        URL,
+        "call",
+        "--contract-address",
+        &address,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .env("SNCAST_RPC_URL", "http://127.0.0.1:1/rpc")
+        .args(args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[tokio::test]
+async fn test_account_from_env_overrides_scarb() {
+    let args = vec![
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "--path-to-scarb-toml",
+        "tests/data/files/correct_Scarb.toml",
+        "--profile",
+        "profile1",
+        "show-config",
+    ];
+
+    let snapbox = Command::new(cargo_bin!("sncast"))
+        .env("SNCAST_ACCOUNT", ACCOUNT)
+        .args(args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains(&format!("account: {ACCOUNT}")));
+}
+
+#[tokio::test]
+async fn test_multiple_accounts_files() {
+    let accounts_files = format!("tests/data/accounts/nonexistent.json:{ACCOUNT_FILE_PATH}");
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        &accounts_files,
+        "--account",
+        ACCOUNT,
+        "nonce",
+    ];
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("nonce: 0x"));
+}
+
+#[tokio::test]
+async fn test_multiple_accounts_files_conflicting_addresses() {
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "--accounts-file",
+        "tests/data/accounts/conflicting_accounts.json",
+        "--account",
+        "user1",
+        "nonce",
+    ];
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("Account user1 is defined with different addresses in"));
+}
+
+#[tokio::test]
+async fn test_signer_command_fails() {
+    let args = vec![
+        "--url",
+        URL,
+        "--signer-command",
+        "echo 'device locked' >&2; exit 1",
+        "--account",
+        "tests/data/keystore/my_account.json",
+        "invoke",
+        "--contract-address",
+        "0x1",
+        "--function",
+        "put",
+        "--max-fee",
+        "99999999999999999",
+    ];
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stderr_matches(indoc! {r"
+        command: invoke
+        error: Failed to sign transaction: Signer command exited with [..]: device locked
+    "});
+}
+
+#[tokio::test]
+async fn test_signer_command_conflicts_with_keystore() {
+    let args = vec![
+        "--url",
+        URL,
+        "--signer-command",
+        "my-signer",
+        "--keystore",
+        "tests/data/keystore/my_key.json",
+        "--account",
+        "tests/data/keystore/my_account.json",
+        "declare",
+        "--contract-name",
+        "my_contract",
+    ];
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().failure().stderr_matches(indoc! {r"
+        error: the argument '--signer-command <SIGNER_COMMAND>' cannot be used with '--keystore <KEYSTORE>'
+        ...
+    "});
+}
+
+#[tokio::test]
+async fn test_verbose_logs_to_stderr() {
+    let args = vec![
+        "--url",
+        URL,
+        "-vv",
+        "call",
+        "--contract-address",
+        "0x0",
+        "--function",
+        "doesnotmatter",
+    ];
+
+    let snapbox = runner(&args).env_remove("SNCAST_LOG");
+    let output = snapbox.assert().success().get_output().clone();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.contains("Resolved config"));
+    assert!(stderr.contains("Sending RPC request starknet_call"));
+    assert!(stderr.contains("Command finished in"));
+    assert!(!String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("RPC request"));
+}
+
+#[test]
+fn test_color_always() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--color",
+        "always",
+        "account",
+        "export",
+        "--name",
+        "user99",
+        "--network",
+        "alpha-goerli",
+    ]);
+
+    let output = runner(&args).assert().get_output().clone();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr
+        .contains("error: \x1b[31mAccount user99 not found under network alpha-goerli\x1b[0m"));
+
+    // colors are not used by default when the output is not a terminal
+    let args: Vec<&str> = args
+        .into_iter()
+        .filter(|arg| *arg != "--color" && *arg != "always")
+        .collect();
+    let output = runner(&args).assert().get_output().clone();
+    assert!(!String::from_utf8(output.stderr).unwrap().contains('\x1b'));
+}
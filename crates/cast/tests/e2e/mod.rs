@@ -1,9 +1,18 @@
 mod account;
+mod balance;
 mod call;
+mod class_hash_at;
+mod completions;
+mod client;
 mod declare;
 mod deploy;
+mod get_transaction_receipt;
 mod invoke;
 mod main_tests;
 mod multicall;
+mod networks;
+mod nonce;
 mod script;
 mod show_config;
+mod tx_status;
+mod verify_signature;
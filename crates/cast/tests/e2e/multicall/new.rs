@@ -20,6 +20,20 @@ async fn test_happy_case_stdout() {
     assert!(stdout_str.contains(DEFAULT_MULTICALL_CONTENTS));
 }
 
+#[tokio::test]
+async fn test_happy_case_stdout_json() {
+    let mut args = default_cli_args();
+
+    args.append(&mut vec!["--json", "multicall", "new"]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(output["content"], DEFAULT_MULTICALL_CONTENTS);
+    assert!(output.get("path").is_none());
+}
+
 #[tokio::test]
 async fn test_happy_case_file() {
     let mut args = default_cli_args();
@@ -2,6 +2,7 @@ use crate::helpers::constants::MULTICALL_CONFIGS_DIR;
 use crate::helpers::fixtures::default_cli_args;
 use crate::helpers::runner::runner;
 use std::path::Path;
+use tempfile::TempDir;
 
 #[tokio::test]
 async fn test_happy_case() {
@@ -23,7 +24,10 @@ async fn test_happy_case() {
     let stdout_str =
         std::str::from_utf8(&out.stdout).expect("failed to convert command output to string");
 
-    assert!(out.stderr.is_empty());
+    let stderr_str =
+        std::str::from_utf8(&out.stderr).expect("failed to convert command output to string");
+
+    assert!(!stderr_str.contains("error"));
     assert!(stdout_str.contains("command: multicall"));
 }
 
@@ -47,10 +51,267 @@ async fn test_calldata_ids() {
     let stdout_str =
         std::str::from_utf8(&out.stdout).expect("failed to convert command output to string");
 
-    assert!(out.stderr.is_empty());
+    let stderr_str =
+        std::str::from_utf8(&out.stderr).expect("failed to convert command output to string");
+
+    assert!(!stderr_str.contains("error"));
+    assert!(stdout_str.contains("command: multicall"));
+}
+
+#[tokio::test]
+async fn test_step_references() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user6"]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_invoke_step_refs.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec!["multicall", "run", "--path", path_str]);
+
+    let snapbox = runner(&args);
+    let bdg = snapbox.assert();
+    let out = bdg.get_output();
+
+    let stdout_str =
+        std::str::from_utf8(&out.stdout).expect("failed to convert command output to string");
+
+    let stderr_str =
+        std::str::from_utf8(&out.stderr).expect("failed to convert command output to string");
+
+    assert!(!stderr_str.contains("error"));
+    assert!(stdout_str.contains("command: multicall"));
+}
+
+#[tokio::test]
+async fn test_depends_on() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user7", "--wait"]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_invoke_depends_on.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec!["multicall", "run", "--path", path_str]);
+
+    let snapbox = runner(&args);
+    let bdg = snapbox.assert();
+    let out = bdg.get_output();
+
+    let stdout_str =
+        std::str::from_utf8(&out.stdout).expect("failed to convert command output to string");
+
+    let stderr_str =
+        std::str::from_utf8(&out.stderr).expect("failed to convert command output to string");
+
+    assert!(!stderr_str.contains("error"));
+    assert!(stderr_str.contains("Sending transaction 2 of 2 with 1 call(s)"));
+    assert!(stdout_str.contains("command: multicall"));
+    assert!(stdout_str.contains("transaction_hashes"));
+}
+
+#[tokio::test]
+async fn test_continue_on_error() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--json", "--account", "user3", "--wait"]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("invoke_continue_on_error.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec![
+        "multicall",
+        "run",
+        "--path",
+        path_str,
+        "--continue-on-error",
+    ]);
+
+    let snapbox = runner(&args);
+    let out = snapbox.assert().failure().get_output().clone();
+
+    let output: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let statuses: Vec<&str> = output["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|step| step["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, vec!["succeeded", "failed", "skipped"]);
+    assert_eq!(output["steps"][1]["id"], "invalid_put");
+    assert!(output["steps"][0]["transaction_hash"].is_string());
+
+    let stderr_str =
+        std::str::from_utf8(&out.stderr).expect("failed to convert command output to string");
+    assert!(stderr_str.contains("Skipping transaction 3 of 3"));
+}
+
+#[tokio::test]
+async fn test_continue_on_error_independent_calls() {
+    let tempdir = TempDir::new().unwrap();
+    let output_file = tempdir.path().join("output.txt");
+    let output_file_str = output_file.to_str().unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user5",
+        "--wait",
+        "--output-file",
+        output_file_str,
+        "--quiet",
+    ]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("invoke_independent_continue_on_error.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec![
+        "multicall",
+        "run",
+        "--path",
+        path_str,
+        "--continue-on-error",
+    ]);
+
+    let snapbox = runner(&args);
+    let out = snapbox.assert().failure().get_output().clone();
+    assert!(out.stdout.is_empty());
+
+    let output = std::fs::read_to_string(output_file).unwrap();
+    let statuses: Vec<&str> = output
+        .lines()
+        .skip_while(|line| !line.starts_with("step"))
+        .skip(1)
+        .map(|line| line.split_whitespace().nth(2).unwrap())
+        .collect();
+    assert_eq!(statuses, vec!["succeeded", "failed", "succeeded"]);
+    assert!(output.starts_with("command: multicall run\n"));
+}
+
+#[tokio::test]
+async fn test_variables() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user6"]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_invoke_variables.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec!["multicall", "run", "--path", path_str]);
+
+    let snapbox = runner(&args).env(
+        "MULTICALL_MAP_CLASS_HASH",
+        "0x3a8b191831033ba48ee176d5dde7088e71c853002b02a1cfa5a760aa98be046",
+    );
+    let bdg = snapbox.assert();
+    let out = bdg.get_output();
+
+    let stdout_str =
+        std::str::from_utf8(&out.stdout).expect("failed to convert command output to string");
+
+    let stderr_str =
+        std::str::from_utf8(&out.stderr).expect("failed to convert command output to string");
+
+    assert!(!stderr_str.contains("error"));
     assert!(stdout_str.contains("command: multicall"));
 }
 
+#[tokio::test]
+async fn test_variables_unset() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user6"]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_invoke_variables.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec!["multicall", "run", "--path", path_str]);
+
+    let snapbox = runner(&args).env_remove("MULTICALL_MAP_CLASS_HASH");
+    let bdg = snapbox.assert();
+    let out = bdg.get_output();
+
+    let stderr_str =
+        std::str::from_utf8(&out.stderr).expect("failed to convert command output to string");
+
+    assert!(stderr_str.contains("Environment variable MULTICALL_MAP_CLASS_HASH is not set"));
+}
+
+#[tokio::test]
+async fn test_simulate() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user2"]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_invoke.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec![
+        "multicall",
+        "run",
+        "--path",
+        path_str,
+        "--simulate",
+    ]);
+
+    let snapbox = runner(&args);
+    let bdg = snapbox.assert().success();
+    let out = bdg.get_output();
+
+    let stdout_str =
+        std::str::from_utf8(&out.stdout).expect("failed to convert command output to string");
+
+    assert!(stdout_str.contains("command: multicall run"));
+    assert!(stdout_str.contains("overall_fee: "));
+    assert!(stdout_str.contains("calls: "));
+    assert!(stdout_str.contains("events: "));
+    assert!(!stdout_str.contains("reverted_call"));
+}
+
+#[tokio::test]
+async fn test_simulate_reverted_call() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user2", "--int-format"]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_succ_invoke_fail.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec![
+        "multicall",
+        "run",
+        "--path",
+        path_str,
+        "--simulate",
+    ]);
+
+    let snapbox = runner(&args);
+    let bdg = snapbox.assert().success();
+    let out = bdg.get_output();
+
+    let stdout_str =
+        std::str::from_utf8(&out.stdout).expect("failed to convert command output to string");
+
+    assert!(stdout_str.contains("reverted_call: 1"));
+    assert!(stdout_str.contains("revert_reason: "));
+}
+
 #[tokio::test]
 async fn test_invalid_path() {
     let mut args = default_cli_args();
@@ -0,0 +1,48 @@
+use crate::helpers::runner::runner;
+use indoc::indoc;
+
+#[test]
+fn test_happy_case() {
+    let snapbox = runner(&["networks"]);
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: networks
+        alpha-goerli:
+          chain_id: SN_GOERLI
+          chain_id_hex: 0x534e5f474f45524c49
+          chain_id_int: 1536727068981429685321
+          faucet_url: https://faucet.goerli.starknet.io/api/v1/faucet
+        alpha-goerli2:
+        ...
+        alpha-mainnet:
+          chain_id: SN_MAIN
+        ...
+    "});
+}
+
+#[test]
+fn test_json() {
+    let snapbox = runner(&["--json", "networks"]);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(output["command"], "networks");
+    assert_eq!(output["networks"][2]["name"], "alpha-mainnet");
+    assert_eq!(output["networks"][2]["chain_id"], "SN_MAIN");
+    assert!(output["networks"][2].get("faucet_url").is_none());
+}
+
+#[test]
+fn test_output_file_quiet() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let output_file = tempdir.path().join("output.log");
+    let output_file = output_file.to_str().unwrap();
+
+    let snapbox = runner(&["--output-file", output_file, "--quiet", "networks"]);
+    let output = snapbox.assert().success().get_output().clone();
+
+    assert!(output.stdout.is_empty());
+    let contents = std::fs::read_to_string(output_file).unwrap();
+    assert!(contents.starts_with("command: networks\nalpha-goerli:\n"));
+    assert!(contents.contains("  chain_id: SN_MAIN\n"));
+}
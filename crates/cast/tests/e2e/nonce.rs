@@ -0,0 +1,45 @@
+use crate::helpers::fixtures::default_cli_args;
+use crate::helpers::runner::runner;
+
+#[test]
+fn test_happy_case_account() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user1", "nonce"]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("command: nonce"));
+    assert!(output.contains("nonce: 0x"));
+}
+
+#[test]
+fn test_happy_case_int_format() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user1",
+        "--int-format",
+        "nonce",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("command: nonce"));
+    assert!(!output.contains("nonce: 0x"));
+}
+
+#[test]
+fn test_contract_not_deployed() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["nonce", "--address", "0x123456789"]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("command: nonce"));
+    assert!(output.contains("error: There is no contract at the specified address"));
+}
@@ -1,5 +1,6 @@
 use crate::helpers::runner::runner;
 use indoc::indoc;
+use tempfile::TempDir;
 
 #[tokio::test]
 async fn test_show_config_from_scarb_toml() {
@@ -19,6 +20,7 @@ async fn test_show_config_from_scarb_toml() {
         accounts_file_path: ../account-file
         chain_id: alpha-goerli
         profile: profile1
+        resolved_chain_id: SN_GOERLI
         rpc_url: http://127.0.0.1:5055/rpc
         scarb_path: tests/data/show_config/all_Scarb.toml
     "});
@@ -43,6 +45,7 @@ async fn test_show_config_from_cli() {
         account: /path/to/account.json
         chain_id: alpha-goerli
         keystore: ../keystore
+        resolved_chain_id: SN_GOERLI
         rpc_url: http://127.0.0.1:5055/rpc
     "});
 }
@@ -67,6 +70,7 @@ async fn test_show_config_from_cli_and_scarb() {
         accounts_file_path: ../account-file
         chain_id: alpha-goerli
         profile: profile1
+        resolved_chain_id: SN_GOERLI
         rpc_url: http://127.0.0.1:5055/rpc
         scarb_path: tests/data/show_config/all_Scarb.toml
     "});
@@ -90,6 +94,7 @@ async fn test_show_config_when_no_keystore() {
         accounts_file_path: ../account-file
         chain_id: alpha-goerli
         profile: profile1
+        resolved_chain_id: SN_GOERLI
         rpc_url: http://127.0.0.1:5055/rpc
         scarb_path: tests/data/show_config/all_Scarb.toml
     "});
@@ -110,7 +115,221 @@ async fn test_show_config_when_keystore() {
         account: /path/to/account.json
         chain_id: alpha-goerli
         keystore: ../keystore
+        resolved_chain_id: SN_GOERLI
         rpc_url: http://127.0.0.1:5055/rpc
         scarb_path: tests/data/show_config/all_Scarb.toml
     "});
 }
+
+#[tokio::test]
+async fn test_show_config_json() {
+    let args = vec![
+        "--json",
+        "--path-to-scarb-toml",
+        "tests/data/show_config/all_Scarb.toml",
+        "--profile",
+        "profile1",
+        "show-config",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(
+        output,
+        serde_json::json!({
+            "command": "show-config",
+            "account": "user1",
+            "accounts_file_path": "../account-file",
+            "chain_id": "alpha-goerli",
+            "profile": "profile1",
+            "resolved_chain_id": "SN_GOERLI",
+            "rpc_url": "http://127.0.0.1:5055/rpc",
+            "scarb_path": "tests/data/show_config/all_Scarb.toml",
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_show_config_chain_id_override() {
+    // the provider is not queried for the chain id, so an unreachable url is fine
+    let args = vec![
+        "--url",
+        "http://127.0.0.1:1/rpc",
+        "--chain-id",
+        "SN_GOERLI2",
+        "show-config",
+    ];
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: show-config
+        chain_id: alpha-goerli2
+        resolved_chain_id: SN_GOERLI2
+        rpc_url: http://127.0.0.1:1/rpc
+    "});
+}
+
+#[tokio::test]
+async fn test_show_config_toml() {
+    let args = vec![
+        "--path-to-scarb-toml",
+        "tests/data/show_config/all_Scarb.toml",
+        "--profile",
+        "profile1",
+        "show-config",
+        "--format",
+        "toml",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: toml::Table = toml::from_str(&String::from_utf8(output).unwrap()).unwrap();
+    let profile = &output["tool"]["sncast"]["profile1"];
+
+    assert_eq!(profile["url"].as_str(), Some("http://127.0.0.1:5055/rpc"));
+    assert_eq!(profile["account"].as_str(), Some("user1"));
+    assert_eq!(profile["accounts-file"].as_str(), Some("../account-file"));
+    assert_eq!(profile["default-block-id"].as_str(), Some("pending"));
+    assert!(profile.get("keystore").is_none());
+}
+
+#[tokio::test]
+async fn test_show_config_toml_omits_signer_command() {
+    let args = vec![
+        "--url",
+        "http://127.0.0.1:5055/rpc",
+        "--signer-command",
+        "my-signer --private-key 0x123",
+        "show-config",
+        "--format",
+        "toml",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with("[tool.sncast]\n"));
+    assert!(!output.contains("signer-command"));
+    assert!(!output.contains("0x123"));
+}
+
+#[tokio::test]
+async fn test_show_config_toml_output_file_quiet() {
+    let tempdir = TempDir::new().unwrap();
+    let output_file = tempdir.path().join("output.toml");
+    let output_file = output_file.to_str().unwrap();
+
+    let args = vec![
+        "--output-file",
+        output_file,
+        "--quiet",
+        "--url",
+        "http://127.0.0.1:5055/rpc",
+        "show-config",
+        "--format",
+        "toml",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    assert!(output.is_empty());
+    let contents = std::fs::read_to_string(output_file).unwrap();
+    let contents: toml::Table = toml::from_str(&contents).unwrap();
+    assert_eq!(
+        contents["tool"]["sncast"]["url"].as_str(),
+        Some("http://127.0.0.1:5055/rpc")
+    );
+}
+
+#[tokio::test]
+async fn test_show_config_toml_with_json() {
+    let args = vec![
+        "--json",
+        "--url",
+        "http://127.0.0.1:5055/rpc",
+        "show-config",
+        "--format",
+        "toml",
+    ];
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().failure().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("--json cannot be used with --format toml"));
+}
+
+#[tokio::test]
+async fn test_show_config_keystore_and_account_from_accounts_file() {
+    let args = vec![
+        "--json",
+        "--url",
+        "http://127.0.0.1:5055/rpc",
+        "--accounts-file",
+        "tests/data/accounts/accounts.json",
+        "--account",
+        "user1",
+        "--keystore",
+        "../keystore",
+        "show-config",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    let warnings = output["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0]
+        .as_str()
+        .unwrap()
+        .starts_with("Account user1 is defined in the accounts file tests/data/accounts/accounts.json, but --keystore is set"));
+}
+
+#[tokio::test]
+async fn test_show_config_keystore_and_account_from_accounts_file_warning() {
+    let args = vec![
+        "--url",
+        "http://127.0.0.1:5055/rpc",
+        "--accounts-file",
+        "tests/data/accounts/accounts.json",
+        "--account",
+        "user1",
+        "--keystore",
+        "../keystore",
+        "show-config",
+    ];
+
+    let snapbox = runner(&args);
+
+    snapbox
+        .assert()
+        .success()
+        .stderr_matches("Warning: Account user1 is defined in the accounts file [..], but --keystore is set, so it takes effect instead[..]\n");
+}
+
+#[tokio::test]
+async fn test_show_config_from_config_file() {
+    let args = vec![
+        "--config-file",
+        "tests/data/files/snfoundry.toml",
+        "--profile",
+        "profile1",
+        "show-config",
+    ];
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: show-config
+        account: user3
+        ...
+        profile: profile1
+        resolved_chain_id: SN_GOERLI
+        rpc_url: http://127.0.0.1:5055/rpc
+    "});
+}
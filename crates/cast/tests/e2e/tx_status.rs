@@ -0,0 +1,50 @@
+use crate::helpers::fixtures::{default_cli_args, from_env, get_transaction_hash};
+use crate::helpers::runner::runner;
+use indoc::indoc;
+
+#[test]
+fn test_happy_case() {
+    let contract_address = from_env("CAST_MAP_ADDRESS").unwrap();
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "--wait",
+        "invoke",
+        "--contract-address",
+        &contract_address,
+        "--function",
+        "put",
+        "--calldata",
+        "0x6 0x7",
+        "--max-fee",
+        "99999999999999999",
+    ]);
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let hash = format!("{:#x}", get_transaction_hash(&output));
+
+    let mut args = default_cli_args();
+    args.append(&mut vec!["tx-status", &hash]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: tx-status
+        execution_status: succeeded
+        finality_status: accepted_on_l2
+    "});
+}
+
+#[test]
+fn test_nonexistent_transaction() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["tx-status", "0x1"]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("command: tx-status"));
+    assert!(output.contains("error:"));
+}
@@ -0,0 +1,77 @@
+use crate::helpers::fixtures::default_cli_args;
+use crate::helpers::runner::runner;
+use starknet::core::types::FieldElement;
+use starknet::signers::SigningKey;
+
+// private key of user2 from the accounts file used in tests
+const USER2_PRIVATE_KEY: &str = "0xd55976edf8fadf692436af68f7476817";
+
+#[test]
+fn test_valid_signature() {
+    let private_key =
+        SigningKey::from_secret_scalar(FieldElement::from_hex_be(USER2_PRIVATE_KEY).unwrap());
+    let signature = private_key.sign(&FieldElement::from(123_u8)).unwrap();
+    let r = format!("{:#x}", signature.r);
+    let s = format!("{:#x}", signature.s);
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "verify-signature",
+        "--hash",
+        "0x7b",
+        "--signature",
+        &r,
+        &s,
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stdout.clone()).unwrap();
+
+    assert!(output.contains("command: verify-signature"));
+    assert!(output.contains("valid: true"));
+}
+
+#[test]
+fn test_invalid_signature() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--json",
+        "verify-signature",
+        "--hash",
+        "0x7b",
+        "--signature",
+        "0x1",
+        "0x2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(output["valid"], "false");
+}
+
+#[test]
+fn test_contract_not_deployed() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "verify-signature",
+        "--address",
+        "0x123456789",
+        "--hash",
+        "0x7b",
+        "--signature",
+        "0x1",
+        "0x2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = String::from_utf8(snapbox.assert().success().get_output().stderr.clone()).unwrap();
+
+    assert!(output.contains("command: verify-signature"));
+    assert!(output.contains("error: There is no contract at the specified address"));
+}
@@ -1,6 +1,8 @@
 use crate::helpers::constants::{ACCOUNT_FILE_PATH, CONTRACTS_DIR, DEVNET_ENV_FILE, URL};
 use camino::Utf8PathBuf;
 use cast::get_keystore_password;
+use cast::helpers::constants::{DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY};
+use cast::helpers::retry_transport::RetryTransport;
 use cast::{get_account, get_provider, parse_number};
 use primitive_types::U256;
 use serde_json::{json, Map, Value};
@@ -11,7 +13,6 @@ use starknet::core::types::FieldElement;
 use starknet::core::types::TransactionReceipt;
 use starknet::core::utils::get_contract_address;
 use starknet::core::utils::get_selector_from_name;
-use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 use starknet::signers::SigningKey;
 use std::collections::HashMap;
@@ -24,12 +25,14 @@ use tempfile::TempDir;
 use url::Url;
 
 pub async fn declare_contract(account: &str, path: &str, shortname: &str) -> FieldElement {
-    let provider = get_provider(URL).expect("Could not get the provider");
+    let provider = get_provider(URL, DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY)
+        .expect("Could not get the provider");
     let account = get_account(
         account,
-        &Utf8PathBuf::from(ACCOUNT_FILE_PATH),
+        &[Utf8PathBuf::from(ACCOUNT_FILE_PATH)],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await
     .expect("Could not get the account");
@@ -71,12 +74,14 @@ pub async fn declare_contract(account: &str, path: &str, shortname: &str) -> Fie
 pub async fn declare_deploy_contract(account: &str, path: &str, shortname: &str) {
     let class_hash = declare_contract(account, path, shortname).await;
 
-    let provider = get_provider(URL).expect("Could not get the provider");
+    let provider = get_provider(URL, DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY)
+        .expect("Could not get the provider");
     let account = get_account(
         account,
-        &Utf8PathBuf::from(ACCOUNT_FILE_PATH),
+        &[Utf8PathBuf::from(ACCOUNT_FILE_PATH)],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await
     .expect("Could not get the account");
@@ -98,12 +103,14 @@ pub async fn declare_deploy_contract(account: &str, path: &str, shortname: &str)
 }
 
 pub async fn invoke_map_contract(key: &str, value: &str, account: &str, contract_address: &str) {
-    let provider = get_provider(URL).expect("Could not get the provider");
+    let provider = get_provider(URL, DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY)
+        .expect("Could not get the provider");
     let account = get_account(
         account,
-        &Utf8PathBuf::from(ACCOUNT_FILE_PATH),
+        &[Utf8PathBuf::from(ACCOUNT_FILE_PATH)],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await
     .expect("Could not get the account");
@@ -192,9 +199,13 @@ pub async fn get_transaction_receipt(tx_hash: FieldElement) -> TransactionReceip
 }
 
 #[must_use]
-pub fn create_test_provider() -> JsonRpcClient<HttpTransport> {
+pub fn create_test_provider() -> JsonRpcClient<RetryTransport> {
     let parsed_url = Url::parse(URL).unwrap();
-    JsonRpcClient::new(HttpTransport::new(parsed_url))
+    JsonRpcClient::new(RetryTransport::new(
+        parsed_url,
+        DEFAULT_RPC_RETRIES,
+        DEFAULT_RPC_RETRY_BASE_DELAY,
+    ))
 }
 
 #[must_use]
@@ -2,26 +2,27 @@ use crate::helpers::constants::URL;
 use crate::helpers::fixtures::create_test_provider;
 
 use camino::Utf8PathBuf;
+use cast::helpers::constants::{DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY};
 use cast::{get_account, get_provider};
 use std::fs;
 use url::ParseError;
 
 #[tokio::test]
 async fn test_get_provider() {
-    let provider = get_provider(URL);
+    let provider = get_provider(URL, DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY);
     assert!(provider.is_ok());
 }
 
 #[tokio::test]
 async fn test_get_provider_invalid_url() {
-    let provider = get_provider("what");
+    let provider = get_provider("what", DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY);
     let err = provider.unwrap_err();
     assert!(err.is::<ParseError>());
 }
 
 #[tokio::test]
 async fn test_get_provider_empty_url() {
-    let provider = get_provider("");
+    let provider = get_provider("", DEFAULT_RPC_RETRIES, DEFAULT_RPC_RETRY_BASE_DELAY);
     let err = provider.unwrap_err();
     assert!(err
         .to_string()
@@ -33,9 +34,10 @@ async fn test_get_account() {
     let provider = create_test_provider();
     let account = get_account(
         "user1",
-        &Utf8PathBuf::from("tests/data/accounts/accounts.json"),
+        &[Utf8PathBuf::from("tests/data/accounts/accounts.json")],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await;
 
@@ -52,9 +54,12 @@ async fn test_get_account_no_file() {
     let provider = create_test_provider();
     let account = get_account(
         "user1",
-        &Utf8PathBuf::from("tests/data/accounts/nonexistentfile.json"),
+        &[Utf8PathBuf::from(
+            "tests/data/accounts/nonexistentfile.json",
+        )],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await;
     let err = account.unwrap_err();
@@ -68,9 +73,10 @@ async fn test_get_account_invalid_file() {
     let provider = create_test_provider();
     let account = get_account(
         "user1",
-        &Utf8PathBuf::from("tests/data/accounts/invalid_format.json"),
+        &[Utf8PathBuf::from("tests/data/accounts/invalid_format.json")],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await;
     let err = account.unwrap_err();
@@ -82,9 +88,10 @@ async fn test_get_account_no_account() {
     let provider = create_test_provider();
     let account = get_account(
         "",
-        &Utf8PathBuf::from("tests/data/accounts/accounts.json"),
+        &[Utf8PathBuf::from("tests/data/accounts/accounts.json")],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await;
     let err = account.unwrap_err();
@@ -98,9 +105,10 @@ async fn test_get_account_no_user_for_network() {
     let provider = create_test_provider();
     let account = get_account(
         "user10",
-        &Utf8PathBuf::from("tests/data/accounts/accounts.json"),
+        &[Utf8PathBuf::from("tests/data/accounts/accounts.json")],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await;
     let err = account.unwrap_err();
@@ -114,9 +122,12 @@ async fn test_get_account_failed_to_convert_field_elements() {
     let provider = create_test_provider();
     let account1 = get_account(
         "with_wrong_private_key",
-        &Utf8PathBuf::from("tests/data/accounts/faulty_accounts.json"),
+        &[Utf8PathBuf::from(
+            "tests/data/accounts/faulty_accounts.json",
+        )],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await;
     let err1 = account1.unwrap_err();
@@ -126,9 +137,12 @@ async fn test_get_account_failed_to_convert_field_elements() {
 
     let account2 = get_account(
         "with_wrong_address",
-        &Utf8PathBuf::from("tests/data/accounts/faulty_accounts.json"),
+        &[Utf8PathBuf::from(
+            "tests/data/accounts/faulty_accounts.json",
+        )],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await;
     let err2 = account2.unwrap_err();
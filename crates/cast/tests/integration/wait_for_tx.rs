@@ -3,8 +3,10 @@ use crate::helpers::{
     fixtures::{create_test_provider, from_env},
 };
 use camino::Utf8PathBuf;
-use cast::{get_account, helpers::constants::DEFAULT_RETRIES};
-use cast::{handle_wait_for_tx, parse_number, wait_for_tx};
+use cast::get_account;
+use cast::{
+    handle_wait_for_tx, parse_number, wait_for_tx, ValidatedWaitParams, WaitForTx, WaitTimeoutError,
+};
 use starknet::contract::ContractFactory;
 use starknet::core::types::FieldElement;
 
@@ -12,7 +14,12 @@ use starknet::core::types::FieldElement;
 async fn test_happy_path() {
     let provider = create_test_provider();
     let hash = from_env("CAST_MAP_DECLARE_HASH").unwrap();
-    let res = wait_for_tx(&provider, parse_number(&hash).unwrap(), DEFAULT_RETRIES).await;
+    let res = wait_for_tx(
+        &provider,
+        parse_number(&hash).unwrap(),
+        ValidatedWaitParams::default(),
+    )
+    .await;
 
     assert!(res.is_ok());
     assert!(matches!(res.unwrap(), "Transaction accepted"));
@@ -23,9 +30,10 @@ async fn test_rejected_transaction() {
     let provider = create_test_provider();
     let account = get_account(
         ACCOUNT,
-        &Utf8PathBuf::from(ACCOUNT_FILE_PATH),
+        &[Utf8PathBuf::from(ACCOUNT_FILE_PATH)],
         &provider,
         &Utf8PathBuf::default(),
+        "",
     )
     .await
     .expect("Could not get the account");
@@ -44,14 +52,14 @@ async fn test_rejected_transaction() {
 
 #[tokio::test]
 #[should_panic(
-    expected = "Could not get transaction with hash: 0x123456789. Transaction rejected or not received."
+    expected = "Could not get transaction with hash: 0x123456789. Last known status: not received."
 )]
 async fn test_wait_for_nonexistent_tx() {
     let provider = create_test_provider();
     wait_for_tx(
         &provider,
         parse_number("0x123456789").expect("Could not parse a number"),
-        3,
+        ValidatedWaitParams::new(1, 3).unwrap(),
     )
     .await
     .unwrap();
@@ -61,7 +69,33 @@ async fn test_wait_for_nonexistent_tx() {
 async fn test_happy_path_handle_wait_for_tx() {
     let provider = create_test_provider();
     let hash = from_env("CAST_MAP_DECLARE_HASH").unwrap();
-    let res = handle_wait_for_tx(&provider, parse_number(&hash).unwrap(), 1, true).await;
+    let res = handle_wait_for_tx(
+        &provider,
+        parse_number(&hash).unwrap(),
+        1,
+        WaitForTx {
+            wait: true,
+            wait_params: ValidatedWaitParams::default(),
+        },
+    )
+    .await;
 
     assert!(matches!(res, Ok(1)));
 }
+
+#[tokio::test]
+async fn test_wait_for_nonexistent_tx_timeout_message() {
+    let provider = create_test_provider();
+    let err = wait_for_tx(
+        &provider,
+        parse_number("0x123456789").expect("Could not parse a number"),
+        ValidatedWaitParams::new(1, 2).unwrap(),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(err
+        .to_string()
+        .contains("Waiting timed out after 2s, check the transaction status later"));
+    assert!(err.downcast_ref::<WaitTimeoutError>().is_some());
+}